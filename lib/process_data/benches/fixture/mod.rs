@@ -0,0 +1,171 @@
+//! A synthetic procfs that mimics the files `ProcessData` reads for every process, including a few threads and file
+//! descriptors each. Its PIDs are far above the largest possible `pid_max`, so nothing in it can be mistaken for a real
+//! process. It's shared by the `procfs` benchmark and the tests of `ProcfsScanner`.
+
+use std::{fs, io, os::unix::fs::symlink, path::Path};
+
+pub const THREADS_PER_PROCESS: usize = 4;
+
+const FDS_PER_PROCESS: usize = 16;
+
+/// The largest `pid_max` is 2^22, see man proc_sys_kernel(5)
+pub const FIRST_PID: usize = 10_000_000;
+
+/// Creates `processes` processes in `root`, starting at `FIRST_PID`
+pub fn create(root: &Path, processes: usize) -> io::Result<()> {
+    for i in 0..processes {
+        let pid = FIRST_PID + i;
+        let dir = root.join(pid.to_string());
+
+        fs::create_dir_all(dir.join("task"))?;
+        fs::create_dir(dir.join("fd"))?;
+        fs::create_dir(dir.join("fdinfo"))?;
+
+        fs::write(dir.join("stat"), stat(pid, THREADS_PER_PROCESS))?;
+        fs::write(dir.join("statm"), "5000 1200 800 200 0 900 0\n")?;
+        fs::write(dir.join("status"), status(pid))?;
+        fs::write(dir.join("comm"), "bench-worker\n")?;
+        fs::write(
+            dir.join("cmdline"),
+            format!("/usr/bin/bench-worker\0--id\0{i}\0"),
+        )?;
+        fs::write(dir.join("io"), IO)?;
+        fs::write(
+            dir.join("cgroup"),
+            "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-bench\\x2dworker-1234.scope\n",
+        )?;
+        fs::write(dir.join("smaps_rollup"), SMAPS_ROLLUP)?;
+        fs::write(dir.join("oom_score"), "666\n")?;
+        fs::write(dir.join("oom_score_adj"), "0\n")?;
+
+        for thread in 0..THREADS_PER_PROCESS {
+            let tid = if thread == 0 {
+                pid
+            } else {
+                FIRST_PID + processes + i * THREADS_PER_PROCESS + thread
+            };
+
+            let task_dir = dir.join("task").join(tid.to_string());
+            fs::create_dir(&task_dir)?;
+            fs::write(task_dir.join("stat"), stat(tid, THREADS_PER_PROCESS))?;
+        }
+
+        for fd in 0..FDS_PER_PROCESS {
+            symlink("/dev/null", dir.join("fd").join(fd.to_string()))?;
+            fs::write(
+                dir.join("fdinfo").join(fd.to_string()),
+                "pos:\t0\nflags:\t02100002\nmnt_id:\t25\nino:\t5\n",
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// All 52 fields of man proc_pid_stat(5)
+fn stat(pid: usize, threads: usize) -> String {
+    format!(
+        "{pid} (bench-worker) S 1 {pid} {pid} 0 -1 4194560 1234 0 12 0 250 75 0 0 20 0 {threads} 0 6203 \
+        123456789 1200 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 17 3 0 0 0 0 0 0 0 0 0 0 0 0 0\n"
+    )
+}
+
+fn status(pid: usize) -> String {
+    format!(
+        "Name:\tbench-worker
+Umask:\t0022
+State:\tS (sleeping)
+Tgid:\t{pid}
+Ngid:\t0
+Pid:\t{pid}
+PPid:\t1
+TracerPid:\t0
+Uid:\t1000\t1000\t1000\t1000
+Gid:\t1000\t1000\t1000\t1000
+FDSize:\t64
+Groups:\t10 1000
+NStgid:\t{pid}
+NSpid:\t{pid}
+NSpgid:\t{pid}
+NSsid:\t{pid}
+Kthread:\t0
+VmPeak:\t   20480 kB
+VmSize:\t   20000 kB
+VmLck:\t       0 kB
+VmPin:\t       0 kB
+VmHWM:\t    4800 kB
+VmRSS:\t    4800 kB
+RssAnon:\t    1600 kB
+RssFile:\t    3200 kB
+RssShmem:\t       0 kB
+VmData:\t    1200 kB
+VmStk:\t     132 kB
+VmExe:\t     900 kB
+VmLib:\t    2000 kB
+VmPTE:\t      60 kB
+VmSwap:\t     128 kB
+HugetlbPages:\t       0 kB
+CoreDumping:\t0
+THP_enabled:\t1
+untag_mask:\t0xffffffffffffffff
+Threads:\t{THREADS_PER_PROCESS}
+SigQ:\t0/62688
+SigPnd:\t0000000000000000
+ShdPnd:\t0000000000000000
+SigBlk:\t0000000000010000
+SigIgn:\t0000000000380004
+SigCgt:\t000000004b817efb
+CapInh:\t0000000000000000
+CapPrm:\t0000000000000000
+CapEff:\t0000000000000000
+CapBnd:\t000001ffffffffff
+CapAmb:\t0000000000000000
+NoNewPrivs:\t0
+Seccomp:\t0
+Seccomp_filters:\t0
+Speculation_Store_Bypass:\tthread vulnerable
+SpeculationIndirectBranch:\tconditional enabled
+Cpus_allowed:\tffff
+Cpus_allowed_list:\t0-15
+Mems_allowed:\t00000000,00000001
+Mems_allowed_list:\t0
+voluntary_ctxt_switches:\t150
+nonvoluntary_ctxt_switches:\t545
+"
+    )
+}
+
+const IO: &str = "rchar: 323934931
+wchar: 323929600
+syscr: 632687
+syscw: 632675
+read_bytes: 4096
+write_bytes: 323932160
+cancelled_write_bytes: 0
+";
+
+const SMAPS_ROLLUP: &str =
+    "5581a4c1d000-7ffd3b5f9000 ---p 00000000 00:00 0                          [rollup]
+Rss:                4800 kB
+Pss:                2100 kB
+Pss_Dirty:          1200 kB
+Pss_Anon:           1600 kB
+Pss_File:            500 kB
+Pss_Shmem:             0 kB
+Shared_Clean:       3000 kB
+Shared_Dirty:          0 kB
+Private_Clean:       200 kB
+Private_Dirty:      1600 kB
+Referenced:         4800 kB
+Anonymous:          1600 kB
+KSM:                   0 kB
+LazyFree:              0 kB
+AnonHugePages:         0 kB
+ShmemPmdMapped:        0 kB
+FilePmdMapped:         0 kB
+Shared_Hugetlb:        0 kB
+Private_Hugetlb:       0 kB
+Swap:                128 kB
+SwapPss:             128 kB
+Locked:                0 kB
+";
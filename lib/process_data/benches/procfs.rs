@@ -0,0 +1,75 @@
+//! Measures how long scanning a synthetic procfs takes. Usage: `cargo bench --bench procfs -- [processes]`
+
+mod fixture;
+
+use std::{
+    fs,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use process_data::procfs::ProcfsScanner;
+
+use fixture::{FIRST_PID, THREADS_PER_PROCESS};
+
+const DEFAULT_PROCESSES: usize = 5000;
+
+const ITERATIONS: usize = 10;
+
+fn main() {
+    // cargo passes `--bench` to benchmarks, so only look for a number
+    let processes = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(DEFAULT_PROCESSES);
+
+    let root = std::env::temp_dir().join(format!("resources-procfs-{}", std::process::id()));
+
+    println!(
+        "Creating synthetic procfs with {processes} processes of {THREADS_PER_PROCESS} threads each, starting at PID \
+        {FIRST_PID}, in {}",
+        root.display()
+    );
+    fixture::create(&root, processes).expect("unable to create synthetic procfs");
+
+    let mut scanner = ProcfsScanner::new(&root);
+    let scanned = scanner
+        .scan()
+        .expect("unable to scan synthetic procfs")
+        .len();
+    assert_eq!(
+        scanned, processes,
+        "not every synthetic process was scanned"
+    );
+
+    measure("reused scanner", processes, || {
+        black_box(scanner.scan().unwrap());
+    });
+
+    measure("new scanner for every scan", processes, || {
+        black_box(ProcfsScanner::new(&root).scan().unwrap());
+    });
+
+    fs::remove_dir_all(&root).expect("unable to remove synthetic procfs");
+}
+
+fn measure<F: FnMut()>(name: &str, processes: usize, mut scan: F) {
+    let mut durations: Vec<Duration> = (0..ITERATIONS)
+        .map(|_| {
+            let start = Instant::now();
+            scan();
+            start.elapsed()
+        })
+        .collect();
+
+    durations.sort();
+
+    let median = durations[ITERATIONS / 2];
+
+    println!(
+        "{name}: {median:.2?} per scan (min {:.2?}, max {:.2?}), {:.2?} per process",
+        durations[0],
+        durations[ITERATIONS - 1],
+        median / processes as u32
+    );
+}
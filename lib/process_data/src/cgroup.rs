@@ -0,0 +1,312 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+    sync::LazyLock,
+};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::fs_root::sysfs;
+
+static CGROUP_ROOT: LazyLock<PathBuf> = LazyLock::new(|| sysfs("fs/cgroup"));
+
+/// A limit found in a cgroup v2 interface file, these may either be a number or `max`
+///
+/// The ordering is from strictest to most lenient limit.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum CgroupLimit {
+    Limited(u64),
+    Unlimited,
+}
+
+impl CgroupLimit {
+    pub fn limited(self) -> Option<u64> {
+        match self {
+            Self::Unlimited => None,
+            Self::Limited(limit) => Some(limit),
+        }
+    }
+}
+
+impl FromStr for CgroupLimit {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s == "max" {
+            Ok(Self::Unlimited)
+        } else {
+            Ok(Self::Limited(s.parse().context("invalid cgroup limit")?))
+        }
+    }
+}
+
+/// A limit together with the cgroup it is set in. Limits of a cgroup also apply to all of its descendants, so this
+/// may be an ancestor of the inspected cgroup.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InheritedLimit<T> {
+    pub limit: T,
+    pub cgroup: String,
+}
+
+/// Contents of `cpu.max`, see the kernel's cgroup-v2 documentation
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CpuMax {
+    /// Microseconds of CPU time the cgroup may use in each period
+    pub quota: CgroupLimit,
+    /// In microseconds
+    pub period: u64,
+}
+
+impl CpuMax {
+    /// Returns how many CPUs worth of time the cgroup may use, `None` if it's unlimited
+    pub fn cpus(&self) -> Option<f64> {
+        self.quota
+            .limited()
+            .filter(|_| self.period > 0)
+            .map(|quota| quota as f64 / self.period as f64)
+    }
+}
+
+impl FromStr for CpuMax {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut split = s.split_whitespace();
+
+        let quota = split.next().context("no quota in cpu.max")?.parse()?;
+        let period = split
+            .next()
+            .context("no period in cpu.max")?
+            .parse()
+            .context("invalid period in cpu.max")?;
+
+        Ok(Self { quota, period })
+    }
+}
+
+/// A line of `io.max`, all limits are per second
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IoMax {
+    /// The name of the block device if it could be found, otherwise its `major:minor` numbers
+    pub device: String,
+    pub read_bytes: CgroupLimit,
+    pub write_bytes: CgroupLimit,
+    pub read_ios: CgroupLimit,
+    pub write_ios: CgroupLimit,
+}
+
+impl IoMax {
+    pub fn from_line<S: AsRef<str>>(line: S) -> Result<Self> {
+        let mut split = line.as_ref().split_whitespace();
+
+        let device_numbers = split.next().context("no device in io.max line")?;
+
+        let device = std::fs::read_link(sysfs("dev/block").join(device_numbers))
+            .ok()
+            .and_then(|target| {
+                target
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            })
+            .unwrap_or_else(|| device_numbers.to_string());
+
+        let mut io_max = Self {
+            device,
+            read_bytes: CgroupLimit::Unlimited,
+            write_bytes: CgroupLimit::Unlimited,
+            read_ios: CgroupLimit::Unlimited,
+            write_ios: CgroupLimit::Unlimited,
+        };
+
+        for key_value in split {
+            let (key, value) = key_value
+                .split_once('=')
+                .context("invalid key-value pair in io.max")?;
+            let value = value.parse()?;
+            match key {
+                "rbps" => io_max.read_bytes = value,
+                "wbps" => io_max.write_bytes = value,
+                "riops" => io_max.read_ios = value,
+                "wiops" => io_max.write_ios = value,
+                _ => {}
+            }
+        }
+
+        Ok(io_max)
+    }
+}
+
+/// Resource usage and limits of a cgroup v2, fields are `None` if the corresponding controller is not enabled for
+/// this cgroup
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CgroupLimits {
+    pub path: String,
+    pub memory_current: Option<u64>,
+    /// The strictest `memory.high` of this cgroup and its ancestors
+    pub memory_high: Option<InheritedLimit<CgroupLimit>>,
+    /// The strictest `memory.max` of this cgroup and its ancestors
+    pub memory_max: Option<InheritedLimit<CgroupLimit>>,
+    /// The strictest `cpu.max` of this cgroup and its ancestors
+    pub cpu_max: Option<InheritedLimit<CpuMax>>,
+    pub cpu_weight: Option<u64>,
+    pub pids_current: Option<u64>,
+    /// The strictest `pids.max` of this cgroup and its ancestors
+    pub pids_max: Option<InheritedLimit<CgroupLimit>>,
+    pub io_max: Option<Vec<IoMax>>,
+}
+
+impl CgroupLimits {
+    /// Reads the limits of the cgroup with the path `path` (as found in `/proc/<pid>/cgroup`)
+    pub fn read<S: AsRef<str>>(path: S) -> Result<Self> {
+        let path = path.as_ref();
+        let cgroup_dir = Self::cgroup_dir(path)?;
+
+        if !cgroup_dir.is_dir() {
+            bail!("cgroup {path} does not exist")
+        }
+
+        let read = |file: &str| std::fs::read_to_string(cgroup_dir.join(file)).ok();
+
+        Ok(Self {
+            path: path.to_string(),
+            memory_current: read("memory.current").and_then(|s| s.trim().parse().ok()),
+            memory_high: Self::strictest_limit(path, "memory.high", |limit: &CgroupLimit| *limit),
+            memory_max: Self::strictest_limit(path, "memory.max", |limit: &CgroupLimit| *limit),
+            cpu_max: Self::strictest_limit(path, "cpu.max", |cpu_max: &CpuMax| {
+                cpu_max
+                    .quota
+                    .limited()
+                    .filter(|_| cpu_max.period > 0)
+                    // compare by the share of a CPU the cgroup gets, in millionths to keep it an integer
+                    .map_or(CgroupLimit::Unlimited, |quota| {
+                        CgroupLimit::Limited(quota.saturating_mul(1_000_000) / cpu_max.period)
+                    })
+            }),
+            cpu_weight: read("cpu.weight").and_then(|s| s.trim().parse().ok()),
+            pids_current: read("pids.current").and_then(|s| s.trim().parse().ok()),
+            pids_max: Self::strictest_limit(path, "pids.max", |limit: &CgroupLimit| *limit),
+            io_max: read("io.max").map(|s| {
+                s.lines()
+                    .filter_map(|line| IoMax::from_line(line).ok())
+                    .collect()
+            }),
+        })
+    }
+
+    /// Walks from the cgroup at `path` up to the root and returns the strictest limit found in `file` according to
+    /// `strictness`. On ties, the limit closest to `path` wins.
+    fn strictest_limit<T, F>(path: &str, file: &str, strictness: F) -> Option<InheritedLimit<T>>
+    where
+        T: FromStr,
+        F: Fn(&T) -> CgroupLimit,
+    {
+        let mut strictest: Option<InheritedLimit<T>> = None;
+
+        for cgroup in Path::new(path).ancestors() {
+            let Some(limit) = Self::cgroup_dir(&cgroup.to_string_lossy())
+                .ok()
+                .and_then(|dir| std::fs::read_to_string(dir.join(file)).ok())
+                .and_then(|contents| contents.parse::<T>().ok())
+            else {
+                // the root cgroup has no limits and controllers might not be enabled for every level
+                continue;
+            };
+
+            if strictest
+                .as_ref()
+                .is_none_or(|strictest| strictness(&limit) < strictness(&strictest.limit))
+            {
+                strictest = Some(InheritedLimit {
+                    limit,
+                    cgroup: cgroup.to_string_lossy().to_string(),
+                });
+            }
+        }
+
+        strictest
+    }
+
+    /// Makes sure that `path` can't be used to escape the cgroup file system
+    fn cgroup_dir(path: &str) -> Result<PathBuf> {
+        let path = Path::new(path);
+
+        if !path.is_absolute() {
+            bail!("cgroup path is not absolute")
+        }
+
+        if path
+            .components()
+            .any(|component| !matches!(component, Component::RootDir | Component::Normal(_)))
+        {
+            bail!("cgroup path contains invalid components")
+        }
+
+        Ok(CGROUP_ROOT.join(path.strip_prefix("/")?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{CgroupLimit, CgroupLimits, CpuMax, IoMax};
+
+    #[test]
+    fn cgroup_limit() {
+        assert_eq!(
+            "max\n".parse::<CgroupLimit>().unwrap(),
+            CgroupLimit::Unlimited
+        );
+        assert_eq!(
+            "536870912\n".parse::<CgroupLimit>().unwrap(),
+            CgroupLimit::Limited(536_870_912)
+        );
+        assert!("lots".parse::<CgroupLimit>().is_err());
+        assert!(CgroupLimit::Limited(u64::MAX) < CgroupLimit::Unlimited);
+    }
+
+    #[test]
+    fn cpu_max() {
+        let cpu_max = "50000 100000\n".parse::<CpuMax>().unwrap();
+        assert_eq!(
+            cpu_max,
+            CpuMax {
+                quota: CgroupLimit::Limited(50000),
+                period: 100_000
+            }
+        );
+        assert_eq!(cpu_max.cpus(), Some(0.5));
+
+        let cpu_max = "max 100000\n".parse::<CpuMax>().unwrap();
+        assert_eq!(cpu_max.quota, CgroupLimit::Unlimited);
+        assert_eq!(cpu_max.cpus(), None);
+    }
+
+    #[test]
+    fn io_max() {
+        let io_max =
+            IoMax::from_line("4095:4095 rbps=2097152 wbps=max riops=max wiops=120").unwrap();
+        assert_eq!(
+            io_max,
+            IoMax {
+                device: "4095:4095".into(),
+                read_bytes: CgroupLimit::Limited(2_097_152),
+                write_bytes: CgroupLimit::Unlimited,
+                read_ios: CgroupLimit::Unlimited,
+                write_ios: CgroupLimit::Limited(120),
+            }
+        );
+    }
+
+    #[test]
+    fn cgroup_dir() {
+        assert_eq!(
+            CgroupLimits::cgroup_dir("/user.slice/user-1000.slice").unwrap(),
+            crate::fs_root::sysfs("fs/cgroup/user.slice/user-1000.slice")
+        );
+        assert!(CgroupLimits::cgroup_dir("/../../etc").is_err());
+        assert!(CgroupLimits::cgroup_dir("user.slice").is_err());
+    }
+}
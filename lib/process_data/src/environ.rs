@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::fs_root::procfs;
+
+/// An environment variable of a process as found in `/proc/<pid>/environ`
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentVariable {
+    pub key: String,
+    pub value: String,
+}
+
+impl EnvironmentVariable {
+    /// Reads the environment of the process with the PID `pid`.
+    ///
+    /// Note that this is the environment the process was started with, changes it made to its own environment later
+    /// on are not reflected here.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `/proc/<pid>/environ` couldn't be read, usually because the process belongs to another
+    /// user or has exited in the meantime
+    pub fn all_of_process(pid: libc::pid_t) -> Result<Vec<Self>> {
+        let environ_path = procfs(pid.to_string()).join("environ");
+
+        let contents = std::fs::read(&environ_path)
+            .with_context(|| format!("unable to read {}", environ_path.display()))?;
+
+        Ok(Self::from_contents(&contents))
+    }
+
+    /// Parses the NUL-separated contents of an `environ` file, entries without a `=` are skipped
+    pub fn from_contents(contents: &[u8]) -> Vec<Self> {
+        let mut variables: Vec<Self> = contents
+            .split(|byte| *byte == b'\0')
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let entry = String::from_utf8_lossy(entry);
+                entry.split_once('=').map(|(key, value)| Self {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                })
+            })
+            .collect();
+
+        variables.sort_by(|a, b| a.key.cmp(&b.key));
+
+        variables
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::EnvironmentVariable;
+
+    #[test]
+    fn from_contents() {
+        let contents = b"WAYLAND_DISPLAY=wayland-0\0LD_PRELOAD=\0no_equals_sign\0\
+                         OPTIONS=--foo=bar\0DISPLAY=:0\0";
+
+        assert_eq!(
+            EnvironmentVariable::from_contents(contents),
+            vec![
+                EnvironmentVariable {
+                    key: "DISPLAY".into(),
+                    value: ":0".into()
+                },
+                EnvironmentVariable {
+                    key: "LD_PRELOAD".into(),
+                    value: String::new()
+                },
+                EnvironmentVariable {
+                    key: "OPTIONS".into(),
+                    value: "--foo=bar".into()
+                },
+                EnvironmentVariable {
+                    key: "WAYLAND_DISPLAY".into(),
+                    value: "wayland-0".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(EnvironmentVariable::from_contents(b""), vec![]);
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+/// Environment variable that makes all collectors read procfs from the given directory instead of `/proc`, e.g. a
+/// procfs that has been captured on another machine. Anything that acts on processes, like `resources-kill`, keeps
+/// using the real `/proc`.
+pub const PROCFS_ROOT_VAR: &str = "RESOURCES_PROCFS_ROOT";
+
+/// Like `PROCFS_ROOT_VAR`, but for sysfs instead of `/sys`
+pub const SYSFS_ROOT_VAR: &str = "RESOURCES_SYSFS_ROOT";
+
+const SYSTEM_PROCFS_ROOT: &str = "/proc";
+
+const SYSTEM_SYSFS_ROOT: &str = "/sys";
+
+pub static PROCFS_ROOT: LazyLock<PathBuf> =
+    LazyLock::new(|| root(std::env::var_os(PROCFS_ROOT_VAR), SYSTEM_PROCFS_ROOT));
+
+pub static SYSFS_ROOT: LazyLock<PathBuf> =
+    LazyLock::new(|| root(std::env::var_os(SYSFS_ROOT_VAR), SYSTEM_SYSFS_ROOT));
+
+/// Returns where `path` (e.g. `meminfo` or `/meminfo`) is found in procfs, honoring `RESOURCES_PROCFS_ROOT`
+pub fn procfs<P: AsRef<Path>>(path: P) -> PathBuf {
+    join(&PROCFS_ROOT, path)
+}
+
+/// Returns where `path` (e.g. `class/net` or `/class/net`) is found in sysfs, honoring `RESOURCES_SYSFS_ROOT`
+pub fn sysfs<P: AsRef<Path>>(path: P) -> PathBuf {
+    join(&SYSFS_ROOT, path)
+}
+
+/// Whether `path` is the procfs of this very system, only then its PIDs refer to processes that can be queried using
+/// syscalls or NVML
+pub fn is_system_procfs<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref() == Path::new(SYSTEM_PROCFS_ROOT)
+}
+
+/// Whether `RESOURCES_PROCFS_ROOT` points somewhere else than `/proc`
+pub fn procfs_overridden() -> bool {
+    !is_system_procfs(PROCFS_ROOT.as_path())
+}
+
+/// Whether `RESOURCES_SYSFS_ROOT` points somewhere else than `/sys`
+pub fn sysfs_overridden() -> bool {
+    SYSFS_ROOT.as_path() != Path::new(SYSTEM_SYSFS_ROOT)
+}
+
+/// Unset and empty variables both mean that the usual mount point is used
+fn root(value: Option<OsString>, default: &str) -> PathBuf {
+    value
+        .filter(|value| !value.is_empty())
+        .map_or_else(|| PathBuf::from(default), PathBuf::from)
+}
+
+/// Unlike `Path::join()`, this doesn't throw `root` away if `path` is absolute
+fn join<P: AsRef<Path>>(root: &Path, path: P) -> PathBuf {
+    let path = path.as_ref();
+    root.join(path.strip_prefix("/").unwrap_or(path))
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{is_system_procfs, join, root};
+
+    #[test]
+    fn roots() {
+        assert_eq!(root(None, "/proc"), PathBuf::from("/proc"));
+        assert_eq!(root(Some("".into()), "/proc"), PathBuf::from("/proc"));
+        assert_eq!(
+            root(Some("/tmp/fixture/proc".into()), "/proc"),
+            PathBuf::from("/tmp/fixture/proc")
+        );
+    }
+
+    #[test]
+    fn system_procfs() {
+        assert!(is_system_procfs("/proc"));
+        assert!(is_system_procfs("/proc/"));
+        assert!(!is_system_procfs("/tmp/fixture/proc"));
+    }
+
+    #[test]
+    fn joins() {
+        let root = Path::new("/tmp/fixture/sys");
+
+        assert_eq!(
+            join(root, "class/net"),
+            PathBuf::from("/tmp/fixture/sys/class/net")
+        );
+        assert_eq!(
+            join(root, "/class/net"),
+            PathBuf::from("/tmp/fixture/sys/class/net")
+        );
+        assert_eq!(join(root, ""), PathBuf::from("/tmp/fixture/sys"));
+    }
+}
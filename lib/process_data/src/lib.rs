@@ -1,31 +1,61 @@
+pub mod cgroup;
+pub mod environ;
+pub mod fs_root;
+pub mod namespaces;
+pub mod open_file;
 pub mod pci_slot;
+pub mod pidfd;
+pub mod procfs;
+pub mod rates;
+pub mod scheduling;
+pub mod security;
+pub mod socket;
+
+#[cfg(test)]
+#[path = "../benches/fixture/mod.rs"]
+mod fixture;
 
 use anyhow::{Context, Result, bail};
-use glob::glob;
-use lazy_regex::{Lazy, Regex, lazy_regex};
+use namespaces::Namespaces;
 use nutype::nutype;
 use nvml_wrapper::enums::device::UsedGpuMemory;
 use nvml_wrapper::error::NvmlError;
 use nvml_wrapper::struct_wrappers::device::{ProcessInfo, ProcessUtilizationSample};
 use nvml_wrapper::{Device, Nvml};
 use pci_slot::PciSlot;
+use pidfd::ProcessIdentity;
+use procfs::{
+    Buffers, DrmFds, ProcDir, ProcfsScanner, affinity_from_mask, field, numeric_field, size_field,
+};
+use scheduling::{IoPriority, SchedulingPolicy};
+use security::SecurityContext;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::CString;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::{Read, Write};
-use std::os::linux::fs::MetadataExt;
+use std::io::Read;
+use std::os::fd::RawFd;
 use std::path::Path;
 use std::str::FromStr;
-use std::sync::{LazyLock, RwLock};
+use std::sync::{LazyLock, Mutex, RwLock};
 use std::time::SystemTime;
+use thiserror::Error;
 
 const STAT_OFFSET: usize = 2; // we split the stat contents where the executable name ends, which is the second element
+const STAT_STATE: usize = 2 - STAT_OFFSET;
 const STAT_PARENT_PID: usize = 3 - STAT_OFFSET;
+const STAT_MINOR_FAULTS: usize = 9 - STAT_OFFSET;
+const STAT_MAJOR_FAULTS: usize = 11 - STAT_OFFSET;
 const STAT_USER_CPU_TIME: usize = 13 - STAT_OFFSET;
 const STAT_SYSTEM_CPU_TIME: usize = 14 - STAT_OFFSET;
+const STAT_CHILDREN_USER_CPU_TIME: usize = 15 - STAT_OFFSET;
+const STAT_CHILDREN_SYSTEM_CPU_TIME: usize = 16 - STAT_OFFSET;
 const STAT_NICE: usize = 18 - STAT_OFFSET;
+const STAT_NUM_THREADS: usize = 19 - STAT_OFFSET;
 const STAT_STARTTIME: usize = 21 - STAT_OFFSET;
+const STAT_PROCESSOR: usize = 38 - STAT_OFFSET;
+const STAT_RT_PRIORITY: usize = 40 - STAT_OFFSET;
+const STAT_POLICY: usize = 41 - STAT_OFFSET;
 
 static USERS_CACHE: LazyLock<HashMap<libc::uid_t, String>> = LazyLock::new(|| unsafe {
     uzers::all_users()
@@ -37,51 +67,13 @@ static PAGESIZE: LazyLock<usize> = LazyLock::new(sysconf::pagesize);
 
 static NUM_CPUS: LazyLock<usize> = LazyLock::new(num_cpus::get);
 
-static RE_UID: Lazy<Regex> = lazy_regex!(r"Uid:\s*(\d+)");
-
-static RE_AFFINITY: Lazy<Regex> = lazy_regex!(r"Cpus_allowed:\s*([0-9A-Fa-f]+)");
-
-static RE_SWAP_USAGGE: Lazy<Regex> = lazy_regex!(r"VmSwap:\s*([0-9]+)\s*kB");
-
-static RE_IO_READ: Lazy<Regex> = lazy_regex!(r"read_bytes:\s*(\d+)");
-
-static RE_IO_WRITE: Lazy<Regex> = lazy_regex!(r"write_bytes:\s*(\d+)");
-
-static RE_DRM_DRIVER: Lazy<Regex> = lazy_regex!(r"drm-driver:\s*(.+)");
-
-static RE_DRM_PDEV: Lazy<Regex> =
-    lazy_regex!(r"drm-pdev:\s*([0-9A-Fa-f]{4}:[0-9A-Fa-f]{2}:[0-9A-Fa-f]{2}\.[0-9A-Fa-f])");
-
-// AMD only
-static RE_DRM_ENGINE_GFX: Lazy<Regex> = lazy_regex!(r"drm-engine-gfx:\s*(\d+)\s*ns");
-
-// AMD only
-static RE_DRM_ENGINE_COMPUTE: Lazy<Regex> = lazy_regex!(r"drm-engine-compute:\s*(\d+)\s*ns");
-
-// AMD only
-static RE_DRM_ENGINE_ENC: Lazy<Regex> = lazy_regex!(r"drm-engine-enc:\s*(\d+)\s*ns");
-
-// AMD only
-static RE_DRM_ENGINE_DEC: Lazy<Regex> = lazy_regex!(r"drm-engine-dec:\s*(\d+)\s*ns");
-
-// AMD only
-static RE_DRM_MEMORY_VRAM: Lazy<Regex> = lazy_regex!(r"drm-memory-vram:\s*(\d+)\s*KiB");
-
-// AMD only
-static RE_DRM_MEMORY_GTT: Lazy<Regex> = lazy_regex!(r"drm-memory-gtt:\s*(\d+)\s*KiB");
-
-// Intel and v3d only
-static RE_DRM_ENGINE_RENDER: Lazy<Regex> = lazy_regex!(r"drm-engine-render:\s*(\d+)\s*ns");
-
-// Intel only
-static RE_DRM_ENGINE_VIDEO: Lazy<Regex> = lazy_regex!(r"drm-engine-video:\s*(\d+)\s*ns");
+/// Used by `ProcessData::all_process_data()`, so that its buffers and caches survive between scans
+static SCANNER: LazyLock<Mutex<ProcfsScanner>> =
+    LazyLock::new(|| Mutex::new(ProcfsScanner::new(fs_root::PROCFS_ROOT.as_path())));
 
-// v3d only
-static RE_DRM_TOTAL_MEMORY: Lazy<Regex> = lazy_regex!(r"drm-total-memory:\s*(\d+)\s*KiB");
+static NVML: LazyLock<Result<Nvml, NvmlError>> = LazyLock::new(Nvml::init);
 
-static NVML: Lazy<Result<Nvml, NvmlError>> = Lazy::new(Nvml::init);
-
-static NVML_DEVICES: Lazy<Vec<(PciSlot, Device)>> = Lazy::new(|| {
+static NVML_DEVICES: LazyLock<Vec<(PciSlot, Device)>> = LazyLock::new(|| {
     if let Ok(nvml) = NVML.as_ref() {
         let device_count = nvml.device_count().unwrap_or(0);
         let mut return_vec = Vec::with_capacity(device_count as usize);
@@ -99,11 +91,11 @@ static NVML_DEVICES: Lazy<Vec<(PciSlot, Device)>> = Lazy::new(|| {
     }
 });
 
-static NVIDIA_PROCESSES_STATS: Lazy<RwLock<HashMap<PciSlot, Vec<ProcessUtilizationSample>>>> =
-    Lazy::new(|| RwLock::new(HashMap::new()));
+static NVIDIA_PROCESSES_STATS: LazyLock<RwLock<HashMap<PciSlot, Vec<ProcessUtilizationSample>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
 
-static NVIDIA_PROCESS_INFOS: Lazy<RwLock<HashMap<PciSlot, Vec<ProcessInfo>>>> =
-    Lazy::new(|| RwLock::new(HashMap::new()));
+static NVIDIA_PROCESS_INFOS: LazyLock<RwLock<HashMap<PciSlot, Vec<ProcessInfo>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
 
 #[nutype(
     validate(less_or_equal = 19),
@@ -113,14 +105,232 @@ static NVIDIA_PROCESS_INFOS: Lazy<RwLock<HashMap<PciSlot, Vec<ProcessInfo>>>> =
 )]
 pub struct Niceness(i8);
 
-#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize, Copy)]
+/// Value of `/proc/<pid>/oom_score_adj` which is added to the OOM score of a process, -1000 exempts a process from
+/// being killed by the OOM killer entirely
+#[nutype(
+    validate(less_or_equal = 1000),
+    validate(greater_or_equal = -1000),
+    derive(Debug, Default, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Copy, FromStr, Deref, TryFrom, Display),
+    default = 0
+)]
+pub struct OomScoreAdj(i16);
+
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Containerization {
     #[default]
     None,
     Flatpak,
     Snap,
+    /// Contains the full container ID
+    Docker(String),
+    /// Contains the full container ID
+    Podman(String),
+    /// Contains the container name
+    Lxc(String),
+    /// A machine registered with systemd-machined, contains the machine name
+    Nspawn(String),
+}
+
+impl Containerization {
+    /// Tries to detect a container runtime from the contents of `/proc/<pid>/cgroup`, only cgroups v2 is supported
+    pub fn from_cgroup<S: AsRef<str>>(cgroup: S) -> Option<Self> {
+        let cgroups_v2_path = cgroup
+            .as_ref()
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))?;
+
+        let mut segments = cgroups_v2_path.split('/').peekable();
+
+        while let Some(segment) = segments.next() {
+            if let Some(id) = segment
+                .strip_prefix("docker-")
+                .and_then(|s| s.strip_suffix(".scope"))
+            {
+                // systemd cgroup driver
+                return Some(Self::Docker(id.to_string()));
+            } else if segment == "docker" {
+                // cgroupfs cgroup driver
+                if let Some(id) = segments.peek().filter(|id| !id.is_empty()) {
+                    return Some(Self::Docker((*id).to_string()));
+                }
+            } else if let Some(id) = segment
+                .strip_prefix("libpod-")
+                .and_then(|s| s.strip_suffix(".scope"))
+            {
+                // conmon is running on the host and only monitors the container
+                if !id.starts_with("conmon-") {
+                    return Some(Self::Podman(id.to_string()));
+                }
+            } else if let Some(name) = segment.strip_prefix("lxc.payload.") {
+                return Some(Self::Lxc(name.to_string()));
+            } else if let Some(name) = segment
+                .strip_prefix("machine-")
+                .and_then(|s| s.strip_suffix(".scope"))
+            {
+                return Some(Self::Nspawn(
+                    unescape::unescape(name).unwrap_or_else(|| name.to_string()),
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// Returns the container ID (or name if the runtime doesn't use IDs) if this is a container runtime
+    pub fn container_id(&self) -> Option<&str> {
+        match self {
+            Self::None | Self::Flatpak | Self::Snap => None,
+            Self::Docker(id) | Self::Podman(id) | Self::Lxc(id) | Self::Nspawn(id) => Some(id),
+        }
+    }
+}
+
+/// The state of a process or thread as reported by the third field of `/proc/<pid>/stat`, see man proc(5)
+#[derive(
+    Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize, Copy, PartialOrd, Ord,
+)]
+pub enum ProcessState {
+    Running,
+    Sleeping,
+    /// Uninterruptible sleep, usually waiting for disk I/O
+    DiskSleep,
+    Zombie,
+    /// Stopped by a signal, e.g. `SIGSTOP`
+    Stopped,
+    /// Stopped by a debugger during tracing
+    TracingStop,
+    Dead,
+    Idle,
+    #[default]
+    Unknown,
+}
+
+impl ProcessState {
+    /// Returns whether a process in this state can be resumed using `SIGCONT`, processes stopped by a debugger can only be
+    /// resumed by the debugger itself
+    pub fn is_stopped(&self) -> bool {
+        matches!(self, ProcessState::Stopped)
+    }
+}
+
+impl From<char> for ProcessState {
+    fn from(value: char) -> Self {
+        match value {
+            'R' => ProcessState::Running,
+            'S' => ProcessState::Sleeping,
+            'D' => ProcessState::DiskSleep,
+            'Z' => ProcessState::Zombie,
+            'T' => ProcessState::Stopped,
+            't' => ProcessState::TracingStop,
+            'X' | 'x' => ProcessState::Dead,
+            'I' => ProcessState::Idle,
+            _ => ProcessState::Unknown,
+        }
+    }
+}
+
+/// Bumped whenever requests or answers of the companion protocol change in an incompatible way
+pub const COMPANION_PROTOCOL_VERSION: u32 = 2;
+
+/// Capability of companion processes that push `ProcessData` on their own when started with `--interval`
+pub const COMPANION_CAPABILITY_STREAMING: &str = "streaming";
+
+/// Parts of `ProcessData` that are expensive to gather and therefore only gathered if asked for
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ProcessDataOptions {
+    /// Read `/proc/<pid>/smaps_rollup`, the kernel has to walk every memory mapping of a process for this
+    pub smaps_rollup: bool,
+}
+
+/// A request that Resources sends to `resources-processes` through its stdin.
+///
+/// Every request is answered with a length-prefixed message containing a `CompanionFrame` of the requested data,
+/// except for `Handshake`, which should be the first request and is answered with a bare `CompanionHandshake`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CompanionRequest {
+    /// Answered with a `Vec<ProcessData>` of all running processes
+    ProcessData(ProcessDataOptions),
+    /// Answered with a `Vec<OpenFile>` of the open files of the given PID
+    OpenFiles(libc::pid_t),
+    /// Answered with a `Vec<Connection>` of all TCP and UDP sockets
+    Connections,
+    /// Answered with `CgroupLimits` of the given cgroup v2 path
+    CgroupLimits(String),
+    /// Answered with a `Vec<EnvironmentVariable>` of the environment of the given PID
+    Environ(libc::pid_t),
+    /// Answered with a `CompanionHandshake`. Versions of `resources-processes` that predate the handshake can't decode
+    /// this request and exit instead.
+    Handshake,
+}
+
+/// Tells Resources which protocol a companion process speaks. This is the first frame of every conversation, so its
+/// layout must never change.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CompanionHandshake {
+    pub protocol_version: u32,
+    /// The version of Resources the companion process belongs to
+    pub version: String,
+    /// Optional features such as `COMPANION_CAPABILITY_STREAMING`, these are strings so that unknown ones can be
+    /// decoded and ignored
+    pub capabilities: Vec<String>,
+}
+
+impl CompanionHandshake {
+    /// The handshake of this very version
+    pub fn current() -> Self {
+        Self {
+            protocol_version: COMPANION_PROTOCOL_VERSION,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            capabilities: vec![COMPANION_CAPABILITY_STREAMING.to_string()],
+        }
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        self.protocol_version == COMPANION_PROTOCOL_VERSION
+    }
+
+    pub fn has_capability<S: AsRef<str>>(&self, capability: S) -> bool {
+        self.capabilities
+            .iter()
+            .any(|ours| ours == capability.as_ref())
+    }
+}
+
+/// Sent by the companion process in place of the requested data if it couldn't be gathered
+#[derive(Debug, Error, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CompanionError {
+    #[error("unable to decode request: {0}")]
+    InvalidRequest(String),
+
+    #[error("permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error("not found: {0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Failed(String),
 }
 
+impl From<anyhow::Error> for CompanionError {
+    fn from(error: anyhow::Error) -> Self {
+        let message = format!("{error:#}");
+
+        match error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<std::io::Error>())
+            .map(std::io::Error::kind)
+        {
+            Some(std::io::ErrorKind::PermissionDenied) => Self::PermissionDenied(message),
+            Some(std::io::ErrorKind::NotFound) => Self::NotFound(message),
+            _ => Self::Failed(message),
+        }
+    }
+}
+
+/// Every answer of the companion process besides the handshake
+pub type CompanionFrame<T> = std::result::Result<T, CompanionError>;
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Copy, PartialOrd, Ord)]
 pub enum GpuIdentifier {
     PciSlot(PciSlot),
@@ -160,6 +370,131 @@ pub struct GpuUsageStats {
     pub nvidia: bool,
 }
 
+/// Represents a single thread (task) of a process, gathered from `/proc/<pid>/task/<tid>/stat`
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadData {
+    pub tid: libc::pid_t,
+    pub comm: String,
+    pub state: ProcessState,
+    pub user_cpu_time: u64,
+    pub system_cpu_time: u64,
+    /// The CPU this thread was last executed on
+    pub last_cpu: usize,
+    pub niceness: Niceness,
+}
+
+impl ThreadData {
+    pub fn try_from_path<P: AsRef<Path>>(task_path: P) -> Result<Self> {
+        let task_path = task_path.as_ref();
+        let stat = std::fs::read_to_string(task_path.join("stat"))?;
+
+        let tid = task_path
+            .file_name()
+            .context("task_path terminates in ..")?
+            .to_str()
+            .context("can't turn OsStr to str")?
+            .parse()?;
+
+        Self::from_stat(tid, stat)
+    }
+
+    /// Parses the contents of `/proc/<pid>/task/<tid>/stat`
+    pub fn from_stat<S: AsRef<str>>(tid: libc::pid_t, stat: S) -> Result<Self> {
+        let stat = stat.as_ref();
+
+        // the executable name may contain spaces and parentheses, so look for the outermost ones
+        let comm_start = stat.find('(').context("stat doesn't have '('")?;
+        let comm_end = stat.rfind(')').context("stat doesn't have ')'")?;
+        let comm = stat
+            .get(comm_start + 1..comm_end)
+            .context("malformed executable name in stat")?
+            .to_string();
+
+        let stat = stat[comm_end + 1..]
+            .split(' ')
+            .skip(1) // the first element would be a space, let's ignore that
+            .collect::<Vec<_>>();
+
+        let state = stat
+            .get(STAT_STATE)
+            .and_then(|state| state.chars().next())
+            .map(ProcessState::from)
+            .context("wrong stat file format")?;
+        let user_cpu_time = stat_field(&stat, STAT_USER_CPU_TIME)?;
+        let system_cpu_time = stat_field(&stat, STAT_SYSTEM_CPU_TIME)?;
+        let last_cpu = stat_field(&stat, STAT_PROCESSOR)?;
+        let niceness = stat_field(&stat, STAT_NICE)?;
+
+        Ok(Self {
+            tid,
+            comm,
+            state,
+            user_cpu_time,
+            system_cpu_time,
+            last_cpu,
+            niceness,
+        })
+    }
+}
+
+/// Memory statistics of a process gathered from `/proc/<pid>/smaps_rollup`, all values are in bytes
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmapsRollup {
+    pub rss: usize,
+    /// Proportional set size: private memory plus this process' share of shared memory
+    pub pss: usize,
+    /// Unique set size: memory that is private to this process
+    pub uss: usize,
+    pub shared_clean: usize,
+    pub shared_dirty: usize,
+}
+
+impl SmapsRollup {
+    pub fn from_contents<S: AsRef<str>>(contents: S) -> Result<Self> {
+        let mut rss = None;
+        let mut pss = None;
+        let mut private_clean = None;
+        let mut private_dirty = None;
+        let mut shared_clean = None;
+        let mut shared_dirty = None;
+
+        for line in contents.as_ref().lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            let target = match key {
+                "Rss" => &mut rss,
+                "Pss" => &mut pss,
+                "Private_Clean" => &mut private_clean,
+                "Private_Dirty" => &mut private_dirty,
+                "Shared_Clean" => &mut shared_clean,
+                "Shared_Dirty" => &mut shared_dirty,
+                _ => continue,
+            };
+
+            *target = value
+                .trim()
+                .trim_end_matches("kB")
+                .trim_end()
+                .parse::<usize>()
+                .ok()
+                .map(|kib| kib.saturating_mul(1024));
+        }
+
+        let private_clean = private_clean.context("no Private_Clean in smaps_rollup")?;
+        let private_dirty = private_dirty.context("no Private_Dirty in smaps_rollup")?;
+
+        Ok(Self {
+            rss: rss.context("no Rss in smaps_rollup")?,
+            pss: pss.context("no Pss in smaps_rollup")?,
+            uss: private_clean.saturating_add(private_dirty),
+            shared_clean: shared_clean.context("no Shared_Clean in smaps_rollup")?,
+            shared_dirty: shared_dirty.context("no Shared_Dirty in smaps_rollup")?,
+        })
+    }
+}
+
 /// Data that could be transferred using `resources-processes`, separated from
 /// `Process` mainly due to `Icon` not being able to derive `Serialize` and
 /// `Deserialize`.
@@ -170,23 +505,70 @@ pub struct ProcessData {
     pub user: String,
     pub comm: String,
     pub commandline: String,
+    pub state: ProcessState,
     pub user_cpu_time: u64,
     pub system_cpu_time: u64,
+    /// CPU time in user mode of waited-for children, in clock ticks
+    pub children_user_cpu_time: u64,
+    /// CPU time in kernel mode of waited-for children, in clock ticks
+    pub children_system_cpu_time: u64,
+    /// Page faults that didn't require loading a page from disk
+    pub minor_faults: u64,
+    /// Page faults that required loading a page from disk (or swap)
+    pub major_faults: u64,
+    pub num_threads: u64,
+    /// `None` if `/proc/<pid>/status` didn't contain this, which shouldn't happen on recent kernels
+    pub voluntary_context_switches: Option<u64>,
+    /// `None` if `/proc/<pid>/status` didn't contain this, which shouldn't happen on recent kernels
+    pub nonvoluntary_context_switches: Option<u64>,
     pub niceness: Niceness,
+    /// `None` if we weren't allowed to read it
+    pub io_priority: Option<IoPriority>,
+    /// `None` if the kernel uses a policy we don't know about
+    pub scheduling_policy: Option<SchedulingPolicy>,
+    /// The badness of this process according to the OOM killer, the process with the highest score gets killed first
+    pub oom_score: Option<u32>,
+    pub oom_score_adj: Option<OomScoreAdj>,
     pub affinity: Vec<bool>,
     pub memory_usage: usize,
+    /// `None` if `/proc/<pid>/smaps_rollup` couldn't be read, e.g. due to missing permissions, or if it hasn't been asked
+    /// for using `ProcessDataOptions`
+    pub smaps_rollup: Option<SmapsRollup>,
     pub swap_usage: usize,
     pub starttime: u64, // in clock ticks, see man proc(5)!
     pub cgroup: Option<String>,
+    /// The full cgroup v2 path, e.g. `/user.slice/user-1000.slice/session-2.scope`
+    pub cgroup_path: Option<String>,
     pub containerization: Containerization,
+    /// Bytes read from the storage layer
     pub read_bytes: Option<u64>,
+    /// Bytes sent to the storage layer
     pub write_bytes: Option<u64>,
+    /// Bytes read using any read-like syscall, including those served from the page cache, pipes or sockets
+    pub read_chars: Option<u64>,
+    /// Bytes written using any write-like syscall, including those that never reach the storage layer
+    pub write_chars: Option<u64>,
+    pub read_syscalls: Option<u64>,
+    pub write_syscalls: Option<u64>,
+    /// Bytes that were accounted in `write_bytes` but not written after all, e.g. because the file got truncated
+    pub cancelled_write_bytes: Option<u64>,
     pub timestamp: u64,
     /// Key: PCI Slot ID of the GPU
     pub gpu_usage_stats: BTreeMap<GpuIdentifier, GpuUsageStats>,
+    pub threads: Vec<ThreadData>,
+    pub namespaces: Namespaces,
+    pub security: SecurityContext,
 }
 
 impl ProcessData {
+    fn cgroup_v2_path<S: AsRef<str>>(cgroup: S) -> Option<String> {
+        cgroup
+            .as_ref()
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .map(str::to_string)
+    }
+
     fn sanitize_cgroup<S: AsRef<str>>(cgroup: S) -> Option<String> {
         let cgroups_v2_line = cgroup.as_ref().split('\n').find(|s| s.starts_with("0::"))?;
         if cgroups_v2_line.ends_with(".scope") {
@@ -220,19 +602,6 @@ impl ProcessData {
         }
     }
 
-    fn get_uid(proc_path: &Path) -> Result<u32> {
-        let status = std::fs::read_to_string(proc_path.join("status"))?;
-        if let Some(captures) = RE_UID.captures(&status) {
-            let first_num_str = captures.get(1).context("no uid found")?;
-            first_num_str
-                .as_str()
-                .parse::<u32>()
-                .context("couldn't parse uid in /status")
-        } else {
-            Ok(0)
-        }
-    }
-
     pub fn update_nvidia_stats() {
         {
             let mut stats = NVIDIA_PROCESSES_STATS.write().unwrap();
@@ -246,29 +615,24 @@ impl ProcessData {
         }
     }
 
-    pub fn all_process_data() -> Result<Vec<Self>> {
-        Self::update_nvidia_stats();
-
-        let mut process_data = vec![];
-        for entry in glob("/proc/[0-9]*/").context("unable to glob")?.flatten() {
-            let data = ProcessData::try_from_path(&entry);
-
-            if let Ok(data) = data {
-                process_data.push(data);
-            }
+    /// Gathers the `ProcessData` of all processes in `/proc` or `RESOURCES_PROCFS_ROOT`
+    pub fn all_process_data(options: ProcessDataOptions) -> Result<Vec<Self>> {
+        if !fs_root::procfs_overridden() {
+            Self::update_nvidia_stats();
         }
 
-        Ok(process_data)
+        let mut scanner = SCANNER.lock().unwrap();
+        scanner.set_options(options);
+        scanner.scan()
     }
 
-    pub fn try_from_path<P: AsRef<Path>>(proc_path: P) -> Result<Self> {
+    /// Gathers the `ProcessData` of a single process, prefer `all_process_data()` or a `ProcfsScanner` when
+    /// gathering them for many processes
+    pub fn try_from_path<P: AsRef<Path>>(
+        proc_path: P,
+        options: ProcessDataOptions,
+    ) -> Result<Self> {
         let proc_path = proc_path.as_ref();
-        let stat = std::fs::read_to_string(proc_path.join("stat"))?;
-        let statm = std::fs::read_to_string(proc_path.join("statm"))?;
-        let status = std::fs::read_to_string(proc_path.join("status"))?;
-        let comm = std::fs::read_to_string(proc_path.join("comm"))?;
-        let commandline = std::fs::read_to_string(proc_path.join("cmdline"))?;
-        let io = std::fs::read_to_string(proc_path.join("io")).ok();
 
         let pid = proc_path
             .file_name()
@@ -277,12 +641,50 @@ impl ProcessData {
             .context("can't turn OsStr to str")?
             .parse()?;
 
+        Self::from_proc_dir(
+            &ProcDir::open(proc_path)?,
+            pid,
+            options,
+            &mut Buffers::default(),
+            &mut HashMap::new(),
+        )
+    }
+
+    pub(crate) fn from_proc_dir(
+        dir: &ProcDir,
+        pid: libc::pid_t,
+        options: ProcessDataOptions,
+        buffers: &mut Buffers,
+        drm_fds: &mut HashMap<ProcessIdentity, DrmFds>,
+    ) -> Result<Self> {
+        // the PIDs of another procfs (see `RESOURCES_PROCFS_ROOT`) belong to processes that syscalls and NVML don't
+        // know about, or worse, to unrelated processes of this system
+        let live = dir.path().parent().is_some_and(fs_root::is_system_procfs);
+
+        dir.read_into(c"stat", &mut buffers.stat)?;
+        dir.read_into(c"statm", &mut buffers.statm)?;
+        dir.read_into(c"status", &mut buffers.status)?;
+        dir.read_into(c"comm", &mut buffers.scratch)?;
+        let comm = buffers.scratch.replace('\n', "");
+
+        let mut commandline = String::new();
+        dir.open_file(c"cmdline")?
+            .read_to_string(&mut commandline)?;
+
+        let io = dir
+            .read_into(c"io", &mut buffers.io)
+            .ok()
+            .map(|()| buffers.io.as_str());
+
+        let status = buffers.status.as_str();
+
         let user = USERS_CACHE
-            .get(&Self::get_uid(proc_path)?)
+            .get(&numeric_field::<libc::uid_t>(status, "Uid").unwrap_or(0))
             .cloned()
             .unwrap_or(String::from("root"));
 
-        let stat = stat
+        let stat = buffers
+            .stat
             .split(')') // since we don't care about the pid or the executable name, split after the executable name to make our life easier
             .last()
             .context("stat doesn't have ')'")?
@@ -290,11 +692,14 @@ impl ProcessData {
             .skip(1) // the first element would be a space, let's ignore that
             .collect::<Vec<_>>();
 
-        let statm = statm.split(' ').collect::<Vec<_>>();
-
-        let comm = comm.replace('\n', "");
+        let statm = buffers.statm.split(' ').collect::<Vec<_>>();
 
         // -2 to accommodate for only collecting after the second item (which is the executable name as mentioned above)
+        let state = stat
+            .get(STAT_STATE)
+            .and_then(|state| state.chars().next())
+            .map(ProcessState::from)
+            .context("wrong stat file format")?;
         let parent_pid = stat
             .get(STAT_PARENT_PID)
             .context("wrong stat file format")
@@ -307,43 +712,57 @@ impl ProcessData {
             .get(STAT_SYSTEM_CPU_TIME)
             .context("wrong stat file format")
             .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let children_user_cpu_time = stat
+            .get(STAT_CHILDREN_USER_CPU_TIME)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let children_system_cpu_time = stat
+            .get(STAT_CHILDREN_SYSTEM_CPU_TIME)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let minor_faults = stat
+            .get(STAT_MINOR_FAULTS)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let major_faults = stat
+            .get(STAT_MAJOR_FAULTS)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let num_threads = stat
+            .get(STAT_NUM_THREADS)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
         let nice = stat
             .get(STAT_NICE)
             .context("wrong stat file format")
             .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let scheduling_policy = stat
+            .get(STAT_POLICY)
+            .and_then(|policy| policy.trim().parse().ok())
+            .and_then(|policy| {
+                let rt_priority = stat
+                    .get(STAT_RT_PRIORITY)
+                    .and_then(|rt_priority| rt_priority.parse().ok())
+                    .unwrap_or_default();
+                SchedulingPolicy::from_raw(policy, rt_priority)
+            });
         let starttime = stat
             .get(STAT_STARTTIME)
             .context("wrong stat file format")
             .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
 
-        let mut affinity = Vec::with_capacity(*NUM_CPUS);
-        RE_AFFINITY
-            .captures(&status)
-            .and_then(|captures| captures.get(1))
-            .map(|capture| capture.as_str())
-            .unwrap_or_default()
-            .chars()
-            .map(|c| c.to_digit(16).unwrap_or_default())
-            .rev()
-            .for_each(|int| {
-                // we want the bits and there are 4 bits in a hex digit
-                (0..4).for_each(|i| {
-                    // this if should prevent wrong size affinity vecs if the thread count is not divisible by 4
-                    if affinity.len() < *NUM_CPUS {
-                        affinity.push((int & (1 << i)) != 0);
-                    }
-                });
-            });
+        let affinity = field(status, "Cpus_allowed")
+            .map(|mask| affinity_from_mask(mask, *NUM_CPUS))
+            .unwrap_or_default();
 
-        let swap_usage = RE_SWAP_USAGGE
-            .captures(&status)
-            .and_then(|captures| captures.get(1))
-            .map(|capture| capture.as_str())
-            .unwrap_or_default()
-            .parse::<usize>()
+        let swap_usage = numeric_field::<usize>(status, "VmSwap")
             .unwrap_or_default() // kworkers don't have swap usage
             .saturating_mul(1000);
 
+        let voluntary_context_switches = numeric_field(status, "voluntary_ctxt_switches");
+
+        let nonvoluntary_context_switches = numeric_field(status, "nonvoluntary_ctxt_switches");
+
         let memory_usage = statm
             .get(1)
             .context("wrong statm file format")
@@ -362,33 +781,78 @@ impl ProcessData {
             )
             .saturating_mul(*PAGESIZE);
 
-        let cgroup = std::fs::read_to_string(proc_path.join("cgroup"))
+        let smaps_rollup = if options.smaps_rollup {
+            dir.read_into(c"smaps_rollup", &mut buffers.smaps_rollup)
+                .ok()
+                .and_then(|()| SmapsRollup::from_contents(&buffers.smaps_rollup).ok())
+        } else {
+            None
+        };
+
+        let raw_cgroup = dir
+            .read_into(c"cgroup", &mut buffers.cgroup)
             .ok()
-            .and_then(Self::sanitize_cgroup);
+            .map(|()| buffers.cgroup.as_str());
+
+        let cgroup = raw_cgroup.and_then(Self::sanitize_cgroup);
 
-        let containerization = if commandline.starts_with("/snap/") {
-            Containerization::Snap
-        } else if proc_path.join("root").join(".flatpak-info").exists() {
-            Containerization::Flatpak
+        let cgroup_path = raw_cgroup.and_then(Self::cgroup_v2_path);
+
+        let containerization =
+            if let Some(container) = raw_cgroup.and_then(Containerization::from_cgroup) {
+                container
+            } else if commandline.starts_with("/snap/") {
+                Containerization::Snap
+            } else if dir.stat(c"root/.flatpak-info").is_ok() {
+                Containerization::Flatpak
+            } else {
+                Containerization::None
+            };
+
+        let io_counter = |key: &str| io.and_then(|io| numeric_field::<u64>(io, key));
+
+        let read_bytes = io_counter("read_bytes");
+        let write_bytes = io_counter("write_bytes");
+        let read_chars = io_counter("rchar");
+        let write_chars = io_counter("wchar");
+        let read_syscalls = io_counter("syscr");
+        let write_syscalls = io_counter("syscw");
+        let cancelled_write_bytes = io_counter("cancelled_write_bytes");
+
+        let gpu_usage_stats = Self::gpu_usage_stats(
+            dir,
+            pid,
+            live,
+            &mut buffers.fdinfo,
+            drm_fds
+                .entry(ProcessIdentity {
+                    pid,
+                    start_time: starttime,
+                })
+                .or_default(),
+        );
+
+        let threads = Self::threads(dir, &mut buffers.thread_stat);
+
+        let namespaces = Namespaces::from_proc_path(dir.path());
+
+        let io_priority = if live {
+            IoPriority::of_thread(pid).ok()
         } else {
-            Containerization::None
+            None
         };
 
-        let read_bytes = io.as_ref().and_then(|io| {
-            RE_IO_READ
-                .captures(io)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-        });
+        let security = SecurityContext::from_proc_path(dir.path(), status);
 
-        let write_bytes = io.as_ref().and_then(|io| {
-            RE_IO_WRITE
-                .captures(io)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-        });
+        let oom_score = dir
+            .read_into(c"oom_score", &mut buffers.scratch)
+            .ok()
+            .and_then(|()| buffers.scratch.trim().parse().ok());
 
-        let gpu_usage_stats = Self::gpu_usage_stats(proc_path, pid);
+        let oom_score_adj = dir
+            .read_into(c"oom_score_adj", &mut buffers.scratch)
+            .ok()
+            .and_then(|()| buffers.scratch.trim().parse().ok());
 
         let timestamp = unix_as_millis();
 
@@ -398,96 +862,125 @@ impl ProcessData {
             user,
             comm,
             commandline,
+            state,
             user_cpu_time,
             system_cpu_time,
+            children_user_cpu_time,
+            children_system_cpu_time,
+            minor_faults,
+            major_faults,
+            num_threads,
+            voluntary_context_switches,
+            nonvoluntary_context_switches,
             niceness: nice,
+            io_priority,
+            scheduling_policy,
+            oom_score,
+            oom_score_adj,
             affinity,
             memory_usage,
+            smaps_rollup,
             swap_usage,
             starttime,
             cgroup,
+            cgroup_path,
             containerization,
             read_bytes,
             write_bytes,
+            read_chars,
+            write_chars,
+            read_syscalls,
+            write_syscalls,
+            cancelled_write_bytes,
             timestamp,
             gpu_usage_stats,
+            threads,
+            namespaces,
+            security,
         })
     }
 
-    fn gpu_usage_stats(proc_path: &Path, pid: i32) -> BTreeMap<GpuIdentifier, GpuUsageStats> {
-        let nvidia_stats = Self::nvidia_gpu_stats_all(pid);
-        let mut other_stats = Self::other_gpu_usage_stats(proc_path, pid).unwrap_or_default();
-        other_stats.extend(nvidia_stats);
+    /// Returns the sum of voluntary and involuntary context switches, `None` if either is unknown
+    pub fn context_switches(&self) -> Option<u64> {
+        self.voluntary_context_switches
+            .zip(self.nonvoluntary_context_switches)
+            .map(|(voluntary, nonvoluntary)| voluntary.saturating_add(nonvoluntary))
+    }
+
+    fn threads(dir: &ProcDir, buffer: &mut String) -> Vec<ThreadData> {
+        let Ok(task_dir) = dir.open_dir("task") else {
+            return Vec::new();
+        };
+
+        task_dir
+            .numeric_entries::<libc::pid_t>()
+            .map(|tids| {
+                tids.into_iter()
+                    .filter_map(|tid| {
+                        let stat_path = CString::new(format!("{tid}/stat")).ok()?;
+                        task_dir.read_into(&stat_path, buffer).ok()?;
+                        ThreadData::from_stat(tid, buffer.as_str()).ok()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn gpu_usage_stats(
+        dir: &ProcDir,
+        pid: i32,
+        live: bool,
+        fdinfo_buffer: &mut String,
+        drm_fds: &mut DrmFds,
+    ) -> BTreeMap<GpuIdentifier, GpuUsageStats> {
+        let mut other_stats =
+            Self::other_gpu_usage_stats(dir, pid, live, fdinfo_buffer, drm_fds).unwrap_or_default();
+        if live {
+            other_stats.extend(Self::nvidia_gpu_stats_all(pid));
+        }
         other_stats
     }
 
     fn other_gpu_usage_stats(
-        proc_path: &Path,
+        dir: &ProcDir,
         pid: i32,
+        live: bool,
+        fdinfo_buffer: &mut String,
+        drm_fds: &mut DrmFds,
     ) -> Result<BTreeMap<GpuIdentifier, GpuUsageStats>> {
-        let fdinfo_dir = proc_path.join("fdinfo");
+        let fd_dir = dir.open_dir("fd")?;
+        let fdinfo_dir = dir.open_dir("fdinfo")?;
 
-        let mut seen_fds = HashSet::new();
+        let fds = fd_dir.numeric_entries::<RawFd>()?;
 
-        let mut return_map = BTreeMap::new();
-        for entry in std::fs::read_dir(fdinfo_dir)? {
-            let entry = entry?;
-            let fdinfo_path = entry.path();
+        let mut seen_fds: Vec<RawFd> = Vec::new();
 
-            let _file = std::fs::File::open(&fdinfo_path);
-            if _file.is_err() {
+        let mut return_map = BTreeMap::new();
+        for fd_num in drm_fds.drm_fds(&fd_dir, &fds) {
+            // Adapted from nvtop's `processinfo_sweep_fdinfos()`
+            // https://github.com/Syllo/nvtop/blob/master/src/extract_processinfo_fdinfo.c
+            // if we've already seen the file this fd refers to, skip, this can only be checked for live processes
+            let not_unique = live
+                && seen_fds.iter().any(|seen_fd| unsafe {
+                    syscalls::syscall!(syscalls::Sysno::kcmp, pid, pid, 0, fd_num, *seen_fd)
+                        .unwrap_or(0)
+                        == 0
+                });
+            if not_unique {
                 continue;
             }
-            let mut file = _file.unwrap();
 
-            let _metadata = file.metadata();
-            if _metadata.is_err() {
-                continue;
-            }
-            let metadata = _metadata.unwrap();
+            seen_fds.push(fd_num);
 
-            // if our fd is 0, 1 or 2 it's probably just a std stream so skip it
-            let fd_num = fdinfo_path
-                .file_name()
-                .and_then(|osstr| osstr.to_str())
-                .unwrap_or("0")
-                .parse::<usize>()
-                .unwrap_or(0);
-            if fd_num <= 2 {
+            let Ok(fdinfo_path) = CString::new(fd_num.to_string()) else {
                 continue;
-            }
-
-            if !metadata.is_file() {
-                continue;
-            }
-
-            // Adapted from nvtop's `is_drm_fd()`
-            // https://github.com/Syllo/nvtop/blob/master/src/extract_processinfo_fdinfo.c
-            let fd_path = fdinfo_path.to_str().map(|s| s.replace("fdinfo", "fd"));
-            if let Some(fd_path) = fd_path {
-                if let Ok(fd_metadata) = std::fs::metadata(fd_path) {
-                    let major = libc::major(fd_metadata.st_rdev());
-                    if (fd_metadata.st_mode() & libc::S_IFMT) != libc::S_IFCHR || major != 226 {
-                        continue;
-                    }
-                }
-            }
+            };
 
-            // Adapted from nvtop's `processinfo_sweep_fdinfos()`
-            // https://github.com/Syllo/nvtop/blob/master/src/extract_processinfo_fdinfo.c
-            // if we've already seen the file this fd refers to, skip
-            let not_unique = seen_fds.iter().any(|seen_fd| unsafe {
-                syscalls::syscall!(syscalls::Sysno::kcmp, pid, pid, 0, fd_num, *seen_fd)
-                    .unwrap_or(0)
-                    == 0
-            });
-            if not_unique {
+            if fdinfo_dir.read_into(&fdinfo_path, fdinfo_buffer).is_err() {
                 continue;
             }
 
-            seen_fds.insert(fd_num);
-
-            if let Ok(stats) = Self::read_fdinfo(&mut file, metadata.len() as usize) {
+            if let Ok(stats) = Self::parse_fdinfo(fdinfo_buffer) {
                 return_map
                     .entry(stats.0)
                     .and_modify(|existing_value: &mut GpuUsageStats| {
@@ -511,96 +1004,47 @@ impl ProcessData {
         Ok(return_map)
     }
 
-    fn read_fdinfo(
-        fdinfo_file: &mut File,
-        file_size: usize,
-    ) -> Result<(GpuIdentifier, GpuUsageStats)> {
-        let mut content = String::with_capacity(file_size);
-        fdinfo_file.read_to_string(&mut content)?;
-        fdinfo_file.flush()?;
-
-        let driver = RE_DRM_DRIVER
-            .captures(&content)
-            .and_then(|captures| captures.get(1))
-            .map(|capture| capture.as_str());
-
-        if driver.is_some() {
-            let gpu_identifier = RE_DRM_PDEV
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| PciSlot::from_str(capture.as_str()).ok())
-                .map(|pci_slot| GpuIdentifier::PciSlot(pci_slot))
-                .unwrap_or_default();
-
-            let gfx = RE_DRM_ENGINE_GFX
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default();
-
-            let render = RE_DRM_ENGINE_RENDER
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default();
-
-            let compute = RE_DRM_ENGINE_COMPUTE
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default();
-
-            let enc = RE_DRM_ENGINE_ENC
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default();
-
-            let video = RE_DRM_ENGINE_VIDEO
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default();
-
-            let dec = RE_DRM_ENGINE_DEC
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default();
-
-            let vram = RE_DRM_MEMORY_VRAM
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default()
-                .saturating_mul(1024);
-
-            let gtt = RE_DRM_MEMORY_GTT
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default()
-                .saturating_mul(1024);
-
-            let total_memory = RE_DRM_TOTAL_MEMORY
-                .captures(&content)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-                .unwrap_or_default()
-                .saturating_mul(1024);
-
-            let stats = GpuUsageStats {
-                gfx: gfx.saturating_add(render).saturating_add(compute),
-                mem: vram.saturating_add(gtt).saturating_add(total_memory),
-                enc: enc.saturating_add(video),
-                dec,
-                nvidia: false,
-            };
-
-            return Ok((gpu_identifier, stats));
+    /// Parses the contents of `/proc/<pid>/fdinfo/<fd>` of a DRM file descriptor, see
+    /// <https://docs.kernel.org/gpu/drm-usage-stats.html>
+    fn parse_fdinfo(content: &str) -> Result<(GpuIdentifier, GpuUsageStats)> {
+        if field(content, "drm-driver").is_none() {
+            bail!("unable to find gpu information in this fdinfo");
         }
 
-        bail!("unable to find gpu information in this fdinfo");
+        let gpu_identifier = field(content, "drm-pdev")
+            .and_then(|pdev| PciSlot::from_str(pdev).ok())
+            .map(GpuIdentifier::PciSlot)
+            .unwrap_or_default();
+
+        let counter = |key: &str| numeric_field::<u64>(content, key).unwrap_or_default();
+        let size = |key: &str| size_field(content, key).unwrap_or_default();
+
+        // AMD only
+        let gfx = counter("drm-engine-gfx");
+        let compute = counter("drm-engine-compute");
+        let enc = counter("drm-engine-enc");
+        let dec = counter("drm-engine-dec");
+        let vram = size("drm-memory-vram");
+        let gtt = size("drm-memory-gtt");
+
+        // Intel and v3d only
+        let render = counter("drm-engine-render");
+
+        // Intel only
+        let video = counter("drm-engine-video");
+
+        // v3d only
+        let total_memory = size("drm-total-memory");
+
+        let stats = GpuUsageStats {
+            gfx: gfx.saturating_add(render).saturating_add(compute),
+            mem: vram.saturating_add(gtt).saturating_add(total_memory),
+            enc: enc.saturating_add(video),
+            dec,
+            nvidia: false,
+        };
+
+        Ok((gpu_identifier, stats))
     }
 
     fn nvidia_gpu_stats_all(pid: i32) -> BTreeMap<GpuIdentifier, GpuUsageStats> {
@@ -681,9 +1125,225 @@ impl ProcessData {
     }
 }
 
+fn stat_field<T: FromStr>(stat: &[&str], index: usize) -> Result<T> {
+    stat.get(index)
+        .context("wrong stat file format")
+        .and_then(|x| x.parse().ok().context("couldn't parse stat file content"))
+}
+
 pub fn unix_as_millis() -> u64 {
     SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap()
         .as_millis() as u64
 }
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::{
+        COMPANION_CAPABILITY_STREAMING, CompanionError, CompanionHandshake, Containerization,
+        GpuIdentifier, GpuUsageStats, ProcessData, ProcessState, SmapsRollup, pci_slot::PciSlot,
+    };
+    use pretty_assertions::assert_eq;
+
+    const SMAPS_ROLLUP: &str =
+        "5581a4c1d000-7ffd3b5f9000 ---p 00000000 00:00 0                          [rollup]
+Rss:               12344 kB
+Pss:                4567 kB
+Pss_Dirty:          1234 kB
+Pss_Anon:           1000 kB
+Pss_File:           3567 kB
+Pss_Shmem:             0 kB
+Shared_Clean:       7000 kB
+Shared_Dirty:        100 kB
+Private_Clean:      3000 kB
+Private_Dirty:      2244 kB
+Referenced:        12000 kB
+Anonymous:          2300 kB
+Swap:                  0 kB
+SwapPss:               0 kB
+Locked:                0 kB
+";
+
+    #[test]
+    fn process_state_from_stat_char() {
+        assert_eq!(ProcessState::from('R'), ProcessState::Running);
+        assert_eq!(ProcessState::from('S'), ProcessState::Sleeping);
+        assert_eq!(ProcessState::from('D'), ProcessState::DiskSleep);
+        assert_eq!(ProcessState::from('Z'), ProcessState::Zombie);
+        assert_eq!(ProcessState::from('T'), ProcessState::Stopped);
+        assert_eq!(ProcessState::from('t'), ProcessState::TracingStop);
+        assert_eq!(ProcessState::from('X'), ProcessState::Dead);
+        assert_eq!(ProcessState::from('I'), ProcessState::Idle);
+        assert_eq!(ProcessState::from('?'), ProcessState::Unknown);
+    }
+
+    #[test]
+    fn smaps_rollup_from_contents() {
+        let smaps_rollup = SmapsRollup::from_contents(SMAPS_ROLLUP).unwrap();
+        assert_eq!(
+            smaps_rollup,
+            SmapsRollup {
+                rss: 12344 * 1024,
+                pss: 4567 * 1024,
+                uss: 5244 * 1024,
+                shared_clean: 7000 * 1024,
+                shared_dirty: 100 * 1024,
+            }
+        );
+    }
+
+    #[test]
+    fn smaps_rollup_incomplete() {
+        assert!(SmapsRollup::from_contents("Rss: 1234 kB\n").is_err());
+    }
+
+    #[test]
+    fn fdinfo_amdgpu() {
+        let fdinfo = "pos:\t0
+flags:\t02100002
+mnt_id:\t26
+ino:\t1071
+drm-driver:\tamdgpu
+drm-client-id:\t42
+drm-pdev:\t0000:03:00.0
+drm-memory-vram:\t1024 KiB
+drm-memory-gtt:\t512 KiB
+drm-engine-gfx:\t1000 ns
+drm-engine-compute:\t500 ns
+drm-engine-enc:\t20 ns
+drm-engine-dec:\t30 ns
+drm-engine-capacity-gfx:\t2";
+
+        let (gpu, stats) = ProcessData::parse_fdinfo(fdinfo).unwrap();
+
+        assert_eq!(
+            gpu,
+            GpuIdentifier::PciSlot(PciSlot::from_str("0000:03:00.0").unwrap())
+        );
+        assert_eq!(
+            stats,
+            GpuUsageStats {
+                gfx: 1500,
+                mem: 1536 * 1024,
+                enc: 20,
+                dec: 30,
+                nvidia: false,
+            }
+        );
+
+        assert!(ProcessData::parse_fdinfo("pos:\t0\nflags:\t02\n").is_err());
+    }
+
+    #[test]
+    fn fdinfo_v3d() {
+        let fdinfo = "pos:\t0
+flags:\t02100002
+drm-driver:\tv3d
+drm-client-id:\t7
+drm-engine-render:\t2000 ns
+drm-total-memory:\t2 MiB
+drm-shared-memory:\t0
+drm-resident-memory:\t2 MiB";
+
+        let (gpu, stats) = ProcessData::parse_fdinfo(fdinfo).unwrap();
+
+        assert_eq!(gpu, GpuIdentifier::default());
+        assert_eq!(
+            stats,
+            GpuUsageStats {
+                gfx: 2000,
+                mem: 2 * 1024 * 1024,
+                enc: 0,
+                dec: 0,
+                nvidia: false,
+            }
+        );
+    }
+
+    #[test]
+    fn containerization_from_cgroup() {
+        assert_eq!(
+            Containerization::from_cgroup("0::/system.slice/docker-4f3c2a.scope\n"),
+            Some(Containerization::Docker("4f3c2a".into()))
+        );
+        assert_eq!(
+            Containerization::from_cgroup("0::/docker/4f3c2a\n"),
+            Some(Containerization::Docker("4f3c2a".into()))
+        );
+        assert_eq!(
+            Containerization::from_cgroup(
+                "0::/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-9d1e.scope/container\n"
+            ),
+            Some(Containerization::Podman("9d1e".into()))
+        );
+        assert_eq!(
+            Containerization::from_cgroup(
+                "0::/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-conmon-9d1e.scope\n"
+            ),
+            None
+        );
+        assert_eq!(
+            Containerization::from_cgroup("0::/lxc.payload.web/system.slice/nginx.service\n"),
+            Some(Containerization::Lxc("web".into()))
+        );
+        assert_eq!(Containerization::from_cgroup("0::/lxc.monitor.web\n"), None);
+        assert_eq!(
+            Containerization::from_cgroup(
+                "0::/machine.slice/machine-arch\\x2dbox.scope/payload/init.scope\n"
+            ),
+            Some(Containerization::Nspawn("arch-box".into()))
+        );
+        assert_eq!(
+            Containerization::from_cgroup(
+                "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-org.gnome.Terminal.slice/vte-spawn-1.scope\n"
+            ),
+            None
+        );
+        assert_eq!(
+            Containerization::from_cgroup("12:pids:/docker/4f3c2a\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn process_state_is_stopped() {
+        assert!(ProcessState::Stopped.is_stopped());
+        assert!(!ProcessState::TracingStop.is_stopped());
+        assert!(!ProcessState::Running.is_stopped());
+        assert!(!ProcessState::Zombie.is_stopped());
+    }
+
+    #[test]
+    fn companion_handshake() {
+        let handshake = CompanionHandshake::current();
+        assert!(handshake.is_compatible());
+        assert!(handshake.has_capability(COMPANION_CAPABILITY_STREAMING));
+        assert!(!handshake.has_capability("teleportation"));
+
+        let old = CompanionHandshake {
+            protocol_version: 0,
+            ..handshake
+        };
+        assert!(!old.is_compatible());
+    }
+
+    #[test]
+    fn companion_error() {
+        let permission_denied =
+            anyhow::Error::new(std::io::Error::from(std::io::ErrorKind::PermissionDenied))
+                .context("unable to read /proc/1/fd");
+
+        assert!(matches!(
+            CompanionError::from(permission_denied),
+            CompanionError::PermissionDenied(_)
+        ));
+
+        assert_eq!(
+            CompanionError::from(anyhow::anyhow!("something broke")),
+            CompanionError::Failed("something broke".to_string())
+        );
+    }
+}
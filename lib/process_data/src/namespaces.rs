@@ -0,0 +1,125 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// The types of namespaces found in `/proc/<pid>/ns/`, see man namespaces(7)
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum NamespaceKind {
+    Cgroup,
+    Ipc,
+    Mount,
+    Network,
+    Pid,
+    User,
+    Uts,
+}
+
+impl NamespaceKind {
+    pub const ALL: [NamespaceKind; 7] = [
+        NamespaceKind::Pid,
+        NamespaceKind::Network,
+        NamespaceKind::Mount,
+        NamespaceKind::User,
+        NamespaceKind::Uts,
+        NamespaceKind::Ipc,
+        NamespaceKind::Cgroup,
+    ];
+
+    /// The name of the corresponding file in `/proc/<pid>/ns/`
+    pub fn file_name(self) -> &'static str {
+        match self {
+            NamespaceKind::Cgroup => "cgroup",
+            NamespaceKind::Ipc => "ipc",
+            NamespaceKind::Mount => "mnt",
+            NamespaceKind::Network => "net",
+            NamespaceKind::Pid => "pid",
+            NamespaceKind::User => "user",
+            NamespaceKind::Uts => "uts",
+        }
+    }
+}
+
+/// Inode numbers of the namespaces a process is a member of. Two processes are in the same namespace if and only if
+/// these numbers are equal. They are `None` if we're not allowed to read them, which is usually the case for
+/// processes of other users.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Namespaces {
+    pub cgroup: Option<u64>,
+    pub ipc: Option<u64>,
+    pub mnt: Option<u64>,
+    pub net: Option<u64>,
+    pub pid: Option<u64>,
+    pub user: Option<u64>,
+    pub uts: Option<u64>,
+}
+
+impl Namespaces {
+    pub fn from_proc_path<P: AsRef<Path>>(proc_path: P) -> Self {
+        let ns_path = proc_path.as_ref().join("ns");
+
+        let read = |kind: NamespaceKind| {
+            std::fs::read_link(ns_path.join(kind.file_name()))
+                .ok()
+                .and_then(|target| Self::parse_link(target.to_string_lossy()))
+        };
+
+        Self {
+            cgroup: read(NamespaceKind::Cgroup),
+            ipc: read(NamespaceKind::Ipc),
+            mnt: read(NamespaceKind::Mount),
+            net: read(NamespaceKind::Network),
+            pid: read(NamespaceKind::Pid),
+            user: read(NamespaceKind::User),
+            uts: read(NamespaceKind::Uts),
+        }
+    }
+
+    pub fn get(&self, kind: NamespaceKind) -> Option<u64> {
+        match kind {
+            NamespaceKind::Cgroup => self.cgroup,
+            NamespaceKind::Ipc => self.ipc,
+            NamespaceKind::Mount => self.mnt,
+            NamespaceKind::Network => self.net,
+            NamespaceKind::Pid => self.pid,
+            NamespaceKind::User => self.user,
+            NamespaceKind::Uts => self.uts,
+        }
+    }
+
+    /// Parses the target of a namespace symlink, e.g. `net:[4026531840]`
+    fn parse_link<S: AsRef<str>>(target: S) -> Option<u64> {
+        let (_, inode) = target.as_ref().split_once(":[")?;
+        inode.strip_suffix(']')?.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{NamespaceKind, Namespaces};
+
+    #[test]
+    fn parse_link() {
+        assert_eq!(Namespaces::parse_link("net:[4026531840]"), Some(4026531840));
+        assert_eq!(
+            Namespaces::parse_link("pid_for_children:[4026531836]"),
+            Some(4026531836)
+        );
+        assert_eq!(Namespaces::parse_link("net:4026531840"), None);
+        assert_eq!(Namespaces::parse_link("net:[abc]"), None);
+    }
+
+    #[test]
+    fn get() {
+        let namespaces = Namespaces {
+            net: Some(1),
+            pid: Some(2),
+            ..Default::default()
+        };
+
+        assert_eq!(namespaces.get(NamespaceKind::Network), Some(1));
+        assert_eq!(namespaces.get(NamespaceKind::Pid), Some(2));
+        assert_eq!(namespaces.get(NamespaceKind::User), None);
+    }
+}
@@ -0,0 +1,210 @@
+use std::{
+    collections::HashMap,
+    os::linux::fs::MetadataExt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fs_root::procfs,
+    socket::{Socket, SocketProtocol},
+};
+
+const DELETED_SUFFIX: &str = " (deleted)";
+
+const DRM_MAJOR: u32 = 226;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub enum FileDescriptorKind {
+    #[default]
+    File,
+    Directory,
+    /// A character or block device other than a DRM device
+    Device,
+    /// A DRM (i.e. GPU) device
+    Drm,
+    Pipe,
+    /// Contains the inode of the socket
+    Socket(u64),
+    /// Contains the name of the anonymous inode, e.g. `eventfd`
+    AnonInode(String),
+}
+
+/// An open file descriptor of a process as found in `/proc/<pid>/fd/` and `/proc/<pid>/fdinfo/`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct OpenFile {
+    pub fd: usize,
+    /// Where `/proc/<pid>/fd/<fd>` points to, without the " (deleted)" suffix
+    pub target: String,
+    pub kind: FileDescriptorKind,
+    pub deleted: bool,
+    pub position: Option<u64>,
+    /// The flags the file was opened with (e.g. `O_RDWR`)
+    pub flags: Option<u32>,
+    /// Only available for sockets that could be found in the process' network namespace
+    pub socket: Option<Socket>,
+}
+
+impl OpenFile {
+    /// Returns all open file descriptors of the process with the PID `pid`, sorted by their number.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the file descriptors of the process can't be listed, e.g. due to
+    /// missing permissions
+    pub fn all_of_process(pid: libc::pid_t) -> Result<Vec<Self>> {
+        let proc_path = procfs(pid.to_string());
+
+        let entries = std::fs::read_dir(proc_path.join("fd"))
+            .with_context(|| format!("unable to read file descriptors of {pid}"))?;
+
+        let sockets = Socket::all_in(proc_path.join("net"), &SocketProtocol::ALL);
+
+        let mut open_files: Vec<Self> = entries
+            .flatten()
+            .filter_map(|entry| Self::try_from_path(entry.path(), &proc_path, &sockets).ok())
+            .collect();
+
+        open_files.sort_by_key(|open_file| open_file.fd);
+
+        Ok(open_files)
+    }
+
+    fn try_from_path(
+        fd_path: PathBuf,
+        proc_path: &Path,
+        sockets: &HashMap<u64, Socket>,
+    ) -> Result<Self> {
+        let fd = fd_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .context("invalid fd file name")?
+            .parse::<usize>()?;
+
+        let link = std::fs::read_link(&fd_path)?.to_string_lossy().to_string();
+
+        let (target, deleted) = match link.strip_suffix(DELETED_SUFFIX) {
+            Some(target) => (target.to_string(), true),
+            None => (link, false),
+        };
+
+        let kind = FileDescriptorKind::from_target(&target)
+            .unwrap_or_else(|| FileDescriptorKind::from_path(&fd_path));
+
+        let socket = if let FileDescriptorKind::Socket(inode) = kind {
+            sockets.get(&inode).cloned()
+        } else {
+            None
+        };
+
+        let (position, flags) =
+            std::fs::read_to_string(proc_path.join("fdinfo").join(fd.to_string()))
+                .map(|fdinfo| parse_fdinfo(&fdinfo))
+                .unwrap_or_default();
+
+        Ok(Self {
+            fd,
+            target,
+            kind,
+            deleted,
+            position,
+            flags,
+            socket,
+        })
+    }
+}
+
+impl FileDescriptorKind {
+    /// Determines the kind for targets that are not paths, e.g. `socket:[1234]`
+    pub(crate) fn from_target(target: &str) -> Option<Self> {
+        if let Some(inode) = target
+            .strip_prefix("socket:[")
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            inode.parse().ok().map(FileDescriptorKind::Socket)
+        } else if target.starts_with("pipe:[") {
+            Some(FileDescriptorKind::Pipe)
+        } else {
+            target.strip_prefix("anon_inode:").map(|name| {
+                FileDescriptorKind::AnonInode(
+                    name.trim_start_matches('[')
+                        .trim_end_matches(']')
+                        .to_string(),
+                )
+            })
+        }
+    }
+
+    fn from_path(fd_path: &Path) -> Self {
+        let Ok(metadata) = std::fs::metadata(fd_path) else {
+            return FileDescriptorKind::File;
+        };
+
+        let file_type = metadata.st_mode() & libc::S_IFMT;
+        if file_type == libc::S_IFCHR && libc::major(metadata.st_rdev()) == DRM_MAJOR {
+            FileDescriptorKind::Drm
+        } else if file_type == libc::S_IFCHR || file_type == libc::S_IFBLK {
+            FileDescriptorKind::Device
+        } else if file_type == libc::S_IFDIR {
+            FileDescriptorKind::Directory
+        } else {
+            FileDescriptorKind::File
+        }
+    }
+}
+
+/// Returns the position and the (octal) flags of an fdinfo file
+fn parse_fdinfo<S: AsRef<str>>(fdinfo: S) -> (Option<u64>, Option<u32>) {
+    let mut position = None;
+    let mut flags = None;
+
+    for line in fdinfo.as_ref().lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        match key {
+            "pos" => position = value.trim().parse().ok(),
+            "flags" => flags = u32::from_str_radix(value.trim(), 8).ok(),
+            _ => continue,
+        }
+    }
+
+    (position, flags)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FileDescriptorKind, parse_fdinfo};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn kind_from_target() {
+        assert_eq!(
+            FileDescriptorKind::from_target("socket:[36527]"),
+            Some(FileDescriptorKind::Socket(36527))
+        );
+        assert_eq!(
+            FileDescriptorKind::from_target("pipe:[1234]"),
+            Some(FileDescriptorKind::Pipe)
+        );
+        assert_eq!(
+            FileDescriptorKind::from_target("anon_inode:[eventfd]"),
+            Some(FileDescriptorKind::AnonInode("eventfd".into()))
+        );
+        assert_eq!(
+            FileDescriptorKind::from_target("anon_inode:inotify"),
+            Some(FileDescriptorKind::AnonInode("inotify".into()))
+        );
+        assert_eq!(FileDescriptorKind::from_target("/home/user/file.txt"), None);
+    }
+
+    #[test]
+    fn fdinfo() {
+        let fdinfo = "pos:\t4096\nflags:\t02100002\nmnt_id:\t25\nino:\t1234\n";
+        assert_eq!(parse_fdinfo(fdinfo), (Some(4096), Some(0o2100002)));
+        assert_eq!(parse_fdinfo(""), (None, None));
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    fmt::Display,
+    io,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+    str::FromStr,
+};
+
+use anyhow::{Context, Result};
+use syscalls::{Sysno, syscall};
+
+use crate::STAT_STARTTIME;
+
+/// Identifies a process by its PID together with its start time. Unlike the PID alone, this can't refer to another
+/// process once the PID has been recycled. The string representation is `<pid>:<start time>`, which is how processes
+/// are passed to `resources-kill` and `resources-adjust`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ProcessIdentity {
+    pub pid: libc::pid_t,
+    /// In clock ticks after boot, see the `starttime` field in man proc_pid_stat(5)
+    pub start_time: u64,
+}
+
+impl ProcessIdentity {
+    /// Opens a pidfd for this process, see `PidFd::open_verified()`
+    pub fn open(self) -> io::Result<PidFd> {
+        PidFd::open_verified(self.pid, self.start_time)
+    }
+}
+
+impl Display for ProcessIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.pid, self.start_time)
+    }
+}
+
+impl FromStr for ProcessIdentity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (pid, start_time) = s
+            .split_once(':')
+            .context("process identity is missing the start time")?;
+
+        Ok(Self {
+            pid: pid.parse().context("invalid PID")?,
+            start_time: start_time.parse().context("invalid start time")?,
+        })
+    }
+}
+
+/// A file descriptor referring to a process, see man pidfd_open(2). Signals sent through it can only ever reach the
+/// process it has been opened for, even if its PID gets recycled in the meantime.
+#[derive(Debug)]
+pub struct PidFd {
+    fd: OwnedFd,
+}
+
+impl PidFd {
+    /// Opens a pidfd for the process with the PID `pid` and makes sure that it's the process that has been started at
+    /// `start_time`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with `ESRCH` if there is no process with that PID, with `ESTALE` if the PID belongs to a
+    /// different process by now or with the `errno` of `pidfd_open` otherwise
+    pub fn open_verified(pid: libc::pid_t, start_time: u64) -> io::Result<Self> {
+        let fd = unsafe { syscall!(Sysno::pidfd_open, pid, 0) }
+            .map_err(|errno| io::Error::from_raw_os_error(errno.into_raw()))?;
+
+        let pidfd = Self {
+            fd: unsafe { OwnedFd::from_raw_fd(fd as RawFd) },
+        };
+
+        // the pidfd keeps referring to the process it has been opened for, so if the start time still matches
+        // afterwards, the process we're going to act on is the one that was meant
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).map_err(|error| {
+            if error.kind() == io::ErrorKind::NotFound {
+                io::Error::from_raw_os_error(libc::ESRCH)
+            } else {
+                error
+            }
+        })?;
+
+        if start_time_from_stat(&stat) == Some(start_time) {
+            Ok(pidfd)
+        } else {
+            Err(io::Error::from_raw_os_error(libc::ESTALE))
+        }
+    }
+
+    /// Sends `signal` to the process
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with the `errno` of `pidfd_send_signal`, e.g. `ESRCH` if the process has exited in the
+    /// meantime or `EPERM` if we're not allowed to signal it
+    pub fn send_signal(&self, signal: libc::c_int) -> io::Result<()> {
+        unsafe {
+            syscall!(
+                Sysno::pidfd_send_signal,
+                self.fd.as_raw_fd(),
+                signal,
+                0, // no siginfo_t, so that it behaves like kill(2)
+                0
+            )
+        }
+        .map(|_| ())
+        .map_err(|errno| io::Error::from_raw_os_error(errno.into_raw()))
+    }
+}
+
+/// Extracts the start time from the contents of `/proc/<pid>/stat`
+fn start_time_from_stat(stat: &str) -> Option<u64> {
+    stat.rsplit_once(')')?
+        .1
+        .split(' ')
+        .skip(1) // the first element would be a space, just like in `ProcessData`
+        .nth(STAT_STARTTIME)?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{ProcessIdentity, start_time_from_stat};
+
+    #[test]
+    fn process_identity() {
+        let identity = ProcessIdentity {
+            pid: 1234,
+            start_time: 56789,
+        };
+
+        assert_eq!(identity.to_string(), "1234:56789");
+        assert_eq!("1234:56789".parse::<ProcessIdentity>().unwrap(), identity);
+
+        assert!("1234".parse::<ProcessIdentity>().is_err());
+        assert!("1234:abc".parse::<ProcessIdentity>().is_err());
+    }
+
+    #[test]
+    fn start_time() {
+        let stat = "1234 (tmux: server) S 1 1234 1234 0 -1 4194624 1811 0 0 0 52 31 0 0 20 0 1 0 6203 \
+            12369920 1203 18446744073709551615 1 1 0 0 0 0 0 3674112 1266777851 0 0 0 17 3 0 0 0 0 0 0 0 0 0 0 0 0 0";
+
+        assert_eq!(start_time_from_stat(stat), Some(6203));
+        assert_eq!(start_time_from_stat("1234 (broken"), None);
+    }
+}
@@ -0,0 +1,530 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::{CStr, CString},
+    fs::File,
+    io::{self, Read},
+    mem::MaybeUninit,
+    os::{
+        fd::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
+        unix::fs::OpenOptionsExt,
+    },
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{ProcessData, ProcessDataOptions, pidfd::ProcessIdentity};
+
+/// Major device number of DRM devices
+const DRM_MAJOR: u32 = 226;
+
+/// How long we trust the answer whether a file descriptor refers to a DRM device. Closed file descriptor numbers are
+/// reused, so a process might have opened a GPU under a number that we've already checked.
+const DRM_FD_RECHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A directory in procfs such as `/proc/<pid>` whose files are opened relative to it using openat(2), this spares the
+/// kernel from resolving the whole path for every file and us from allocating a `PathBuf` for each one
+#[derive(Debug)]
+pub struct ProcDir {
+    fd: OwnedFd,
+    path: PathBuf,
+}
+
+impl ProcDir {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_DIRECTORY)
+            .open(path.as_ref())?;
+
+        Ok(Self {
+            fd: file.into(),
+            path: path.as_ref().to_path_buf(),
+        })
+    }
+
+    /// Opens the subdirectory `name`, e.g. `task` or the directory of a PID
+    pub fn open_dir(&self, name: &str) -> io::Result<Self> {
+        let c_name =
+            CString::new(name).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+        Ok(Self {
+            fd: self.openat(&c_name, libc::O_DIRECTORY)?,
+            path: self.path.join(name),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn open_file(&self, name: &CStr) -> io::Result<File> {
+        self.openat(name, 0).map(File::from)
+    }
+
+    /// Replaces the contents of `buffer` with the contents of the file `name`
+    pub fn read_into(&self, name: &CStr, buffer: &mut String) -> io::Result<()> {
+        buffer.clear();
+        self.open_file(name)?.read_to_string(buffer).map(|_| ())
+    }
+
+    /// Returns the metadata of `name`, following symlinks
+    pub fn stat(&self, name: &CStr) -> io::Result<libc::stat> {
+        let mut stat = MaybeUninit::<libc::stat>::uninit();
+
+        let ret =
+            unsafe { libc::fstatat(self.fd.as_raw_fd(), name.as_ptr(), stat.as_mut_ptr(), 0) };
+
+        if ret == 0 {
+            Ok(unsafe { stat.assume_init() })
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Returns all entries whose names are numbers, i.e. PIDs, TIDs or file descriptors depending on the directory.
+    /// They're listed through our file descriptor, so a `task` or `fd` directory always belongs to the same process as
+    /// the other files we've read, even if its PID has been recycled in the meantime.
+    pub fn numeric_entries<T: FromStr>(&self) -> io::Result<Vec<T>> {
+        // fdopendir(3) takes over the file descriptor, so give it a fresh one of the same directory
+        let fd = self.openat(c".", libc::O_DIRECTORY)?;
+
+        let dir = unsafe { libc::fdopendir(fd.as_raw_fd()) };
+
+        if dir.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+
+        // closedir(3) closes it from now on
+        let _ = fd.into_raw_fd();
+
+        let mut entries = Vec::new();
+
+        loop {
+            let entry = unsafe { libc::readdir(dir) };
+
+            if entry.is_null() {
+                break;
+            }
+
+            let name = unsafe { CStr::from_ptr((*entry).d_name.as_ptr()) };
+
+            if let Some(number) = name.to_str().ok().and_then(|name| name.parse().ok()) {
+                entries.push(number);
+            }
+        }
+
+        unsafe { libc::closedir(dir) };
+
+        Ok(entries)
+    }
+
+    fn openat(&self, name: &CStr, flags: libc::c_int) -> io::Result<OwnedFd> {
+        let fd = unsafe {
+            libc::openat(
+                self.fd.as_raw_fd(),
+                name.as_ptr(),
+                libc::O_RDONLY | libc::O_CLOEXEC | flags,
+            )
+        };
+
+        if fd < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+        }
+    }
+}
+
+/// Buffers that are reused for every process of a scan, so that reading procfs doesn't allocate over and over
+#[derive(Debug, Default)]
+pub(crate) struct Buffers {
+    pub stat: String,
+    pub statm: String,
+    pub status: String,
+    pub io: String,
+    pub smaps_rollup: String,
+    pub cgroup: String,
+    /// For files that are only needed for a moment, e.g. `comm` or `oom_score`
+    pub scratch: String,
+    pub thread_stat: String,
+    pub fdinfo: String,
+}
+
+/// Remembers which file descriptors of a process refer to DRM devices, so that the others don't have to be checked on
+/// every scan
+#[derive(Debug)]
+pub(crate) struct DrmFds {
+    is_drm: HashMap<RawFd, bool>,
+    checked_at: Instant,
+}
+
+impl Default for DrmFds {
+    fn default() -> Self {
+        Self {
+            is_drm: HashMap::new(),
+            checked_at: Instant::now(),
+        }
+    }
+}
+
+impl DrmFds {
+    /// Returns those of the open file descriptors `fds` that refer to DRM devices, `fd_dir` is `/proc/<pid>/fd`
+    pub(crate) fn drm_fds(&mut self, fd_dir: &ProcDir, fds: &[RawFd]) -> Vec<RawFd> {
+        if self.checked_at.elapsed() >= DRM_FD_RECHECK_INTERVAL {
+            self.is_drm.clear();
+            self.checked_at = Instant::now();
+        }
+
+        let open: HashSet<&RawFd> = fds.iter().collect();
+        self.is_drm.retain(|fd, _| open.contains(fd));
+
+        fds.iter()
+            .copied()
+            // if our fd is 0, 1 or 2 it's probably just a std stream so skip it
+            .filter(|fd| *fd > 2)
+            .filter(|fd| {
+                *self
+                    .is_drm
+                    .entry(*fd)
+                    .or_insert_with(|| is_drm_fd(fd_dir, *fd))
+            })
+            .collect()
+    }
+}
+
+// Adapted from nvtop's `is_drm_fd()`
+// https://github.com/Syllo/nvtop/blob/master/src/extract_processinfo_fdinfo.c
+fn is_drm_fd(fd_dir: &ProcDir, fd: RawFd) -> bool {
+    let Ok(name) = CString::new(fd.to_string()) else {
+        return false;
+    };
+
+    fd_dir.stat(&name).is_ok_and(|stat| {
+        (stat.st_mode & libc::S_IFMT) == libc::S_IFCHR && libc::major(stat.st_rdev) == DRM_MAJOR
+    })
+}
+
+/// Gathers the `ProcessData` of all processes in a procfs. It keeps its buffers and what it has learned about the file
+/// descriptors of each process between scans, so it's meant to be kept around rather than created for every scan.
+#[derive(Debug)]
+pub struct ProcfsScanner {
+    root: PathBuf,
+    options: ProcessDataOptions,
+    buffers: Buffers,
+    drm_fds: HashMap<ProcessIdentity, DrmFds>,
+}
+
+impl ProcfsScanner {
+    /// Creates a scanner for the procfs at `root`, usually `/proc`
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self {
+            root: root.into(),
+            options: ProcessDataOptions::default(),
+            buffers: Buffers::default(),
+            drm_fds: HashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_options(mut self, options: ProcessDataOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Changes what the following scans gather
+    pub fn set_options(&mut self, options: ProcessDataOptions) {
+        self.options = options;
+    }
+
+    /// Gathers the `ProcessData` of every process, processes that exit during the scan are skipped
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the procfs root can't be listed
+    pub fn scan(&mut self) -> Result<Vec<ProcessData>> {
+        let root = ProcDir::open(&self.root)
+            .with_context(|| format!("unable to open {}", self.root.display()))?;
+
+        let pids = root
+            .numeric_entries::<libc::pid_t>()
+            .context("unable to list processes")?;
+
+        let mut process_data = Vec::with_capacity(pids.len());
+
+        for pid in pids {
+            let Ok(dir) = root.open_dir(&pid.to_string()) else {
+                continue;
+            };
+
+            if let Ok(data) = ProcessData::from_proc_dir(
+                &dir,
+                pid,
+                self.options,
+                &mut self.buffers,
+                &mut self.drm_fds,
+            ) {
+                process_data.push(data);
+            }
+        }
+
+        // forget about processes that have exited
+        let alive: HashSet<ProcessIdentity> = process_data
+            .iter()
+            .map(|data| ProcessIdentity {
+                pid: data.pid,
+                start_time: data.starttime,
+            })
+            .collect();
+        self.drm_fds.retain(|identity, _| alive.contains(identity));
+
+        Ok(process_data)
+    }
+}
+
+/// Returns the trimmed value of the line starting with `key:`, which is the format of e.g. `status`, `io` and fdinfo
+pub(crate) fn field<'a>(contents: &'a str, key: &str) -> Option<&'a str> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+        .map(str::trim)
+}
+
+/// Like `field()`, but parses the first word of the value, so that units like `kB` or `ns` are ignored
+pub(crate) fn numeric_field<T: FromStr>(contents: &str, key: &str) -> Option<T> {
+    field(contents, key)?
+        .split_ascii_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Like `numeric_field()`, but for sizes like `drm-memory-vram: 1024 KiB` in fdinfo, returned in bytes. Sizes without
+/// a unit are in bytes, unknown units yield `None`.
+pub(crate) fn size_field(contents: &str, key: &str) -> Option<u64> {
+    let mut value = field(contents, key)?.split_ascii_whitespace();
+
+    let size: u64 = value.next()?.parse().ok()?;
+
+    let factor = match value.next() {
+        None => 1,
+        Some("KiB") => 1 << 10,
+        Some("MiB") => 1 << 20,
+        Some("GiB") => 1 << 30,
+        Some(_) => return None,
+    };
+
+    Some(size.saturating_mul(factor))
+}
+
+/// Turns a hexadecimal CPU mask like `Cpus_allowed` in `status` into one `bool` per logical CPU, starting with CPU 0.
+/// Masks of systems with more than 32 CPUs are separated by commas every 8 digits.
+pub(crate) fn affinity_from_mask(mask: &str, num_cpus: usize) -> Vec<bool> {
+    let mut affinity = Vec::with_capacity(num_cpus);
+
+    mask.chars()
+        .filter(|c| *c != ',')
+        .map(|c| c.to_digit(16).unwrap_or_default())
+        .rev()
+        .for_each(|int| {
+            // we want the bits and there are 4 bits in a hex digit
+            (0..4).for_each(|i| {
+                // this if should prevent wrong size affinity vecs if the thread count is not divisible by 4
+                if affinity.len() < num_cpus {
+                    affinity.push((int & (1 << i)) != 0);
+                }
+            });
+        });
+
+    affinity
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        Containerization, NUM_CPUS, PAGESIZE, ProcessDataOptions, ProcessState, SmapsRollup,
+        fixture,
+        fixture::{FIRST_PID, THREADS_PER_PROCESS},
+    };
+
+    use super::{ProcfsScanner, affinity_from_mask, field, numeric_field, size_field};
+
+    const STATUS: &str = "Name:\tbash
+Umask:\t0022
+State:\tS (sleeping)
+Uid:\t1000\t1000\t1000\t1000
+Gid:\t1000\t1000\t1000\t1000
+VmSwap:\t     128 kB
+Cpus_allowed:\tff
+Cpus_allowed_list:\t0-7
+voluntary_ctxt_switches:\t150
+nonvoluntary_ctxt_switches:\t545";
+
+    #[test]
+    fn fields() {
+        assert_eq!(field(STATUS, "State"), Some("S (sleeping)"));
+        assert_eq!(field(STATUS, "Cpus_allowed"), Some("ff"));
+        assert_eq!(field(STATUS, "Missing"), None);
+
+        assert_eq!(numeric_field::<u32>(STATUS, "Uid"), Some(1000));
+        assert_eq!(numeric_field::<usize>(STATUS, "VmSwap"), Some(128));
+        // keys have to match completely
+        assert_eq!(
+            numeric_field::<u64>(STATUS, "voluntary_ctxt_switches"),
+            Some(150)
+        );
+        assert_eq!(
+            numeric_field::<u64>(STATUS, "nonvoluntary_ctxt_switches"),
+            Some(545)
+        );
+        assert_eq!(numeric_field::<u64>(STATUS, "Name"), None);
+    }
+
+    #[test]
+    fn sizes() {
+        let fdinfo = "drm-driver:\tv3d
+drm-total-memory:\t12 MiB
+drm-shared-memory:\t512 KiB
+drm-resident-memory:\t4096
+drm-purgeable-memory:\t1 PiB";
+
+        assert_eq!(
+            size_field(fdinfo, "drm-total-memory"),
+            Some(12 * 1024 * 1024)
+        );
+        assert_eq!(size_field(fdinfo, "drm-shared-memory"), Some(512 * 1024));
+        assert_eq!(size_field(fdinfo, "drm-resident-memory"), Some(4096));
+        assert_eq!(size_field(fdinfo, "drm-purgeable-memory"), None);
+        assert_eq!(size_field(fdinfo, "drm-driver"), None);
+        assert_eq!(size_field(fdinfo, "drm-active-memory"), None);
+    }
+
+    #[test]
+    fn affinity() {
+        assert_eq!(affinity_from_mask("5", 4), vec![true, false, true, false]);
+        assert_eq!(affinity_from_mask("ff", 6), vec![true; 6]);
+        assert_eq!(affinity_from_mask("", 4), Vec::<bool>::new());
+
+        let affinity = affinity_from_mask("1,00000001", 64);
+        assert_eq!(affinity.len(), 36);
+        assert!(affinity[0]);
+        assert!(affinity[32]);
+        assert_eq!(affinity.iter().filter(|allowed| **allowed).count(), 2);
+    }
+
+    #[test]
+    fn scan_fixture() {
+        let root = std::env::temp_dir().join(format!(
+            "resources-procfs-scanner-test-{}",
+            std::process::id()
+        ));
+
+        fixture::create(&root, 3).unwrap();
+
+        let flatpak_pid = FIRST_PID + 2;
+        std::fs::create_dir(root.join(format!("{flatpak_pid}/root"))).unwrap();
+        std::fs::write(root.join(format!("{flatpak_pid}/root/.flatpak-info")), "").unwrap();
+
+        let mut scanner =
+            ProcfsScanner::new(&root).with_options(ProcessDataOptions { smaps_rollup: true });
+
+        let mut process_data = scanner.scan().unwrap();
+        process_data.sort_by_key(|data| data.pid);
+
+        let pids: Vec<_> = process_data.iter().map(|data| data.pid).collect();
+        let first_pid = FIRST_PID as libc::pid_t;
+        assert_eq!(pids, vec![first_pid, first_pid + 1, first_pid + 2]);
+
+        let data = &process_data[0];
+
+        // stat
+        assert_eq!(data.comm, "bench-worker");
+        assert_eq!(
+            data.commandline,
+            ["/usr/bin/bench-worker", "--id", "0", ""].join("\0")
+        );
+        assert_eq!(data.state, ProcessState::Sleeping);
+        assert_eq!(data.parent_pid, 1);
+        assert_eq!(data.minor_faults, 1234);
+        assert_eq!(data.major_faults, 12);
+        assert_eq!(data.user_cpu_time, 250);
+        assert_eq!(data.system_cpu_time, 75);
+        assert_eq!(data.children_user_cpu_time, 0);
+        assert_eq!(data.children_system_cpu_time, 0);
+        assert_eq!(*data.niceness, 0);
+        assert_eq!(data.num_threads, THREADS_PER_PROCESS as u64);
+        assert_eq!(data.starttime, 6203);
+
+        // statm, resident minus shared pages
+        assert_eq!(data.memory_usage, 400 * *PAGESIZE);
+
+        // status
+        assert_eq!(data.swap_usage, 128 * 1000);
+        assert_eq!(data.voluntary_context_switches, Some(150));
+        assert_eq!(data.nonvoluntary_context_switches, Some(545));
+        assert_eq!(data.affinity.len(), (*NUM_CPUS).min(16));
+        assert!(data.affinity.iter().all(|allowed| *allowed));
+
+        // io
+        assert_eq!(data.read_chars, Some(323_934_931));
+        assert_eq!(data.write_chars, Some(323_929_600));
+        assert_eq!(data.read_syscalls, Some(632_687));
+        assert_eq!(data.write_syscalls, Some(632_675));
+        assert_eq!(data.read_bytes, Some(4096));
+        assert_eq!(data.write_bytes, Some(323_932_160));
+        assert_eq!(data.cancelled_write_bytes, Some(0));
+
+        // smaps_rollup
+        assert_eq!(
+            data.smaps_rollup,
+            Some(SmapsRollup {
+                rss: 4800 * 1024,
+                pss: 2100 * 1024,
+                uss: 1800 * 1024,
+                shared_clean: 3000 * 1024,
+                shared_dirty: 0,
+            })
+        );
+
+        // cgroup
+        assert_eq!(
+            data.cgroup_path.as_deref(),
+            Some(
+                "/user.slice/user-1000.slice/user@1000.service/app.slice/app-bench\\x2dworker-1234.scope"
+            )
+        );
+        assert_eq!(data.containerization, Containerization::None);
+        assert_eq!(process_data[2].containerization, Containerization::Flatpak);
+
+        assert_eq!(data.oom_score, Some(666));
+        assert_eq!(data.oom_score_adj.map(|adj| *adj), Some(0));
+
+        // none of the file descriptors refer to a GPU
+        assert!(data.gpu_usage_stats.is_empty());
+
+        // threads, the main thread has the PID as its TID
+        assert_eq!(data.threads.len(), THREADS_PER_PROCESS);
+        assert!(data.threads.iter().any(|thread| thread.tid == data.pid));
+        assert!(data.threads.iter().all(|thread| {
+            thread.state == ProcessState::Sleeping
+                && thread.user_cpu_time == 250
+                && thread.system_cpu_time == 75
+        }));
+
+        // smaps_rollup is only read if asked for
+        scanner.set_options(ProcessDataOptions::default());
+        assert!(
+            scanner
+                .scan()
+                .unwrap()
+                .iter()
+                .all(|data| data.smaps_rollup.is_none())
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::LazyLock;
+
+use crate::NUM_CPUS;
+
+/// Clock ticks per second, the unit of CPU times in procfs
+pub static TICK_RATE: LazyLock<usize> =
+    LazyLock::new(|| sysconf::sysconf(sysconf::SysconfVariable::ScClkTck).unwrap_or(100) as usize);
+
+/// Returns the share of the whole system's CPU time (i.e. all logical CPUs together) that was spent between two
+/// snapshots, from 0.0 to 1.0. `cpu_time` is user plus system CPU time in clock ticks, timestamps are in milliseconds.
+/// Returns 0.0 if there is no previous snapshot, i.e. `cpu_time_last` is 0.
+#[must_use]
+pub fn cpu_time_ratio(
+    cpu_time: u64,
+    cpu_time_last: u64,
+    timestamp: u64,
+    timestamp_last: u64,
+) -> f32 {
+    if cpu_time_last == 0 {
+        return 0.0;
+    }
+
+    let delta_cpu_time = cpu_time.saturating_sub(cpu_time_last) as f32 * 1000.0;
+    let delta_time = timestamp.saturating_sub(timestamp_last);
+
+    let ratio = delta_cpu_time
+        / (delta_time
+            .saturating_mul(*TICK_RATE as u64)
+            .saturating_mul(*NUM_CPUS as u64)) as f32;
+
+    if ratio.is_finite() { ratio } else { 0.0 }
+}
+
+/// Returns how much a monotonically increasing counter (e.g. `read_bytes`) grew per second between two snapshots,
+/// timestamps are in milliseconds. Returns 0.0 if there is no previous snapshot, i.e. `timestamp_last` is 0.
+#[must_use]
+pub fn per_second(value: u64, value_last: u64, timestamp: u64, timestamp_last: u64) -> f64 {
+    if timestamp_last == 0 {
+        return 0.0;
+    }
+
+    let delta = value.saturating_sub(value_last) as f64;
+    let time_delta = timestamp.saturating_sub(timestamp_last) as f64;
+
+    (delta / time_delta) * 1000.0
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{NUM_CPUS, TICK_RATE, cpu_time_ratio, per_second};
+
+    #[test]
+    fn cpu_time() {
+        // one CPU fully busy for a second
+        let ticks = *TICK_RATE as u64;
+        let ratio = cpu_time_ratio(1000 + ticks, 1000, 6000, 5000);
+        assert!((ratio - 1.0 / *NUM_CPUS as f32).abs() < 0.0001);
+
+        assert_eq!(cpu_time_ratio(1000, 0, 6000, 5000), 0.0);
+        assert_eq!(cpu_time_ratio(1000, 1000, 5000, 5000), 0.0);
+    }
+
+    #[test]
+    fn counter() {
+        assert_eq!(per_second(3000, 1000, 6000, 4000), 1000.0);
+        assert_eq!(per_second(3000, 1000, 6000, 0), 0.0);
+        // counters shouldn't shrink, but if they do, don't underflow
+        assert_eq!(per_second(1000, 3000, 6000, 4000), 0.0);
+    }
+}
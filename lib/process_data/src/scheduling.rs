@@ -0,0 +1,322 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use syscalls::{Sysno, syscall};
+
+// see linux/ioprio.h, these are not part of the libc crate
+const IOPRIO_WHO_PROCESS: usize = 1;
+const IOPRIO_CLASS_SHIFT: u16 = 13;
+const IOPRIO_LEVEL_MASK: u16 = (1 << IOPRIO_CLASS_SHIFT) - 1;
+
+/// The highest (and thus least important) level of the real-time and best-effort I/O scheduling classes
+pub const IOPRIO_MAX_LEVEL: u8 = 7;
+
+/// The I/O scheduling class of a process, see man ioprio_set(2)
+#[derive(
+    Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord,
+)]
+pub enum IoPriorityClass {
+    /// No class has been set explicitly, the kernel derives a best-effort level from the niceness then
+    #[default]
+    None,
+    RealTime,
+    BestEffort,
+    Idle,
+}
+
+/// The I/O priority of a process consisting of its class and, for the real-time and best-effort classes, a level
+/// between 0 (highest priority) and 7 (lowest priority)
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IoPriority {
+    pub class: IoPriorityClass,
+    pub level: u8,
+}
+
+impl IoPriority {
+    pub fn from_raw(raw: u16) -> Self {
+        let class = match raw >> IOPRIO_CLASS_SHIFT {
+            1 => IoPriorityClass::RealTime,
+            2 => IoPriorityClass::BestEffort,
+            3 => IoPriorityClass::Idle,
+            _ => IoPriorityClass::None,
+        };
+
+        Self {
+            class,
+            level: (raw & IOPRIO_LEVEL_MASK).min(u16::from(IOPRIO_MAX_LEVEL)) as u8,
+        }
+    }
+
+    pub fn to_raw(self) -> u16 {
+        let (class, level) = match self.class {
+            IoPriorityClass::None => (0, 0),
+            IoPriorityClass::RealTime => (1, self.level.min(IOPRIO_MAX_LEVEL)),
+            IoPriorityClass::BestEffort => (2, self.level.min(IOPRIO_MAX_LEVEL)),
+            // the idle class has no levels
+            IoPriorityClass::Idle => (3, 0),
+        };
+
+        (class << IOPRIO_CLASS_SHIFT) | u16::from(level)
+    }
+
+    /// Reads the I/O priority of the thread with the ID `tid`, for the main thread this is its PID
+    pub fn of_thread(tid: libc::pid_t) -> Result<Self> {
+        let raw = unsafe { syscall!(Sysno::ioprio_get, IOPRIO_WHO_PROCESS, tid) }
+            .map_err(|errno| std::io::Error::from_raw_os_error(errno.into_raw()))
+            .context("ioprio_get failed")?;
+
+        Ok(Self::from_raw(raw as u16))
+    }
+
+    /// Sets the I/O priority of the thread with the ID `tid`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with the `errno` of `ioprio_set`, usually `EPERM` if we're not allowed to change the
+    /// priority of that thread
+    pub fn apply(self, tid: libc::pid_t) -> std::io::Result<()> {
+        unsafe {
+            syscall!(
+                Sysno::ioprio_set,
+                IOPRIO_WHO_PROCESS,
+                tid,
+                usize::from(self.to_raw())
+            )
+        }
+        .map(|_| ())
+        .map_err(|errno| std::io::Error::from_raw_os_error(errno.into_raw()))
+    }
+}
+
+/// The format is the same as accepted by `ionice`'s class names, e.g. `best-effort/4`, `idle` or `none`
+impl Display for IoPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.class {
+            IoPriorityClass::None => write!(f, "none"),
+            IoPriorityClass::RealTime => write!(f, "realtime/{}", self.level),
+            IoPriorityClass::BestEffort => write!(f, "best-effort/{}", self.level),
+            IoPriorityClass::Idle => write!(f, "idle"),
+        }
+    }
+}
+
+impl FromStr for IoPriority {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (class, level) = s.split_once('/').unwrap_or((s, "0"));
+
+        let class = match class {
+            "none" => IoPriorityClass::None,
+            "realtime" => IoPriorityClass::RealTime,
+            "best-effort" => IoPriorityClass::BestEffort,
+            "idle" => IoPriorityClass::Idle,
+            _ => bail!("unknown I/O scheduling class {class}"),
+        };
+
+        let level = level.parse().context("invalid I/O priority level")?;
+        if level > IOPRIO_MAX_LEVEL {
+            bail!("I/O priority level {level} is out of range")
+        }
+
+        Ok(Self { class, level })
+    }
+}
+
+/// The CPU scheduling policy of a process, see man sched(7)
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchedulingPolicy {
+    #[default]
+    Other,
+    Batch,
+    Idle,
+    /// Contains the real-time priority between 1 and 99
+    Fifo(u32),
+    /// Contains the real-time priority between 1 and 99
+    RoundRobin(u32),
+    /// Can only be displayed, we don't support setting this policy since it needs additional parameters
+    Deadline,
+}
+
+impl SchedulingPolicy {
+    pub const MIN_RT_PRIORITY: u32 = 1;
+    pub const MAX_RT_PRIORITY: u32 = 99;
+
+    /// Builds the policy from the `policy` and `rt_priority` fields of `/proc/<pid>/stat`
+    pub fn from_raw(policy: i32, rt_priority: u32) -> Option<Self> {
+        match policy {
+            libc::SCHED_OTHER => Some(Self::Other),
+            libc::SCHED_BATCH => Some(Self::Batch),
+            libc::SCHED_IDLE => Some(Self::Idle),
+            libc::SCHED_FIFO => Some(Self::Fifo(rt_priority)),
+            libc::SCHED_RR => Some(Self::RoundRobin(rt_priority)),
+            // not exposed by the libc crate, see linux/sched.h
+            6 => Some(Self::Deadline),
+            _ => None,
+        }
+    }
+
+    pub fn is_real_time(self) -> bool {
+        matches!(self, Self::Fifo(_) | Self::RoundRobin(_))
+    }
+
+    /// Sets the scheduling policy of the thread with the ID `tid`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with the `errno` of `sched_setscheduler`, usually `EPERM` if we're not allowed to change
+    /// the policy of that thread or lack the privileges for real-time policies
+    pub fn apply(self, tid: libc::pid_t) -> std::io::Result<()> {
+        let (policy, priority) = match self {
+            Self::Other => (libc::SCHED_OTHER, 0),
+            Self::Batch => (libc::SCHED_BATCH, 0),
+            Self::Idle => (libc::SCHED_IDLE, 0),
+            Self::Fifo(priority) => (libc::SCHED_FIFO, priority),
+            Self::RoundRobin(priority) => (libc::SCHED_RR, priority),
+            Self::Deadline => return Err(std::io::Error::from_raw_os_error(libc::EINVAL)),
+        };
+
+        let param = libc::sched_param {
+            sched_priority: priority as i32,
+        };
+
+        if unsafe { libc::sched_setscheduler(tid, policy, &param) } == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
+}
+
+/// The format uses the policy names of `chrt`, e.g. `other`, `batch` or `fifo/50`
+impl Display for SchedulingPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Other => write!(f, "other"),
+            Self::Batch => write!(f, "batch"),
+            Self::Idle => write!(f, "idle"),
+            Self::Fifo(priority) => write!(f, "fifo/{priority}"),
+            Self::RoundRobin(priority) => write!(f, "rr/{priority}"),
+            Self::Deadline => write!(f, "deadline"),
+        }
+    }
+}
+
+impl FromStr for SchedulingPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (policy, priority) = match s.split_once('/') {
+            Some((policy, priority)) => (
+                policy,
+                Some(
+                    priority
+                        .parse::<u32>()
+                        .context("invalid real-time priority")?,
+                ),
+            ),
+            None => (s, None),
+        };
+
+        let real_time_priority = || {
+            priority
+                .filter(|priority| {
+                    (Self::MIN_RT_PRIORITY..=Self::MAX_RT_PRIORITY).contains(priority)
+                })
+                .context("real-time policies need a priority between 1 and 99")
+        };
+
+        match policy {
+            "other" => Ok(Self::Other),
+            "batch" => Ok(Self::Batch),
+            "idle" => Ok(Self::Idle),
+            "fifo" => Ok(Self::Fifo(real_time_priority()?)),
+            "rr" => Ok(Self::RoundRobin(real_time_priority()?)),
+            _ => bail!("unknown or unsupported scheduling policy {policy}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{IoPriority, IoPriorityClass, SchedulingPolicy};
+
+    #[test]
+    fn io_priority_raw() {
+        let best_effort = IoPriority {
+            class: IoPriorityClass::BestEffort,
+            level: 4,
+        };
+        assert_eq!(best_effort.to_raw(), (2 << 13) | 4);
+        assert_eq!(IoPriority::from_raw(best_effort.to_raw()), best_effort);
+
+        assert_eq!(IoPriority::from_raw(0), IoPriority::default());
+        assert_eq!(
+            IoPriority::from_raw(3 << 13),
+            IoPriority {
+                class: IoPriorityClass::Idle,
+                level: 0
+            }
+        );
+    }
+
+    #[test]
+    fn io_priority_from_str() {
+        assert_eq!(
+            "realtime/2".parse::<IoPriority>().unwrap(),
+            IoPriority {
+                class: IoPriorityClass::RealTime,
+                level: 2
+            }
+        );
+        assert_eq!(
+            "idle".parse::<IoPriority>().unwrap().class,
+            IoPriorityClass::Idle
+        );
+        assert!("best-effort/8".parse::<IoPriority>().is_err());
+        assert!("urgent/1".parse::<IoPriority>().is_err());
+
+        let best_effort = IoPriority {
+            class: IoPriorityClass::BestEffort,
+            level: 7,
+        };
+        assert_eq!(
+            best_effort.to_string().parse::<IoPriority>().unwrap(),
+            best_effort
+        );
+    }
+
+    #[test]
+    fn scheduling_policy_from_str() {
+        assert_eq!(
+            "batch".parse::<SchedulingPolicy>().unwrap(),
+            SchedulingPolicy::Batch
+        );
+        assert_eq!(
+            "rr/10".parse::<SchedulingPolicy>().unwrap(),
+            SchedulingPolicy::RoundRobin(10)
+        );
+        assert!("fifo".parse::<SchedulingPolicy>().is_err());
+        assert!("fifo/100".parse::<SchedulingPolicy>().is_err());
+        assert!("deadline".parse::<SchedulingPolicy>().is_err());
+
+        let fifo = SchedulingPolicy::Fifo(50);
+        assert_eq!(fifo.to_string().parse::<SchedulingPolicy>().unwrap(), fifo);
+    }
+
+    #[test]
+    fn scheduling_policy_from_raw() {
+        assert_eq!(
+            SchedulingPolicy::from_raw(libc::SCHED_FIFO, 50),
+            Some(SchedulingPolicy::Fifo(50))
+        );
+        assert_eq!(
+            SchedulingPolicy::from_raw(libc::SCHED_IDLE, 0),
+            Some(SchedulingPolicy::Idle)
+        );
+        assert_eq!(SchedulingPolicy::from_raw(42, 0), None);
+    }
+}
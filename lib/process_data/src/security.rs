@@ -0,0 +1,290 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Names of the capabilities by their bit number, see linux/capability.h
+const CAPABILITY_NAMES: [&str; 41] = [
+    "CAP_CHOWN",
+    "CAP_DAC_OVERRIDE",
+    "CAP_DAC_READ_SEARCH",
+    "CAP_FOWNER",
+    "CAP_FSETID",
+    "CAP_KILL",
+    "CAP_SETGID",
+    "CAP_SETUID",
+    "CAP_SETPCAP",
+    "CAP_LINUX_IMMUTABLE",
+    "CAP_NET_BIND_SERVICE",
+    "CAP_NET_BROADCAST",
+    "CAP_NET_ADMIN",
+    "CAP_NET_RAW",
+    "CAP_IPC_LOCK",
+    "CAP_IPC_OWNER",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_CHROOT",
+    "CAP_SYS_PTRACE",
+    "CAP_SYS_PACCT",
+    "CAP_SYS_ADMIN",
+    "CAP_SYS_BOOT",
+    "CAP_SYS_NICE",
+    "CAP_SYS_RESOURCE",
+    "CAP_SYS_TIME",
+    "CAP_SYS_TTY_CONFIG",
+    "CAP_MKNOD",
+    "CAP_LEASE",
+    "CAP_AUDIT_WRITE",
+    "CAP_AUDIT_CONTROL",
+    "CAP_SETFCAP",
+    "CAP_MAC_OVERRIDE",
+    "CAP_MAC_ADMIN",
+    "CAP_SYSLOG",
+    "CAP_WAKE_ALARM",
+    "CAP_BLOCK_SUSPEND",
+    "CAP_AUDIT_READ",
+    "CAP_PERFMON",
+    "CAP_BPF",
+    "CAP_CHECKPOINT_RESTORE",
+];
+
+/// Names of the standard signals, the signal number is the index plus one
+const SIGNAL_NAMES: [&str; 31] = [
+    "SIGHUP",
+    "SIGINT",
+    "SIGQUIT",
+    "SIGILL",
+    "SIGTRAP",
+    "SIGABRT",
+    "SIGBUS",
+    "SIGFPE",
+    "SIGKILL",
+    "SIGUSR1",
+    "SIGSEGV",
+    "SIGUSR2",
+    "SIGPIPE",
+    "SIGALRM",
+    "SIGTERM",
+    "SIGSTKFLT",
+    "SIGCHLD",
+    "SIGCONT",
+    "SIGSTOP",
+    "SIGTSTP",
+    "SIGTTIN",
+    "SIGTTOU",
+    "SIGURG",
+    "SIGXCPU",
+    "SIGXFSZ",
+    "SIGVTALRM",
+    "SIGPROF",
+    "SIGWINCH",
+    "SIGIO",
+    "SIGPWR",
+    "SIGSYS",
+];
+
+/// The first real-time signal as seen by programs using glibc, which reserves the two before it for internal use
+const SIGRTMIN: u32 = 34;
+
+/// The seccomp mode of a process, see man seccomp(2)
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SeccompMode {
+    #[default]
+    Disabled,
+    /// Only `read`, `write`, `_exit` and `sigreturn` are allowed
+    Strict,
+    /// System calls are filtered by a BPF program
+    Filter,
+}
+
+/// Security-related attributes of a process, most of them gathered from `/proc/<pid>/status`
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityContext {
+    /// Bitmask of the capabilities the kernel checks for permissions, `CapEff`
+    pub effective_capabilities: Option<u64>,
+    /// Bitmask of the capabilities the process may assume, `CapPrm`
+    pub permitted_capabilities: Option<u64>,
+    /// Bitmask limiting the capabilities the process and its children can ever gain, `CapBnd`
+    pub bounding_capabilities: Option<u64>,
+    pub no_new_privs: Option<bool>,
+    /// `None` if the kernel has been built without seccomp support
+    pub seccomp: Option<SeccompMode>,
+    pub blocked_signals: Option<u64>,
+    pub ignored_signals: Option<u64>,
+    pub caught_signals: Option<u64>,
+    /// The label of the process according to the active LSM (e.g. SELinux or AppArmor), `None` if there is none
+    pub lsm_label: Option<String>,
+}
+
+impl SecurityContext {
+    /// Parses the contents of `/proc/<pid>/status` and reads the LSM label from `/proc/<pid>/attr/current`
+    pub fn from_proc_path<P: AsRef<Path>, S: AsRef<str>>(proc_path: P, status: S) -> Self {
+        let mut security_context = Self::from_status(status);
+
+        security_context.lsm_label = std::fs::read(proc_path.as_ref().join("attr").join("current"))
+            .ok()
+            .and_then(|label| Self::parse_lsm_label(&label));
+
+        security_context
+    }
+
+    /// Parses the contents of `/proc/<pid>/status`, fields that are missing are left as `None`
+    pub fn from_status<S: AsRef<str>>(status: S) -> Self {
+        let mut security_context = Self::default();
+
+        for line in status.as_ref().lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            let value = value.trim();
+            let hex = || u64::from_str_radix(value, 16).ok();
+
+            match key {
+                "CapEff" => security_context.effective_capabilities = hex(),
+                "CapPrm" => security_context.permitted_capabilities = hex(),
+                "CapBnd" => security_context.bounding_capabilities = hex(),
+                "SigBlk" => security_context.blocked_signals = hex(),
+                "SigIgn" => security_context.ignored_signals = hex(),
+                "SigCgt" => security_context.caught_signals = hex(),
+                "NoNewPrivs" => security_context.no_new_privs = Some(value == "1"),
+                "Seccomp" => {
+                    security_context.seccomp = match value {
+                        "0" => Some(SeccompMode::Disabled),
+                        "1" => Some(SeccompMode::Strict),
+                        "2" => Some(SeccompMode::Filter),
+                        _ => None,
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        security_context
+    }
+
+    /// The label is terminated by a newline (AppArmor) or a NUL byte (SELinux)
+    fn parse_lsm_label(label: &[u8]) -> Option<String> {
+        let label = String::from_utf8_lossy(label);
+        let label = label.trim_end_matches(['\0', '\n']);
+
+        if label.is_empty() {
+            None
+        } else {
+            Some(label.to_string())
+        }
+    }
+}
+
+/// Returns whether `mask` contains every capability we know about
+pub fn has_all_capabilities(mask: u64) -> bool {
+    let all = (1u64 << CAPABILITY_NAMES.len()) - 1;
+    mask & all == all
+}
+
+/// Decodes a capability bitmask into capability names, bits of capabilities unknown to us are named by their number
+pub fn capability_names(mask: u64) -> Vec<String> {
+    (0..u64::BITS)
+        .filter(|bit| mask & (1 << bit) != 0)
+        .map(|bit| {
+            CAPABILITY_NAMES
+                .get(bit as usize)
+                .map_or_else(|| format!("CAP_{bit}"), |name| (*name).to_string())
+        })
+        .collect()
+}
+
+/// Decodes a signal bitmask (as found in `SigBlk`, `SigIgn` and `SigCgt`) into signal names, the lowest bit
+/// corresponds to signal 1
+pub fn signal_names(mask: u64) -> Vec<String> {
+    (0..u64::BITS)
+        .filter(|bit| mask & (1 << bit) != 0)
+        .map(|bit| {
+            let signal = bit + 1;
+            match SIGNAL_NAMES.get(bit as usize) {
+                Some(name) => (*name).to_string(),
+                None if signal == SIGRTMIN => "SIGRTMIN".to_string(),
+                None if signal > SIGRTMIN => format!("SIGRTMIN+{}", signal - SIGRTMIN),
+                // reserved by glibc
+                None => format!("SIG{signal}"),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        SeccompMode, SecurityContext, capability_names, has_all_capabilities, signal_names,
+    };
+
+    const STATUS: &str = "Name:\tsshd
+Umask:\t0022
+State:\tS (sleeping)
+SigQ:\t0/62582
+SigPnd:\t0000000000000000
+ShdPnd:\t0000000000000000
+SigBlk:\t0000000000010000
+SigIgn:\t0000000000001000
+SigCgt:\t0000000180004003
+CapInh:\t0000000000000000
+CapPrm:\t000001ffffffffff
+CapEff:\t0000000000003000
+CapBnd:\t000001ffffffffff
+CapAmb:\t0000000000000000
+NoNewPrivs:\t1
+Seccomp:\t2
+Seccomp_filters:\t1
+";
+
+    #[test]
+    fn from_status() {
+        let security_context = SecurityContext::from_status(STATUS);
+
+        assert_eq!(
+            security_context,
+            SecurityContext {
+                effective_capabilities: Some(0x3000),
+                permitted_capabilities: Some(0x1ff_ffff_ffff),
+                bounding_capabilities: Some(0x1ff_ffff_ffff),
+                no_new_privs: Some(true),
+                seccomp: Some(SeccompMode::Filter),
+                blocked_signals: Some(0x10000),
+                ignored_signals: Some(0x1000),
+                caught_signals: Some(0x1_8000_4003),
+                lsm_label: None,
+            }
+        );
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(
+            capability_names(0x3000),
+            vec!["CAP_NET_ADMIN".to_string(), "CAP_NET_RAW".to_string()]
+        );
+        assert_eq!(capability_names(1 << 45), vec!["CAP_45".to_string()]);
+        assert!(has_all_capabilities(0x1ff_ffff_ffff));
+        assert!(!has_all_capabilities(0x3000));
+
+        assert_eq!(
+            signal_names(0x1_8000_4003),
+            vec!["SIGHUP", "SIGINT", "SIGTERM", "SIG32", "SIG33"]
+        );
+        assert_eq!(signal_names(0b11 << 33), vec!["SIGRTMIN", "SIGRTMIN+1"]);
+    }
+
+    #[test]
+    fn lsm_label() {
+        assert_eq!(
+            SecurityContext::parse_lsm_label(b"system_u:system_r:sshd_t:s0-s0:c0.c1023\0"),
+            Some("system_u:system_r:sshd_t:s0-s0:c0.c1023".to_string())
+        );
+        assert_eq!(
+            SecurityContext::parse_lsm_label(b"unconfined\n"),
+            Some("unconfined".to_string())
+        );
+        assert_eq!(SecurityContext::parse_lsm_label(b""), None);
+    }
+}
@@ -0,0 +1,432 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::Path,
+};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fs_root::{PROCFS_ROOT, procfs},
+    open_file::FileDescriptorKind,
+};
+
+/// Flag that the kernel sets for Unix sockets that are listening for connections (`__SO_ACCEPTCON`)
+const UNIX_FLAG_ACCEPTCON: u32 = 0x10000;
+
+/// Unix socket state for connected sockets (`SS_CONNECTED`)
+const UNIX_STATE_CONNECTED: u8 = 3;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SocketProtocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl SocketProtocol {
+    /// The name of the file in `/proc/<pid>/net/` that lists the sockets of this protocol
+    pub fn proc_file_name(&self) -> &'static str {
+        match self {
+            SocketProtocol::Tcp => "tcp",
+            SocketProtocol::Tcp6 => "tcp6",
+            SocketProtocol::Udp => "udp",
+            SocketProtocol::Udp6 => "udp6",
+            SocketProtocol::Unix => "unix",
+        }
+    }
+
+    pub const ALL: [SocketProtocol; 5] = [
+        SocketProtocol::Tcp,
+        SocketProtocol::Tcp6,
+        SocketProtocol::Udp,
+        SocketProtocol::Udp6,
+        SocketProtocol::Unix,
+    ];
+
+    pub const INET: [SocketProtocol; 4] = [
+        SocketProtocol::Tcp,
+        SocketProtocol::Tcp6,
+        SocketProtocol::Udp,
+        SocketProtocol::Udp6,
+    ];
+}
+
+impl Display for SocketProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SocketProtocol::Tcp => "TCP",
+                SocketProtocol::Tcp6 => "TCPv6",
+                SocketProtocol::Udp => "UDP",
+                SocketProtocol::Udp6 => "UDPv6",
+                SocketProtocol::Unix => "Unix",
+            }
+        )
+    }
+}
+
+/// The state of a socket, following the TCP states the kernel reports in `/proc/net/tcp`.
+///
+/// UDP sockets reuse these states (`Established` for connected sockets, `Close` otherwise) and
+/// Unix sockets are mapped onto `Listen`, `Established` and `Close`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, Hash, PartialEq, Eq)]
+pub enum SocketState {
+    Established,
+    SynSent,
+    SynRecv,
+    FinWait1,
+    FinWait2,
+    TimeWait,
+    Close,
+    CloseWait,
+    LastAck,
+    Listen,
+    Closing,
+    NewSynRecv,
+    #[default]
+    Unknown,
+}
+
+impl From<u8> for SocketState {
+    fn from(value: u8) -> Self {
+        match value {
+            0x01 => SocketState::Established,
+            0x02 => SocketState::SynSent,
+            0x03 => SocketState::SynRecv,
+            0x04 => SocketState::FinWait1,
+            0x05 => SocketState::FinWait2,
+            0x06 => SocketState::TimeWait,
+            0x07 => SocketState::Close,
+            0x08 => SocketState::CloseWait,
+            0x09 => SocketState::LastAck,
+            0x0A => SocketState::Listen,
+            0x0B => SocketState::Closing,
+            0x0C => SocketState::NewSynRecv,
+            _ => SocketState::Unknown,
+        }
+    }
+}
+
+/// A socket as listed in one of the files in `/proc/<pid>/net/`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Socket {
+    pub inode: u64,
+    pub protocol: SocketProtocol,
+    pub state: SocketState,
+    /// Only available for TCP and UDP sockets
+    pub local_address: Option<SocketAddr>,
+    /// Only available for TCP and UDP sockets
+    pub remote_address: Option<SocketAddr>,
+    /// Only available for bound Unix sockets
+    pub path: Option<String>,
+}
+
+impl Socket {
+    /// Reads all sockets of the given protocols of the network namespace that `net_path`
+    /// (usually `/proc/<pid>/net`) belongs to and maps them by their inode.
+    ///
+    /// Protocols whose files can't be read (e.g. because IPv6 is disabled) are skipped.
+    pub fn all_in<P: AsRef<Path>>(
+        net_path: P,
+        protocols: &[SocketProtocol],
+    ) -> HashMap<u64, Socket> {
+        let net_path = net_path.as_ref();
+
+        protocols
+            .iter()
+            .copied()
+            .filter_map(|protocol| {
+                std::fs::read_to_string(net_path.join(protocol.proc_file_name()))
+                    .ok()
+                    .map(|contents| Self::from_table(&contents, protocol))
+            })
+            .flatten()
+            .map(|socket| (socket.inode, socket))
+            .collect()
+    }
+
+    /// Parses the contents of a socket table (e.g. `/proc/net/tcp`), skipping its header line
+    /// and every line that could not be parsed.
+    pub fn from_table<S: AsRef<str>>(contents: S, protocol: SocketProtocol) -> Vec<Socket> {
+        contents
+            .as_ref()
+            .lines()
+            .skip(1)
+            .filter_map(|line| Self::from_line(line, protocol).ok())
+            .collect()
+    }
+
+    pub fn from_line<S: AsRef<str>>(line: S, protocol: SocketProtocol) -> Result<Self> {
+        if protocol == SocketProtocol::Unix {
+            Self::from_unix_line(line.as_ref())
+        } else {
+            Self::from_inet_line(line.as_ref(), protocol)
+        }
+    }
+
+    // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode ...
+    fn from_inet_line(line: &str, protocol: SocketProtocol) -> Result<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+
+        let local_address = fields
+            .get(1)
+            .context("missing local address")
+            .and_then(|address| parse_inet_address(address))?;
+
+        let remote_address = fields
+            .get(2)
+            .context("missing remote address")
+            .and_then(|address| parse_inet_address(address))?;
+
+        let state = fields
+            .get(3)
+            .context("missing state")
+            .and_then(|state| u8::from_str_radix(state, 16).context("invalid state"))?
+            .into();
+
+        let inode = fields
+            .get(9)
+            .context("missing inode")
+            .and_then(|inode| inode.parse().context("invalid inode"))?;
+
+        Ok(Self {
+            inode,
+            protocol,
+            state,
+            local_address: Some(local_address),
+            remote_address: Some(remote_address),
+            path: None,
+        })
+    }
+
+    // Num RefCount Protocol Flags Type St Inode Path
+    fn from_unix_line(line: &str) -> Result<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+
+        let flags = fields
+            .get(3)
+            .context("missing flags")
+            .and_then(|flags| u32::from_str_radix(flags, 16).context("invalid flags"))?;
+
+        let unix_state = fields
+            .get(5)
+            .context("missing state")
+            .and_then(|state| u8::from_str_radix(state, 16).context("invalid state"))?;
+
+        let inode = fields
+            .get(6)
+            .context("missing inode")
+            .and_then(|inode| inode.parse().context("invalid inode"))?;
+
+        let state = if flags & UNIX_FLAG_ACCEPTCON != 0 {
+            SocketState::Listen
+        } else if unix_state == UNIX_STATE_CONNECTED {
+            SocketState::Established
+        } else {
+            SocketState::Close
+        };
+
+        // the path may contain spaces, so take everything after the inode
+        let path = fields
+            .get(7)
+            .map(|_| fields[7..].join(" "))
+            .filter(|path| !path.is_empty());
+
+        Ok(Self {
+            inode,
+            protocol: SocketProtocol::Unix,
+            state,
+            local_address: None,
+            remote_address: None,
+            path,
+        })
+    }
+}
+
+/// A TCP or UDP socket together with the process that owns it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Connection {
+    pub socket: Socket,
+    /// `None` if no process with an open file descriptor for this socket could be found, e.g.
+    /// because it belongs to another user
+    pub pid: Option<libc::pid_t>,
+}
+
+impl Connection {
+    /// Returns all TCP and UDP sockets of the current network namespace and maps them onto the
+    /// processes that have them open.
+    pub fn all() -> Vec<Self> {
+        let mut sockets = Socket::all_in(procfs("net"), &SocketProtocol::INET);
+
+        let mut connections = Vec::with_capacity(sockets.len());
+
+        if let Ok(proc_entries) = std::fs::read_dir(PROCFS_ROOT.as_path()) {
+            for proc_entry in proc_entries.flatten() {
+                let Some(pid) = proc_entry
+                    .file_name()
+                    .to_str()
+                    .and_then(|name| name.parse::<libc::pid_t>().ok())
+                else {
+                    continue;
+                };
+
+                let Ok(fd_entries) = std::fs::read_dir(proc_entry.path().join("fd")) else {
+                    continue;
+                };
+
+                for fd_entry in fd_entries.flatten() {
+                    let inode = std::fs::read_link(fd_entry.path()).ok().and_then(|target| {
+                        FileDescriptorKind::from_target(&target.to_string_lossy())
+                    });
+
+                    if let Some(FileDescriptorKind::Socket(inode)) = inode {
+                        // a socket can be shared by multiple processes (e.g. after forking), the
+                        // first one we find is considered to be its owner
+                        if let Some(socket) = sockets.remove(&inode) {
+                            connections.push(Self {
+                                socket,
+                                pid: Some(pid),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        connections.extend(
+            sockets
+                .into_values()
+                .map(|socket| Self { socket, pid: None }),
+        );
+
+        connections
+    }
+}
+
+/// Parses an address in the format the kernel uses in `/proc/net/{tcp,tcp6,udp,udp6}`,
+/// e.g. `0100007F:0277` for `127.0.0.1:631`.
+///
+/// The address is printed as a sequence of 32-bit words in host byte order while the port is
+/// printed as a regular hexadecimal number.
+fn parse_inet_address(address: &str) -> Result<SocketAddr> {
+    let (ip, port) = address.split_once(':').context("missing port")?;
+
+    let port = u16::from_str_radix(port, 16).context("invalid port")?;
+
+    let ip = match ip.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(
+            u32::from_str_radix(ip, 16)
+                .context("invalid IPv4 address")?
+                .to_ne_bytes(),
+        )),
+        32 => {
+            let mut octets = [0_u8; 16];
+            for (i, chunk) in octets.chunks_exact_mut(4).enumerate() {
+                let word = u32::from_str_radix(&ip[i * 8..(i + 1) * 8], 16)
+                    .context("invalid IPv6 address")?;
+                chunk.copy_from_slice(&word.to_ne_bytes());
+            }
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => bail!("invalid address length"),
+    };
+
+    Ok(SocketAddr::new(ip, port))
+}
+
+#[cfg(test)]
+#[cfg(target_endian = "little")]
+mod test {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    use super::{Socket, SocketProtocol, SocketState};
+    use pretty_assertions::assert_eq;
+
+    const TCP: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 24513 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:A3C2 22D8B85D:01BB 01 00000000:00000000 02:000005C4 00000000  1000        0 1143720 2 0000000000000000 20 4 30 10 -1
+";
+
+    const TCP6: &str = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:0277 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 24512 1 0000000000000000 100 0 0 10 0
+";
+
+    const UNIX: &str = "Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 21789 /run/systemd/private
+0000000000000000: 00000003 00000000 00000000 0001 03 36527
+0000000000000000: 00000003 00000000 00000000 0001 03 36530 @/tmp/.X11-unix/X0
+";
+
+    #[test]
+    fn tcp_table() {
+        let sockets = Socket::from_table(TCP, SocketProtocol::Tcp);
+        assert_eq!(
+            sockets,
+            vec![
+                Socket {
+                    inode: 24513,
+                    protocol: SocketProtocol::Tcp,
+                    state: SocketState::Listen,
+                    local_address: Some(SocketAddr::new(
+                        IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+                        631
+                    )),
+                    remote_address: Some(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0)),
+                    path: None,
+                },
+                Socket {
+                    inode: 1143720,
+                    protocol: SocketProtocol::Tcp,
+                    state: SocketState::Established,
+                    local_address: Some(SocketAddr::new(
+                        IpAddr::V4(Ipv4Addr::new(10, 0, 2, 15)),
+                        41922
+                    )),
+                    remote_address: Some(SocketAddr::new(
+                        IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34)),
+                        443
+                    )),
+                    path: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tcp6_table() {
+        let sockets = Socket::from_table(TCP6, SocketProtocol::Tcp6);
+        assert_eq!(sockets.len(), 1);
+        assert_eq!(
+            sockets[0].local_address,
+            Some(SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 631))
+        );
+        assert_eq!(sockets[0].state, SocketState::Listen);
+    }
+
+    #[test]
+    fn unix_table() {
+        let sockets = Socket::from_table(UNIX, SocketProtocol::Unix);
+        assert_eq!(sockets.len(), 3);
+
+        assert_eq!(sockets[0].inode, 21789);
+        assert_eq!(sockets[0].state, SocketState::Listen);
+        assert_eq!(sockets[0].path.as_deref(), Some("/run/systemd/private"));
+
+        assert_eq!(sockets[1].state, SocketState::Established);
+        assert_eq!(sockets[1].path, None);
+
+        assert_eq!(sockets[2].path.as_deref(), Some("@/tmp/.X11-unix/X0"));
+    }
+
+    #[test]
+    fn invalid_line() {
+        assert!(Socket::from_line("   0: 0100007F 00000000:0000 0A", SocketProtocol::Tcp).is_err());
+    }
+}
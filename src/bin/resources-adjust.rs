@@ -4,9 +4,20 @@ use nix::{
     sched::{CpuSet, sched_setaffinity},
     unistd::Pid,
 };
+use process_data::{
+    OomScoreAdj,
+    pidfd::ProcessIdentity,
+    scheduling::{IoPriority, SchedulingPolicy},
+};
+
+/// Used in place of the I/O priority, scheduling policy or OOM score adjustment argument to leave it untouched
+const KEEP: &str = "-";
 
 fn main() {
-    if let Some(pid) = env::args().nth(1).and_then(|s| s.trim().parse().ok()) {
+    if let Some(process) = env::args()
+        .nth(1)
+        .and_then(|s| s.trim().parse::<ProcessIdentity>().ok())
+    {
         if let Some(nice) = env::args().nth(2).and_then(|s| s.trim().parse().ok()) {
             if let Some(mask) = env::args().nth(3) {
                 let mut cpu_set = CpuSet::new();
@@ -17,14 +28,47 @@ fn main() {
                     }
                 }
 
-                adjust(pid, nice, &cpu_set);
+                let Ok(io_priority) = optional_arg::<IoPriority>(4) else {
+                    std::process::exit(255);
+                };
+
+                let Ok(scheduling_policy) = optional_arg::<SchedulingPolicy>(5) else {
+                    std::process::exit(255);
+                };
+
+                let Ok(oom_score_adj) = optional_arg::<OomScoreAdj>(6) else {
+                    std::process::exit(255);
+                };
+
+                // refuse to touch the PID if it has been recycled since the user chose to adjust the process, there
+                // are no pidfd-based variants of the syscalls below, so this has to suffice
+                if let Err(error) = process.open() {
+                    std::process::exit(error.raw_os_error().unwrap_or(255))
+                }
+
+                let pid = process.pid;
+
+                // unlike the other values, this one is shared by all threads of the process
+                if let Some(oom_score_adj) = oom_score_adj {
+                    let oom_score_adj_path = PathBuf::from("/proc/")
+                        .join(pid.to_string())
+                        .join("oom_score_adj");
+
+                    if let Err(error) =
+                        std::fs::write(oom_score_adj_path, oom_score_adj.to_string())
+                    {
+                        std::process::exit(error.raw_os_error().unwrap_or(255))
+                    }
+                }
+
+                adjust(pid, nice, &cpu_set, io_priority, scheduling_policy);
 
                 // find tasks that belong to this process
                 let tasks_path = PathBuf::from("/proc/").join(pid.to_string()).join("task");
                 for entry in std::fs::read_dir(tasks_path).unwrap().flatten() {
                     let thread_id = entry.file_name().to_string_lossy().parse().unwrap();
 
-                    adjust(thread_id, nice, &cpu_set);
+                    adjust(thread_id, nice, &cpu_set, io_priority, scheduling_policy);
                 }
 
                 std::process::exit(0)
@@ -34,7 +78,29 @@ fn main() {
     std::process::exit(255);
 }
 
-fn adjust(id: i32, nice: i32, cpu_set: &CpuSet) {
+/// Parses the `n`th argument, returns `Ok(None)` if it is missing or `KEEP`
+fn optional_arg<T: std::str::FromStr>(n: usize) -> Result<Option<T>, T::Err> {
+    env::args()
+        .nth(n)
+        .filter(|arg| arg != KEEP)
+        .map(|arg| arg.trim().parse())
+        .transpose()
+}
+
+fn adjust(
+    id: i32,
+    nice: i32,
+    cpu_set: &CpuSet,
+    io_priority: Option<IoPriority>,
+    scheduling_policy: Option<SchedulingPolicy>,
+) {
+    // the niceness is ignored for real-time policies, so switch the policy first in case we're leaving one
+    if let Some(scheduling_policy) = scheduling_policy {
+        if let Err(error) = scheduling_policy.apply(id) {
+            std::process::exit(error.raw_os_error().unwrap_or(255))
+        }
+    }
+
     unsafe {
         libc::setpriority(libc::PRIO_PROCESS, id as u32, nice);
     };
@@ -47,5 +113,11 @@ fn adjust(id: i32, nice: i32, cpu_set: &CpuSet) {
         std::process::exit(error)
     }
 
+    if let Some(io_priority) = io_priority {
+        if let Err(error) = io_priority.apply(id) {
+            std::process::exit(error.raw_os_error().unwrap_or(255))
+        }
+    }
+
     let _ = sched_setaffinity(Pid::from_raw(id), cpu_set);
 }
@@ -1,24 +1,57 @@
 use std::env;
 
-use nix::{sys::signal, unistd::Pid};
+use nix::sys::signal;
+use process_data::pidfd::ProcessIdentity;
 
+/// Usage: `resources-kill <pid>:<start time>[,<pid>:<start time>…] <signal>`
+///
+/// `signal` is the name of the signal without the `SIG` prefix, e.g. `TERM` or `HUP`. If multiple processes are
+/// given, they're signalled in the given order and processes that have already exited in the meantime are skipped.
+/// This is used to end whole process trees leaf-first. Processes whose PID has been recycled by another process are
+/// never signalled, the exit code is `ESTALE` then.
+///
+/// Every process that has been dealt with is printed as `<pid>:<start time> <errno>` on its own line, `0` meaning that
+/// it has been signalled. If a process can't be signalled for any other reason, e.g. `EPERM`, this exits with that
+/// `errno` right away without touching the remaining processes, so that only those need to be retried with pkexec.
 fn main() {
-    if let Some(pid) = env::args().nth(1).and_then(|s| s.trim().parse().ok()) {
+    if let Some(processes) = env::args().nth(1).and_then(|s| {
+        s.trim()
+            .split(',')
+            .map(|process| process.parse().ok())
+            .collect::<Option<Vec<ProcessIdentity>>>()
+    }) {
         if let Some(arg) = env::args().nth(2) {
-            let signal = match arg.as_str() {
-                "STOP" => signal::Signal::SIGSTOP,
-                "CONT" => signal::Signal::SIGCONT,
-                "TERM" => signal::Signal::SIGTERM,
-                "KILL" => signal::Signal::SIGKILL,
-                _ => std::process::exit(254),
+            let Ok(signal) = format!("SIG{arg}").parse::<signal::Signal>() else {
+                std::process::exit(254)
             };
-            let result = signal::kill(Pid::from_raw(pid), Some(signal));
-            if let Err(errno) = result {
-                match errno {
-                    nix::errno::Errno::UnknownErrno => std::process::exit(253),
-                    _ => std::process::exit(errno as i32),
+
+            let mut stale = false;
+
+            for process in processes {
+                let result = process
+                    .open()
+                    .and_then(|pidfd| pidfd.send_signal(signal as i32));
+
+                let errno = match result.map_err(|error| error.raw_os_error()) {
+                    Ok(()) => 0,
+                    // the process is already gone, e.g. because its exiting parent took it down
+                    Err(Some(libc::ESRCH)) => libc::ESRCH,
+                    // keep going with the others, the PID just belongs to an unrelated process by now
+                    Err(Some(libc::ESTALE)) => {
+                        stale = true;
+                        libc::ESTALE
+                    }
+                    Err(Some(errno)) => std::process::exit(errno),
+                    Err(None) => std::process::exit(253),
                 };
+
+                println!("{process} {errno}");
             }
+
+            if stale {
+                std::process::exit(libc::ESTALE);
+            }
+
             std::process::exit(0);
         }
     }
@@ -1,9 +1,52 @@
 use anyhow::Result;
-use process_data::ProcessData;
+use process_data::{
+    CompanionError, CompanionFrame, CompanionHandshake, CompanionRequest, ProcessData,
+    ProcessDataOptions,
+    cgroup::CgroupLimits,
+    environ::EnvironmentVariable,
+    fs_root::{PROCFS_ROOT_VAR, SYSFS_ROOT_VAR},
+    open_file::OpenFile,
+    pidfd::ProcessIdentity,
+    rates,
+    socket::Connection,
+};
 use ron::ser::PrettyConfig;
-use std::io::{Read, Write};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    io::{IsTerminal, Read, Write},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+
+/// How long to wait between the two snapshots needed for CPU and I/O rates if `--watch` isn't used
+const HUMAN_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Format {
+    /// Length-prefixed MessagePack, this is what Resources expects
+    #[default]
+    #[value(name = "msgpack")]
+    MessagePack,
+    /// Length-prefixed Rusty Object Notation, same as `--ron`
+    Ron,
+    /// One compact JSON document per response, terminated by a newline
+    Json,
+    /// Newline-delimited JSON, lists (e.g. of processes) are written with one element per line
+    Ndjson,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum SortBy {
+    #[default]
+    Cpu,
+    Mem,
+    /// Combined read and write speed
+    Io,
+}
 
 #[derive(Parser, Debug)]
 #[command(version, about)]
@@ -15,41 +58,393 @@ struct Args {
     /// Use Rusty Object Notation (use this only for debugging this binary on its own, Resources won't be able to decode RON)
     #[arg(short, long, default_value_t = false)]
     ron: bool,
+
+    /// Push process data every MS milliseconds on our own instead of answering requests from stdin
+    #[arg(
+        short,
+        long,
+        value_name = "MS",
+        conflicts_with_all = ["once", "human"],
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    interval: Option<u64>,
+
+    /// Also read the smaps_rollup of every process to know its proportional and unique memory usage, this makes
+    /// gathering process data considerably slower
+    #[arg(long, default_value_t = false)]
+    smaps_rollup: bool,
+
+    /// Output format, Resources itself can only decode MessagePack
+    #[arg(short, long, value_enum, default_value_t, conflicts_with = "ron")]
+    format: Format,
+
+    /// Output the environment variables of the process with this PID once and then exit, exits with EACCES if they
+    /// can't be read due to missing permissions
+    #[arg(long, value_name = "PID")]
+    environ: Option<libc::pid_t>,
+
+    /// Print a table of processes for humans, similar to top(1), instead of the machine-readable output
+    #[arg(long, default_value_t = false)]
+    human: bool,
+
+    /// Sort the table by this column, descending
+    #[arg(long, value_enum, default_value_t, requires = "human")]
+    sort: SortBy,
+
+    /// Only show processes of this user
+    #[arg(long, value_name = "USER", requires = "human")]
+    user: Option<String>,
+
+    /// Only show processes whose name or command line contains this text, ignoring case
+    #[arg(long, value_name = "TEXT", requires = "human")]
+    filter: Option<String>,
+
+    /// Only show the first N processes
+    #[arg(long, value_name = "N", requires = "human")]
+    top: Option<usize>,
+
+    /// Read procfs from DIR instead of /proc, same as setting RESOURCES_PROCFS_ROOT
+    #[arg(long, value_name = "DIR")]
+    procfs_root: Option<PathBuf>,
+
+    /// Read sysfs from DIR instead of /sys, same as setting RESOURCES_SYSFS_ROOT
+    #[arg(long, value_name = "DIR")]
+    sysfs_root: Option<PathBuf>,
+
+    /// Redraw the table every SECS seconds until interrupted
+    #[arg(long, value_name = "SECS", requires = "human", value_parser = parse_seconds)]
+    watch: Option<f64>,
+}
+
+impl Args {
+    fn format(&self) -> Format {
+        if self.ron { Format::Ron } else { self.format }
+    }
+
+    fn process_data_options(&self) -> ProcessDataOptions {
+        ProcessDataOptions {
+            smaps_rollup: self.smaps_rollup,
+        }
+    }
+}
+
+/// The counters of a process at a point in time that are needed to calculate its rates, see `Process` in Resources
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    cpu_time: u64,
+    read_bytes: Option<u64>,
+    write_bytes: Option<u64>,
+    timestamp: u64,
+}
+
+impl From<&ProcessData> for Sample {
+    fn from(data: &ProcessData) -> Self {
+        Self {
+            cpu_time: data.user_cpu_time.saturating_add(data.system_cpu_time),
+            read_bytes: data.read_bytes,
+            write_bytes: data.write_bytes,
+            timestamp: data.timestamp,
+        }
+    }
+}
+
+/// A line of the table printed by `--human`
+#[derive(Debug, Clone)]
+struct Row {
+    pid: libc::pid_t,
+    user: String,
+    /// From 0.0 to 1.0 across all CPUs, just like in Resources
+    cpu_usage: f32,
+    memory_usage: usize,
+    read_speed: Option<f64>,
+    write_speed: Option<f64>,
+    command: String,
+}
+
+/// The arguments in cmdline are separated and terminated by NULs, which must not reach the terminal, so they're
+/// separated by spaces instead, like Resources shows them
+fn clean_commandline(commandline: &str) -> String {
+    commandline.trim_end_matches('\0').replace('\0', " ")
+}
+
+impl Row {
+    fn new(data: &ProcessData, previous: Option<&Sample>) -> Self {
+        let current = Sample::from(data);
+
+        // processes that have appeared since the last snapshot are treated like Resources treats them, i.e. with
+        // rates of zero
+        let (cpu_time_last, timestamp_last) =
+            previous.map_or((0, 0), |sample| (sample.cpu_time, sample.timestamp));
+
+        let speed = |value: Option<u64>, value_last: Option<u64>| {
+            value.map(|value| {
+                rates::per_second(
+                    value,
+                    value_last.unwrap_or(value),
+                    current.timestamp,
+                    timestamp_last,
+                )
+            })
+        };
+
+        let command = if data.commandline.is_empty() {
+            format!("[{}]", data.comm)
+        } else {
+            clean_commandline(&data.commandline)
+        };
+
+        Self {
+            pid: data.pid,
+            user: data.user.clone(),
+            cpu_usage: rates::cpu_time_ratio(
+                current.cpu_time,
+                cpu_time_last,
+                current.timestamp,
+                timestamp_last,
+            ),
+            memory_usage: data.memory_usage,
+            read_speed: speed(
+                current.read_bytes,
+                previous.and_then(|sample| sample.read_bytes),
+            ),
+            write_speed: speed(
+                current.write_bytes,
+                previous.and_then(|sample| sample.write_bytes),
+            ),
+            command,
+        }
+    }
+
+    fn io_speed(&self) -> f64 {
+        self.read_speed.unwrap_or_default() + self.write_speed.unwrap_or_default()
+    }
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    let format = args.format();
+
+    // the roots are passed as arguments because neither flatpak-spawn nor pkexec hand our environment over to the
+    // companion, nothing has read them yet and we're still single-threaded, so this is sound
+    if let Some(procfs_root) = &args.procfs_root {
+        unsafe { std::env::set_var(PROCFS_ROOT_VAR, procfs_root) };
+    }
+
+    if let Some(sysfs_root) = &args.sysfs_root {
+        unsafe { std::env::set_var(SYSFS_ROOT_VAR, sysfs_root) };
+    }
+
+    if let Some(pid) = args.environ {
+        match EnvironmentVariable::all_of_process(pid) {
+            Ok(environ) => {
+                output_list(&environ, format)?;
+                return Ok(());
+            }
+            Err(error) => {
+                let permission_denied = error
+                    .downcast_ref::<std::io::Error>()
+                    .is_some_and(|error| error.kind() == std::io::ErrorKind::PermissionDenied);
+
+                eprintln!("{error}");
+                std::process::exit(if permission_denied { libc::EACCES } else { 1 });
+            }
+        }
+    }
+
+    if args.human {
+        return human(&args);
+    }
+
+    if let Some(interval) = args.interval {
+        return stream(
+            Duration::from_millis(interval),
+            args.process_data_options(),
+            format,
+        );
+    }
 
     if args.once {
-        output(args.ron)?;
+        output_process_data(
+            &ProcessData::all_process_data(args.process_data_options())?,
+            format,
+        )?;
         return Ok(());
     }
 
     loop {
-        let mut buffer = [0; 1];
+        let request = match read_request()? {
+            Ok(request) => request,
+            Err(error) => {
+                // possibly a request of a newer version of Resources, tell it instead of bailing out
+                output_frame::<()>(
+                    &Err(CompanionError::InvalidRequest(error.to_string())),
+                    format,
+                )?;
+                continue;
+            }
+        };
+
+        match request {
+            CompanionRequest::Handshake => {
+                output(&CompanionHandshake::current(), format)?;
+            }
+            CompanionRequest::ProcessData(options) => {
+                let process_data =
+                    ProcessData::all_process_data(options).map_err(CompanionError::from);
+                output_process_data_frame(&process_data, format)?;
+            }
+            CompanionRequest::OpenFiles(pid) => {
+                let open_files = OpenFile::all_of_process(pid).map_err(CompanionError::from);
+                output_frame(&open_files, format)?;
+            }
+            CompanionRequest::Connections => {
+                output_list_frame(&Ok(Connection::all()), format)?;
+            }
+            CompanionRequest::Environ(pid) => {
+                let environ =
+                    EnvironmentVariable::all_of_process(pid).map_err(CompanionError::from);
+                output_frame(&environ, format)?;
+            }
+            CompanionRequest::CgroupLimits(path) => {
+                let limits = CgroupLimits::read(path).map_err(CompanionError::from);
+                output_frame(&limits, format)?;
+            }
+        }
+    }
+}
+
+/// Outputs process data every `interval` until stdout is closed, preceded by a handshake. If gathering it takes longer
+/// than `interval`, the missed ticks are skipped rather than caught up on.
+fn stream(interval: Duration, options: ProcessDataOptions, format: Format) -> Result<()> {
+    if uses_frames(format) {
+        output(&CompanionHandshake::current(), format)?;
+    }
+
+    let mut next = Instant::now();
+
+    loop {
+        let process_data = ProcessData::all_process_data(options).map_err(CompanionError::from);
+        output_process_data_frame(&process_data, format)?;
 
-        std::io::stdin().read_exact(&mut buffer)?;
+        next += interval;
 
-        output(args.ron)?;
+        let now = Instant::now();
+        if next > now {
+            std::thread::sleep(next - now);
+        } else {
+            next = now;
+        }
     }
 }
 
-fn output(ron: bool) -> Result<()> {
-    let data = ProcessData::all_process_data()?;
+/// Reads a little-endian length followed by a MessagePack-encoded `CompanionRequest` from stdin. The outer `Result`
+/// is `Err` if stdin couldn't be read (e.g. because Resources has exited), the inner one if the request couldn't be
+/// decoded.
+fn read_request() -> Result<Result<CompanionRequest, rmp_serde::decode::Error>> {
+    let stdin = std::io::stdin();
+    let mut handle = stdin.lock();
 
-    let encoded = if ron {
-        ron::ser::to_string_pretty(&data, PrettyConfig::default())?
-            .as_bytes()
-            .to_vec()
+    let mut len_bytes = [0_u8; (usize::BITS / 8) as usize];
+    handle.read_exact(&mut len_bytes)?;
+
+    let len = usize::from_le_bytes(len_bytes);
+
+    let mut request_bytes = vec![0; len];
+    handle.read_exact(&mut request_bytes)?;
+
+    Ok(rmp_serde::from_slice(&request_bytes))
+}
+
+/// Handshakes and `CompanionFrame`s are part of the protocol Resources speaks, scripts reading JSON get the bare data
+/// and errors on stderr instead
+fn uses_frames(format: Format) -> bool {
+    matches!(format, Format::MessagePack | Format::Ron)
+}
+
+fn output_frame<T: Serialize>(frame: &CompanionFrame<T>, format: Format) -> Result<()> {
+    if uses_frames(format) {
+        return output(frame, format);
+    }
+
+    match frame {
+        Ok(data) => output(data, format),
+        Err(error) => {
+            eprintln!("{error}");
+            Ok(())
+        }
+    }
+}
+
+/// Like `output_frame()`, but lists are written with one element per line with `Format::Ndjson`
+fn output_list_frame<T: Serialize>(frame: &CompanionFrame<Vec<T>>, format: Format) -> Result<()> {
+    match frame {
+        Ok(data) if format == Format::Ndjson => output_list(data, format),
+        frame => output_frame(frame, format),
+    }
+}
+
+/// Like `output_list()`, but JSON gets the GPUs of `gpu_usage_stats` in their `Display` representation, since it only
+/// allows strings as map keys. The other formats keep them as they are, so that they can be decoded again.
+fn output_process_data(process_data: &[ProcessData], format: Format) -> Result<()> {
+    if uses_frames(format) {
+        output_list(process_data, format)
     } else {
-        rmp_serde::to_vec(&data)?
-    };
+        output_list(&json_process_data(process_data)?, format)
+    }
+}
 
-    let len_byte_array = encoded.len().to_le_bytes();
+/// Like `output_list_frame()`, but with JSON treated like in `output_process_data()`
+fn output_process_data_frame(
+    frame: &CompanionFrame<Vec<ProcessData>>,
+    format: Format,
+) -> Result<()> {
+    match frame {
+        Ok(process_data) if !uses_frames(format) => output_process_data(process_data, format),
+        frame => output_list_frame(frame, format),
+    }
+}
+
+fn json_process_data(process_data: &[ProcessData]) -> serde_json::Result<Vec<serde_json::Value>> {
+    process_data
+        .iter()
+        .map(|data| {
+            let gpu_usage_stats: BTreeMap<String, _> = data
+                .gpu_usage_stats
+                .iter()
+                .map(|(gpu, stats)| (gpu.to_string(), stats))
+                .collect();
 
+            let mut value = serde_json::to_value(ProcessData {
+                gpu_usage_stats: BTreeMap::new(),
+                ..data.clone()
+            })?;
+            value["gpu_usage_stats"] = serde_json::to_value(gpu_usage_stats)?;
+
+            Ok(value)
+        })
+        .collect()
+}
+
+fn output<T: Serialize>(data: &T, format: Format) -> Result<()> {
     let stdout = std::io::stdout();
     let mut handle = stdout.lock();
 
+    let encoded = match format {
+        Format::MessagePack => rmp_serde::to_vec(data)?,
+        Format::Ron => ron::ser::to_string_pretty(data, PrettyConfig::default())?
+            .as_bytes()
+            .to_vec(),
+        Format::Json | Format::Ndjson => {
+            // meant to be read by scripts line by line, so there's no length prefix
+            serde_json::to_writer(&mut handle, data)?;
+            handle.write_all(b"\n")?;
+            handle.flush()?;
+            return Ok(());
+        }
+    };
+
+    let len_byte_array = encoded.len().to_le_bytes();
+
     handle.write_all(&len_byte_array)?;
 
     handle.write_all(&encoded)?;
@@ -57,3 +452,320 @@ fn output(ron: bool) -> Result<()> {
     handle.flush()?;
     Ok(())
 }
+
+/// Like `output()`, but with `Format::Ndjson` every element is written as soon as it's serialized and on its own line
+fn output_list<T: Serialize>(data: &[T], format: Format) -> Result<()> {
+    if format != Format::Ndjson {
+        return output(&data, format);
+    }
+
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+
+    for element in data {
+        serde_json::to_writer(&mut handle, element)?;
+        handle.write_all(b"\n")?;
+    }
+
+    handle.flush()?;
+    Ok(())
+}
+
+/// Prints a table of processes to stdout, once or every `--watch` seconds
+fn human(args: &Args) -> Result<()> {
+    let interval = args
+        .watch
+        .map_or(HUMAN_SAMPLE_INTERVAL, Duration::from_secs_f64);
+
+    let filter = args.filter.as_ref().map(|filter| filter.to_lowercase());
+
+    let mut previous = samples(&ProcessData::all_process_data(args.process_data_options())?);
+
+    loop {
+        std::thread::sleep(interval);
+
+        let process_data = ProcessData::all_process_data(args.process_data_options())?;
+
+        let mut rows: Vec<Row> = process_data
+            .iter()
+            .filter(|data| args.user.as_ref().is_none_or(|user| &data.user == user))
+            .filter(|data| {
+                filter.as_ref().is_none_or(|filter| {
+                    data.comm.to_lowercase().contains(filter)
+                        || clean_commandline(&data.commandline)
+                            .to_lowercase()
+                            .contains(filter)
+                })
+            })
+            .map(|data| Row::new(data, previous.get(&identity(data))))
+            .collect();
+
+        match args.sort {
+            SortBy::Cpu => rows.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+            SortBy::Mem => rows.sort_by(|a, b| b.memory_usage.cmp(&a.memory_usage)),
+            SortBy::Io => rows.sort_by(|a, b| b.io_speed().total_cmp(&a.io_speed())),
+        }
+
+        if let Some(top) = args.top {
+            rows.truncate(top);
+        }
+
+        let mut table = String::new();
+
+        if args.watch.is_some() {
+            // move the cursor to the top left and clear the screen
+            table.push_str("\x1b[H\x1b[2J");
+        }
+
+        write_table(&mut table, &rows, process_data.len())?;
+
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(table.as_bytes())?;
+        stdout.flush()?;
+
+        if args.watch.is_none() {
+            return Ok(());
+        }
+
+        previous = samples(&process_data);
+    }
+}
+
+fn parse_seconds(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds > 0.0 => Ok(seconds),
+        _ => Err(format!("`{s}` is not a positive number of seconds")),
+    }
+}
+
+fn identity(data: &ProcessData) -> ProcessIdentity {
+    ProcessIdentity {
+        pid: data.pid,
+        start_time: data.starttime,
+    }
+}
+
+/// Processes are keyed by PID and start time, so that a recycled PID doesn't inherit the counters of its predecessor
+fn samples(process_data: &[ProcessData]) -> HashMap<ProcessIdentity, Sample> {
+    process_data
+        .iter()
+        .map(|data| (identity(data), Sample::from(data)))
+        .collect()
+}
+
+fn write_table(table: &mut String, rows: &[Row], total: usize) -> std::fmt::Result {
+    let width = terminal_width();
+
+    writeln!(table, "{} of {total} processes", rows.len())?;
+    writeln!(
+        table,
+        "{:>8} {:<12} {:>7} {:>10} {:>12} {:>12}  COMMAND",
+        "PID", "USER", "CPU", "MEMORY", "READ", "WRITE"
+    )?;
+
+    for row in rows {
+        let mut line = format!(
+            "{:>8} {:<12} {:>6.1}% {:>10} {:>12} {:>12}  {}",
+            row.pid,
+            truncate(&row.user, 12),
+            row.cpu_usage * 100.0,
+            format_bytes(row.memory_usage as f64),
+            row.read_speed.map_or_else(
+                || "N/A".to_string(),
+                |speed| format!("{}/s", format_bytes(speed))
+            ),
+            row.write_speed.map_or_else(
+                || "N/A".to_string(),
+                |speed| format!("{}/s", format_bytes(speed))
+            ),
+            row.command
+        );
+
+        if let Some(width) = width {
+            line = truncate(&line, width);
+        }
+
+        writeln!(table, "{line}")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the width of the terminal stdout is connected to, `None` if it isn't a terminal
+fn terminal_width() -> Option<usize> {
+    let stdout = std::io::stdout();
+
+    if !stdout.is_terminal() {
+        return None;
+    }
+
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+
+    if result == 0 && size.ws_col > 0 {
+        Some(size.ws_col as usize)
+    } else {
+        None
+    }
+}
+
+fn truncate(s: &str, max_chars: usize) -> String {
+    s.chars().take(max_chars).collect()
+}
+
+/// Formats bytes with binary prefixes, the way Resources does by default
+fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    let mut value = bytes;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{value:.0} {}", UNITS[unit])
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use pretty_assertions::assert_eq;
+    use process_data::{GpuIdentifier, GpuUsageStats, ProcessData, rates};
+
+    use super::{Row, Sample, clean_commandline, format_bytes, json_process_data, parse_seconds};
+
+    fn process_data() -> ProcessData {
+        ProcessData {
+            pid: 1234,
+            user: "user".into(),
+            comm: "worker".into(),
+            commandline: "/usr/bin/worker\0--fast\0".into(),
+            user_cpu_time: 100,
+            system_cpu_time: 50,
+            memory_usage: 4096,
+            read_bytes: Some(4096),
+            write_bytes: None,
+            timestamp: 5000,
+            ..Default::default()
+        }
+    }
+
+    fn process_data_with_gpu() -> ProcessData {
+        ProcessData {
+            gpu_usage_stats: BTreeMap::from([(
+                GpuIdentifier::Enumerator(1),
+                GpuUsageStats {
+                    gfx: 5,
+                    mem: 1024,
+                    enc: 0,
+                    dec: 0,
+                    nvidia: false,
+                },
+            )]),
+            ..process_data()
+        }
+    }
+
+    #[test]
+    fn json_gpu_keys() {
+        let json = json_process_data(&[process_data_with_gpu()]).unwrap();
+
+        assert_eq!(json.len(), 1);
+        assert_eq!(json[0]["pid"], 1234);
+        assert_eq!(json[0]["gpu_usage_stats"]["1"]["gfx"], 5);
+        assert_eq!(json[0]["gpu_usage_stats"]["1"]["mem"], 1024);
+    }
+
+    #[test]
+    fn ron_round_trip() {
+        let data = process_data_with_gpu();
+
+        let ron = ron::to_string(&data).unwrap();
+
+        assert_eq!(ron::from_str::<ProcessData>(&ron).unwrap(), data);
+    }
+
+    #[test]
+    fn seconds() {
+        assert_eq!(parse_seconds("2"), Ok(2.0));
+        assert_eq!(parse_seconds("0.5"), Ok(0.5));
+        assert!(parse_seconds("0").is_err());
+        assert!(parse_seconds("-1").is_err());
+        assert!(parse_seconds("inf").is_err());
+        assert!(parse_seconds("NaN").is_err());
+        assert!(parse_seconds("soon").is_err());
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(format_bytes(0.0), "0 B");
+        assert_eq!(format_bytes(1023.0), "1023 B");
+        assert_eq!(format_bytes(1024.0), "1.0 KiB");
+        assert_eq!(format_bytes(1536.0), "1.5 KiB");
+        assert_eq!(format_bytes(2.0 * 1024.0 * 1024.0 * 1024.0), "2.0 GiB");
+        // there's no unit above PiB
+        assert_eq!(format_bytes(2048.0 * 1024_f64.powi(5)), "2048.0 PiB");
+    }
+
+    #[test]
+    fn row_of_new_process() {
+        let row = Row::new(&process_data(), None);
+
+        assert_eq!(row.pid, 1234);
+        assert_eq!(row.user, "user");
+        assert_eq!(row.memory_usage, 4096);
+        assert_eq!(row.command, "/usr/bin/worker --fast");
+        // there's nothing to compare against yet
+        assert_eq!(row.cpu_usage, 0.0);
+        assert_eq!(row.read_speed, Some(0.0));
+        assert_eq!(row.write_speed, None);
+        assert_eq!(row.io_speed(), 0.0);
+    }
+
+    #[test]
+    fn row_of_known_process() {
+        let previous = Sample {
+            cpu_time: 50,
+            read_bytes: Some(2048),
+            write_bytes: None,
+            timestamp: 4000,
+        };
+
+        let row = Row::new(&process_data(), Some(&previous));
+
+        assert_eq!(row.cpu_usage, rates::cpu_time_ratio(150, 50, 5000, 4000));
+        assert!(row.cpu_usage > 0.0);
+        assert_eq!(row.read_speed, Some(2048.0));
+        assert_eq!(row.write_speed, None);
+        assert_eq!(row.io_speed(), 2048.0);
+    }
+
+    #[test]
+    fn row_of_kernel_thread() {
+        let data = ProcessData {
+            commandline: String::new(),
+            ..process_data()
+        };
+
+        assert_eq!(Row::new(&data, None).command, "[worker]");
+    }
+
+    #[test]
+    fn clean_commandlines() {
+        assert_eq!(clean_commandline("sleep\0infinity\0"), "sleep infinity");
+        // empty arguments are kept
+        assert_eq!(clean_commandline("echo\0\0done\0"), "echo  done");
+        // some processes overwrite their cmdline without any NULs
+        assert_eq!(
+            clean_commandline("postgres: checkpointer"),
+            "postgres: checkpointer"
+        );
+    }
+}
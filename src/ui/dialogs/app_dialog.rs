@@ -1,6 +1,7 @@
 use crate::config::PROFILE;
 use crate::i18n::i18n;
 use crate::ui::pages::applications::application_entry::ApplicationEntry;
+use crate::ui::widgets::cgroup_limits_group::ResCgroupLimitsGroup;
 use crate::utils::units::{convert_speed, convert_storage};
 use adw::{prelude::*, subclass::prelude::*};
 use gtk::gio::ThemedIcon;
@@ -52,6 +53,8 @@ mod imp {
         pub processes_amount: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub containerized: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub cgroup_limits: TemplateChild<ResCgroupLimitsGroup>,
     }
 
     #[glib::object_subclass]
@@ -197,5 +200,10 @@ impl ResAppDialog {
 
         imp.processes_amount
             .set_subtitle(&app.running_processes().to_string());
+
+        // the processes of an app come and go, so its cgroup might change as well
+        imp.cgroup_limits
+            .set_cgroup_path(app.cgroup_path().as_deref());
+        imp.cgroup_limits.refresh();
     }
 }
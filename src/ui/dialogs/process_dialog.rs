@@ -1,14 +1,31 @@
 use adw::{prelude::*, subclass::prelude::*};
-use gtk::glib::{self, GString};
-use log::trace;
+use gtk::gio;
+use gtk::glib::{self, GString, MainContext, clone};
+use log::{trace, warn};
+use process_data::environ::EnvironmentVariable;
+use process_data::namespaces::NamespaceKind;
+use process_data::open_file::{FileDescriptorKind, OpenFile};
+use process_data::security::{
+    SeccompMode, SecurityContext, capability_names, has_all_capabilities, signal_names,
+};
+use process_data::socket::{Socket, SocketProtocol, SocketState};
 
 use crate::config::PROFILE;
-use crate::i18n::i18n;
+use crate::i18n::{i18n, i18n_f, ni18n_f, pi18n};
+use crate::ui::pages::connections::socket_state_label;
 use crate::ui::pages::processes::process_entry::ProcessEntry;
+use crate::ui::pages::rate_label;
+use crate::ui::widgets::cgroup_limits_group::ResCgroupLimitsGroup;
+use crate::utils::process::Process;
 use crate::utils::units::{convert_speed, convert_storage, format_time};
 
+/// Listing every process sharing e.g. the system's mount namespace would only make the dialog unwieldy
+const MAX_NAMESPACE_PEERS: usize = 100;
+
 mod imp {
 
+    use std::cell::{Cell, RefCell};
+
     use super::*;
 
     use gtk::CompositeTemplate;
@@ -47,6 +64,14 @@ mod imp {
         #[template_child]
         pub system_cpu_time: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub children_cpu_time: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub major_fault_rate: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub context_switch_rate: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub num_threads: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub pid: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub running_since: TemplateChild<adw::ActionRow>,
@@ -58,6 +83,47 @@ mod imp {
         pub cgroup: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub containerized: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub security_label: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub no_new_privs: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub seccomp: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub effective_capabilities: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub permitted_capabilities: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub bounding_capabilities: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub blocked_signals: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub ignored_signals: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub caught_signals: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub namespaces_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub cgroup_limits: TemplateChild<ResCgroupLimitsGroup>,
+        #[template_child]
+        pub stack: TemplateChild<adw::ViewStack>,
+        #[template_child]
+        pub open_files_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub open_files_refresh_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub environ_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub environ_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub environ_refresh_button: TemplateChild<gtk::Button>,
+
+        pub pid: Cell<libc::pid_t>,
+        pub open_files_loaded: Cell<bool>,
+        pub open_file_rows: RefCell<Vec<adw::ActionRow>>,
+        pub environ_loaded: Cell<bool>,
+        /// Contains the rows together with their lowercase key and value for searching
+        pub environ_rows: RefCell<Vec<(adw::ActionRow, String)>>,
     }
 
     #[glib::object_subclass]
@@ -111,7 +177,55 @@ impl ResProcessDialog {
     }
 
     pub fn init<S: AsRef<str>>(&self, process: &ProcessEntry, user: S) {
+        self.imp().pid.set(process.pid());
         self.setup_widgets(process, user.as_ref());
+        self.setup_signals();
+    }
+
+    pub fn setup_signals(&self) {
+        trace!("Setting up ResProcessDialog signals…");
+
+        let imp = self.imp();
+
+        // reading the open files and the environment can take a while (or require authentication), so only do it
+        // once the user wants to see them
+        imp.stack.connect_visible_child_name_notify(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |stack| match stack.visible_child_name().as_deref() {
+                Some("open-files") if !this.imp().open_files_loaded.get() => {
+                    this.load_open_files();
+                }
+                Some("environment") if !this.imp().environ_loaded.get() => {
+                    this.load_environ();
+                }
+                _ => {}
+            }
+        ));
+
+        imp.environ_refresh_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                this.load_environ();
+            }
+        ));
+
+        imp.environ_search_entry.connect_search_changed(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                this.filter_environ();
+            }
+        ));
+
+        imp.open_files_refresh_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                this.load_open_files();
+            }
+        ));
     }
 
     pub fn setup_widgets(&self, process: &ProcessEntry, user: &str) {
@@ -148,6 +262,9 @@ impl ResProcessDialog {
 
         imp.containerized.set_subtitle(&process.containerization());
 
+        imp.cgroup_limits
+            .set_cgroup_path(process.cgroup_path().as_deref());
+
         self.update(process);
     }
 
@@ -213,5 +330,391 @@ impl ResProcessDialog {
 
         imp.system_cpu_time
             .set_subtitle(&format_time(process.system_cpu_time()));
+
+        imp.children_cpu_time
+            .set_subtitle(&format_time(process.children_cpu_time()));
+
+        imp.major_fault_rate
+            .set_subtitle(&rate_label(process.major_fault_rate()));
+
+        imp.context_switch_rate
+            .set_subtitle(&rate_label(process.context_switch_rate()));
+
+        imp.num_threads
+            .set_subtitle(&process.num_threads().to_string());
+
+        self.set_security(&process.security());
+
+        imp.cgroup_limits.refresh();
+    }
+
+    fn set_security(&self, security: &SecurityContext) {
+        let imp = self.imp();
+
+        imp.security_label
+            .set_subtitle(security.lsm_label.as_deref().unwrap_or(&i18n("N/A")));
+
+        imp.no_new_privs
+            .set_subtitle(&security.no_new_privs.map_or_else(
+                || i18n("N/A"),
+                |no_new_privs| {
+                    if no_new_privs {
+                        i18n("Yes")
+                    } else {
+                        i18n("No")
+                    }
+                },
+            ));
+
+        imp.seccomp.set_subtitle(&match security.seccomp {
+            Some(SeccompMode::Disabled) => i18n("Disabled"),
+            Some(SeccompMode::Strict) => i18n("Strict"),
+            Some(SeccompMode::Filter) => i18n("Filtered"),
+            None => i18n("N/A"),
+        });
+
+        imp.effective_capabilities
+            .set_subtitle(&capabilities_label(security.effective_capabilities));
+        imp.permitted_capabilities
+            .set_subtitle(&capabilities_label(security.permitted_capabilities));
+        imp.bounding_capabilities
+            .set_subtitle(&capabilities_label(security.bounding_capabilities));
+
+        imp.blocked_signals
+            .set_subtitle(&signals_label(security.blocked_signals));
+        imp.ignored_signals
+            .set_subtitle(&signals_label(security.ignored_signals));
+        imp.caught_signals
+            .set_subtitle(&signals_label(security.caught_signals));
+    }
+
+    /// Shows the namespaces of `process` together with the processes of `processes` that share them
+    pub fn set_namespace_peers(&self, process: &ProcessEntry, processes: &[ProcessEntry]) {
+        let imp = self.imp();
+
+        let namespaces = process.namespaces();
+
+        // namespaces of the init process are what the rest of the system uses
+        let init_namespaces = processes
+            .iter()
+            .find(|other| other.pid() == 1)
+            .map(ProcessEntry::namespaces);
+
+        for kind in NamespaceKind::ALL {
+            let Some(inode) = namespaces.get(kind) else {
+                let row = adw::ActionRow::builder()
+                    .title(namespace_kind_label(kind))
+                    .subtitle(i18n("N/A"))
+                    .build();
+                row.add_css_class("property");
+                imp.namespaces_group.add(&row);
+                continue;
+            };
+
+            let mut peers: Vec<&ProcessEntry> = processes
+                .iter()
+                .filter(|other| {
+                    other.pid() != process.pid() && other.namespaces().get(kind) == Some(inode)
+                })
+                .collect();
+            peers.sort_by_key(|peer| peer.pid());
+
+            let mut subtitle = vec![
+                inode.to_string(),
+                ni18n_f(
+                    "Shared with {} other process",
+                    "Shared with {} other processes",
+                    peers.len() as u32,
+                    &[&peers.len().to_string()],
+                ),
+            ];
+
+            if init_namespaces
+                .and_then(|init_namespaces| init_namespaces.get(kind))
+                .is_some_and(|init_inode| init_inode != inode)
+            {
+                subtitle.push(i18n("Separate from the system"));
+            }
+
+            let row = adw::ExpanderRow::builder()
+                .title(namespace_kind_label(kind))
+                .subtitle(subtitle.join(" · "))
+                .enable_expansion(!peers.is_empty())
+                .build();
+            row.add_css_class("property");
+
+            for peer in peers.iter().take(MAX_NAMESPACE_PEERS) {
+                let peer_row = adw::ActionRow::builder()
+                    .title(peer.name())
+                    .subtitle(i18n_f("PID {}", &[&peer.pid().to_string()]))
+                    .use_markup(false)
+                    .build();
+                row.add_row(&peer_row);
+            }
+
+            if peers.len() > MAX_NAMESPACE_PEERS {
+                let more_row = adw::ActionRow::builder()
+                    .title(i18n_f(
+                        "…and {} more",
+                        &[&(peers.len() - MAX_NAMESPACE_PEERS).to_string()],
+                    ))
+                    .build();
+                row.add_row(&more_row);
+            }
+
+            imp.namespaces_group.add(&row);
+        }
+    }
+
+    fn load_open_files(&self) {
+        let imp = self.imp();
+        let pid = imp.pid.get();
+
+        imp.open_files_loaded.set(true);
+        imp.open_files_refresh_button.set_sensitive(false);
+        imp.open_files_group
+            .set_description(Some(&i18n("Loading open files…")));
+
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                let open_files = gio::spawn_blocking(move || Process::open_files(pid)).await;
+
+                match open_files {
+                    Ok(Ok(open_files)) => this.set_open_files(&open_files),
+                    Ok(Err(error)) => {
+                        warn!("Unable to get open files of process {pid}: {error}");
+                        this.set_open_files(&[]);
+                        this.imp().open_files_group.set_description(Some(&i18n(
+                            "Unable to read the open files of this process",
+                        )));
+                    }
+                    Err(_) => this.set_open_files(&[]),
+                }
+
+                this.imp().open_files_refresh_button.set_sensitive(true);
+            }
+        ));
     }
+
+    fn set_open_files(&self, open_files: &[OpenFile]) {
+        let imp = self.imp();
+
+        for row in imp.open_file_rows.borrow_mut().drain(..) {
+            imp.open_files_group.remove(&row);
+        }
+
+        if open_files.is_empty() {
+            imp.open_files_group
+                .set_description(Some(&i18n("This process has no open files")));
+            return;
+        }
+
+        imp.open_files_group.set_description(None);
+
+        let mut rows = imp.open_file_rows.borrow_mut();
+        for open_file in open_files {
+            let row = adw::ActionRow::builder()
+                .title(&open_file.target)
+                .subtitle(open_file_description(open_file))
+                .use_markup(false)
+                .title_selectable(true)
+                .build();
+
+            imp.open_files_group.add(&row);
+            rows.push(row);
+        }
+    }
+
+    fn load_environ(&self) {
+        let imp = self.imp();
+        let pid = imp.pid.get();
+
+        imp.environ_loaded.set(true);
+        imp.environ_refresh_button.set_sensitive(false);
+        imp.environ_group
+            .set_description(Some(&i18n("Loading environment variables…")));
+
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                let environ = gio::spawn_blocking(move || Process::environ(pid)).await;
+
+                match environ {
+                    Ok(Ok(environ)) => this.set_environ(&environ),
+                    Ok(Err(error)) => {
+                        warn!("Unable to get environment of process {pid}: {error}");
+                        this.set_environ(&[]);
+                        this.imp().environ_group.set_description(Some(&i18n(
+                            "Unable to read the environment variables of this process",
+                        )));
+                    }
+                    Err(_) => this.set_environ(&[]),
+                }
+
+                this.imp().environ_refresh_button.set_sensitive(true);
+            }
+        ));
+    }
+
+    fn set_environ(&self, environ: &[EnvironmentVariable]) {
+        let imp = self.imp();
+
+        for (row, _) in imp.environ_rows.borrow_mut().drain(..) {
+            imp.environ_group.remove(&row);
+        }
+
+        if environ.is_empty() {
+            imp.environ_group
+                .set_description(Some(&i18n("This process has no environment variables")));
+            return;
+        }
+
+        imp.environ_group.set_description(None);
+
+        let mut rows = imp.environ_rows.borrow_mut();
+        for variable in environ {
+            let row = adw::ActionRow::builder()
+                .title(&variable.key)
+                .subtitle(&variable.value)
+                .use_markup(false)
+                .subtitle_selectable(true)
+                .build();
+            row.add_css_class("property");
+
+            imp.environ_group.add(&row);
+            rows.push((
+                row,
+                format!("{}={}", variable.key, variable.value).to_lowercase(),
+            ));
+        }
+
+        drop(rows);
+        self.filter_environ();
+    }
+
+    fn filter_environ(&self) {
+        let imp = self.imp();
+
+        let search = imp.environ_search_entry.text().to_lowercase();
+
+        for (row, haystack) in imp.environ_rows.borrow().iter() {
+            row.set_visible(haystack.contains(&search));
+        }
+    }
+}
+
+fn capabilities_label(capabilities: Option<u64>) -> String {
+    match capabilities {
+        None => i18n("N/A"),
+        Some(0) => i18n("None"),
+        Some(capabilities) if has_all_capabilities(capabilities) => i18n("All"),
+        Some(capabilities) => capability_names(capabilities).join(", "),
+    }
+}
+
+fn signals_label(signals: Option<u64>) -> String {
+    match signals {
+        None => i18n("N/A"),
+        Some(0) => i18n("None"),
+        Some(signals) => signal_names(signals).join(", "),
+    }
+}
+
+fn namespace_kind_label(kind: NamespaceKind) -> String {
+    match kind {
+        NamespaceKind::Cgroup => pi18n("namespace", "Control Group"),
+        NamespaceKind::Ipc => pi18n("namespace", "IPC"),
+        NamespaceKind::Mount => pi18n("namespace", "Mount"),
+        NamespaceKind::Network => pi18n("namespace", "Network"),
+        NamespaceKind::Pid => pi18n("namespace", "PID"),
+        NamespaceKind::User => pi18n("namespace", "User"),
+        NamespaceKind::Uts => pi18n("namespace", "UTS"),
+    }
+}
+
+fn open_file_description(open_file: &OpenFile) -> String {
+    let mut parts = vec![
+        i18n_f("FD {}", &[&open_file.fd.to_string()]),
+        file_descriptor_kind_label(&open_file.kind),
+    ];
+
+    if let Some(socket) = &open_file.socket {
+        parts.push(socket_description(socket));
+    }
+
+    if let Some(position) = open_file.position {
+        parts.push(i18n_f("Position {}", &[&position.to_string()]));
+    }
+
+    if let Some(flags) = open_file.flags {
+        parts.push(open_flags_label(flags));
+    }
+
+    if open_file.deleted {
+        parts.push(i18n("Deleted"));
+    }
+
+    parts.join(" · ")
+}
+
+fn file_descriptor_kind_label(kind: &FileDescriptorKind) -> String {
+    match kind {
+        FileDescriptorKind::File => pi18n("file descriptor type", "File"),
+        FileDescriptorKind::Directory => pi18n("file descriptor type", "Directory"),
+        FileDescriptorKind::Device => pi18n("file descriptor type", "Device"),
+        FileDescriptorKind::Drm => pi18n("file descriptor type", "GPU"),
+        FileDescriptorKind::Pipe => pi18n("file descriptor type", "Pipe"),
+        FileDescriptorKind::Socket(_) => pi18n("file descriptor type", "Socket"),
+        FileDescriptorKind::AnonInode(name) => i18n_f("Anonymous Inode ({})", &[name.as_str()]),
+    }
+}
+
+fn socket_description(socket: &Socket) -> String {
+    let state = socket_state_label(socket.state);
+
+    match (socket.protocol, socket.local_address, socket.remote_address) {
+        (SocketProtocol::Unix, _, _) => match &socket.path {
+            Some(path) => format!("{} {path} ({state})", socket.protocol),
+            None => format!("{} ({state})", socket.protocol),
+        },
+        (_, Some(local_address), _) if socket.state == SocketState::Listen => {
+            format!("{} {local_address} ({state})", socket.protocol)
+        }
+        (_, Some(local_address), Some(remote_address)) => format!(
+            "{} {local_address} → {remote_address} ({state})",
+            socket.protocol
+        ),
+        _ => format!("{} ({state})", socket.protocol),
+    }
+}
+
+/// Returns the access mode and the most interesting other flags, e.g. `O_RDWR | O_CLOEXEC`
+fn open_flags_label(flags: u32) -> String {
+    let flags = flags as libc::c_int;
+
+    let mut names = vec![match flags & libc::O_ACCMODE {
+        libc::O_WRONLY => "O_WRONLY",
+        libc::O_RDWR => "O_RDWR",
+        _ => "O_RDONLY",
+    }];
+
+    for (flag, name) in [
+        (libc::O_APPEND, "O_APPEND"),
+        (libc::O_NONBLOCK, "O_NONBLOCK"),
+        (libc::O_SYNC, "O_SYNC"),
+        (libc::O_DIRECT, "O_DIRECT"),
+        (libc::O_PATH, "O_PATH"),
+        (libc::O_CLOEXEC, "O_CLOEXEC"),
+    ] {
+        if flags & flag == flag {
+            names.push(name);
+        }
+    }
+
+    names.join(" | ")
 }
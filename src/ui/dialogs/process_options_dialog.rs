@@ -11,7 +11,10 @@ use adw::{ToastOverlay, prelude::*, subclass::prelude::*};
 use async_channel::Sender;
 use gtk::glib::{self, MainContext, clone};
 use log::trace;
-use process_data::Niceness;
+use process_data::{
+    Niceness, OomScoreAdj,
+    scheduling::{IoPriority, IoPriorityClass, SchedulingPolicy},
+};
 
 mod imp {
 
@@ -36,9 +39,24 @@ mod imp {
         pub affinity_row: TemplateChild<adw::ExpanderRow>,
         #[template_child]
         pub select_all_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub io_class_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub io_level_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub scheduling_policy_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub rt_priority_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub oom_score_adj_row: TemplateChild<adw::SpinRow>,
 
         pub cpu_rows: RefCell<Vec<adw::SwitchRow>>,
 
+        /// What the I/O priority and scheduling policy rows showed when the dialog was opened, they are only applied
+        /// if they have been changed
+        pub initial_io_priority: Cell<IoPriority>,
+        pub initial_scheduling_policy: Cell<SchedulingPolicy>,
+
         pub pid: Cell<libc::pid_t>,
     }
 
@@ -119,6 +137,53 @@ impl ResProcessOptionsDialog {
         }
     }
 
+    fn get_current_io_priority(&self) -> IoPriority {
+        let imp = self.imp();
+
+        let class = match imp.io_class_row.selected() {
+            1 => IoPriorityClass::RealTime,
+            2 => IoPriorityClass::BestEffort,
+            3 => IoPriorityClass::Idle,
+            _ => IoPriorityClass::None,
+        };
+
+        let level = match class {
+            IoPriorityClass::RealTime | IoPriorityClass::BestEffort => {
+                imp.io_level_row.value() as u8
+            }
+            IoPriorityClass::None | IoPriorityClass::Idle => 0,
+        };
+
+        IoPriority { class, level }
+    }
+
+    fn get_current_scheduling_policy(&self) -> SchedulingPolicy {
+        let imp = self.imp();
+
+        let rt_priority = imp.rt_priority_row.value() as u32;
+
+        match imp.scheduling_policy_row.selected() {
+            1 => SchedulingPolicy::Batch,
+            2 => SchedulingPolicy::Idle,
+            3 => SchedulingPolicy::Fifo(rt_priority),
+            4 => SchedulingPolicy::RoundRobin(rt_priority),
+            _ => SchedulingPolicy::Other,
+        }
+    }
+
+    /// Only shows the level and real-time priority rows when they have an effect
+    fn update_scheduling_rows(&self) {
+        let imp = self.imp();
+
+        imp.io_level_row.set_sensitive(matches!(
+            self.get_current_io_priority().class,
+            IoPriorityClass::RealTime | IoPriorityClass::BestEffort
+        ));
+
+        imp.rt_priority_row
+            .set_visible(self.get_current_scheduling_policy().is_real_time());
+    }
+
     pub fn setup_widgets(&self, process: &ProcessEntry) {
         trace!("Setting up ResProcessOptionsDialog widgets…");
 
@@ -163,6 +228,51 @@ impl ResProcessOptionsDialog {
             imp.cpu_rows.borrow_mut().push(switch_row);
         }
 
+        let io_priority = process.io_priority_value().unwrap_or_default();
+
+        imp.io_class_row.set_selected(match io_priority.class {
+            IoPriorityClass::None => 0,
+            IoPriorityClass::RealTime => 1,
+            IoPriorityClass::BestEffort => 2,
+            IoPriorityClass::Idle => 3,
+        });
+
+        // without an explicit class, the kernel derives a best-effort level from the niceness
+        let io_level = match io_priority.class {
+            IoPriorityClass::None => (i32::from(process.niceness()) + 20) / 5,
+            _ => i32::from(io_priority.level),
+        };
+        imp.io_level_row.set_value(f64::from(io_level));
+
+        let scheduling_policy = process.scheduling_policy_value().unwrap_or_default();
+
+        imp.scheduling_policy_row
+            .set_selected(match scheduling_policy {
+                SchedulingPolicy::Other | SchedulingPolicy::Deadline => 0,
+                SchedulingPolicy::Batch => 1,
+                SchedulingPolicy::Idle => 2,
+                SchedulingPolicy::Fifo(_) => 3,
+                SchedulingPolicy::RoundRobin(_) => 4,
+            });
+
+        if let SchedulingPolicy::Fifo(rt_priority) | SchedulingPolicy::RoundRobin(rt_priority) =
+            scheduling_policy
+        {
+            imp.rt_priority_row.set_value(f64::from(rt_priority));
+        }
+
+        if let Some(oom_score_adj) = process.oom_score_adj() {
+            imp.oom_score_adj_row.set_value(f64::from(*oom_score_adj));
+        } else {
+            imp.oom_score_adj_row.set_sensitive(false);
+        }
+
+        imp.initial_io_priority.set(self.get_current_io_priority());
+        imp.initial_scheduling_policy
+            .set(self.get_current_scheduling_policy());
+
+        self.update_scheduling_rows();
+
         imp.pid.set(process.pid());
     }
 
@@ -176,6 +286,9 @@ impl ResProcessOptionsDialog {
 
         let imp = self.imp();
 
+        // taken as the dialog opens, so that a recycled PID can't be adjusted by mistake
+        let identity = process.identity();
+
         imp.select_all_button.connect_clicked(clone!(
             #[weak(rename_to = this)]
             self,
@@ -190,6 +303,18 @@ impl ResProcessOptionsDialog {
             }
         ));
 
+        imp.io_class_row.connect_selected_notify(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.update_scheduling_rows()
+        ));
+
+        imp.scheduling_policy_row.connect_selected_notify(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.update_scheduling_rows()
+        ));
+
         imp.apply_button.connect_clicked(clone!(
             #[weak(rename_to = this)]
             self,
@@ -216,11 +341,27 @@ impl ResProcessOptionsDialog {
                             .map(adw::SwitchRow::is_active)
                             .collect();
 
+                        let io_priority = this.get_current_io_priority();
+                        let scheduling_policy = this.get_current_scheduling_policy();
+
+                        let oom_score_adj =
+                            OomScoreAdj::try_new(imp.oom_score_adj_row.value() as i16)
+                                .ok()
+                                .filter(|oom_score_adj| {
+                                    Some(*oom_score_adj) != process.oom_score_adj()
+                                })
+                                .filter(|_| imp.oom_score_adj_row.is_sensitive());
+
                         let _ = sender
                             .send(Action::AdjustProcess(
-                                process.pid(),
+                                identity,
                                 this.get_current_niceness(),
                                 affinity,
+                                (io_priority != imp.initial_io_priority.get())
+                                    .then_some(io_priority),
+                                (scheduling_policy != imp.initial_scheduling_policy.get())
+                                    .then_some(scheduling_policy),
+                                oom_score_adj,
                                 process.name().to_string(),
                                 toast_overlay.clone(),
                             ))
@@ -4,7 +4,9 @@ use log::trace;
 
 use crate::{
     config::PROFILE,
-    utils::settings::{Base, RefreshSpeed, SETTINGS, SidebarMeterType, TemperatureUnit},
+    utils::settings::{
+        Base, MemoryMetric, RefreshSpeed, SETTINGS, SidebarMeterType, TemperatureUnit,
+    },
 };
 
 mod imp {
@@ -62,10 +64,32 @@ mod imp {
         pub apps_show_swap_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub apps_show_combined_memory_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_char_read_speed_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_char_read_total_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_char_write_speed_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_char_write_total_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_read_syscalls_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_write_syscalls_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_cancelled_write_total_row: TemplateChild<adw::SwitchRow>,
 
         #[template_child]
         pub processes_niceness: TemplateChild<adw::SwitchRow>,
         #[template_child]
+        pub memory_metric_combo_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub processes_show_tree_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_subtree_usage_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_elevated_monitoring_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
         pub processes_show_id_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub processes_show_user_row: TemplateChild<adw::SwitchRow>,
@@ -101,6 +125,32 @@ mod imp {
         pub processes_show_swap_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub processes_show_combined_memory_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_state_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_major_fault_rate_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_context_switch_rate_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_char_read_speed_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_char_read_total_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_char_write_speed_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_char_write_total_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_read_syscalls_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_write_syscalls_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_cancelled_write_total_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_io_priority_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_scheduling_policy_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_oom_score_row: TemplateChild<adw::SwitchRow>,
 
         #[template_child]
         pub show_virtual_drives_row: TemplateChild<adw::SwitchRow>,
@@ -213,9 +263,31 @@ impl ResSettingsDialog {
         imp.apps_show_swap_row.set_active(SETTINGS.apps_show_swap());
         imp.apps_show_combined_memory_row
             .set_active(SETTINGS.apps_show_combined_memory());
+        imp.apps_show_char_read_speed_row
+            .set_active(SETTINGS.apps_show_char_read_speed());
+        imp.apps_show_char_read_total_row
+            .set_active(SETTINGS.apps_show_char_read_total());
+        imp.apps_show_char_write_speed_row
+            .set_active(SETTINGS.apps_show_char_write_speed());
+        imp.apps_show_char_write_total_row
+            .set_active(SETTINGS.apps_show_char_write_total());
+        imp.apps_show_read_syscalls_row
+            .set_active(SETTINGS.apps_show_read_syscalls());
+        imp.apps_show_write_syscalls_row
+            .set_active(SETTINGS.apps_show_write_syscalls());
+        imp.apps_show_cancelled_write_total_row
+            .set_active(SETTINGS.apps_show_cancelled_write_total());
 
         imp.processes_niceness
             .set_active(SETTINGS.detailed_priority());
+        imp.memory_metric_combo_row
+            .set_selected(SETTINGS.memory_metric() as u32);
+        imp.processes_show_tree_row
+            .set_active(SETTINGS.processes_show_tree());
+        imp.processes_show_subtree_usage_row
+            .set_active(SETTINGS.processes_show_subtree_usage());
+        imp.processes_elevated_monitoring_row
+            .set_active(SETTINGS.processes_elevated_monitoring());
         imp.processes_show_id_row
             .set_active(SETTINGS.processes_show_id());
         imp.processes_show_user_row
@@ -252,6 +324,32 @@ impl ResSettingsDialog {
             .set_active(SETTINGS.processes_show_swap());
         imp.processes_show_combined_memory_row
             .set_active(SETTINGS.processes_show_combined_memory());
+        imp.processes_show_state_row
+            .set_active(SETTINGS.processes_show_state());
+        imp.processes_show_major_fault_rate_row
+            .set_active(SETTINGS.processes_show_major_fault_rate());
+        imp.processes_show_context_switch_rate_row
+            .set_active(SETTINGS.processes_show_context_switch_rate());
+        imp.processes_show_char_read_speed_row
+            .set_active(SETTINGS.processes_show_char_read_speed());
+        imp.processes_show_char_read_total_row
+            .set_active(SETTINGS.processes_show_char_read_total());
+        imp.processes_show_char_write_speed_row
+            .set_active(SETTINGS.processes_show_char_write_speed());
+        imp.processes_show_char_write_total_row
+            .set_active(SETTINGS.processes_show_char_write_total());
+        imp.processes_show_read_syscalls_row
+            .set_active(SETTINGS.processes_show_read_syscalls());
+        imp.processes_show_write_syscalls_row
+            .set_active(SETTINGS.processes_show_write_syscalls());
+        imp.processes_show_cancelled_write_total_row
+            .set_active(SETTINGS.processes_show_cancelled_write_total());
+        imp.processes_show_io_priority_row
+            .set_active(SETTINGS.processes_show_io_priority());
+        imp.processes_show_scheduling_policy_row
+            .set_active(SETTINGS.processes_show_scheduling_policy());
+        imp.processes_show_oom_score_row
+            .set_active(SETTINGS.processes_show_oom_score());
 
         imp.show_virtual_drives_row
             .set_active(SETTINGS.show_virtual_drives());
@@ -378,10 +476,67 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_apps_show_combined_memory(switch_row.is_active());
             });
 
+        imp.apps_show_char_read_speed_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_char_read_speed(switch_row.is_active());
+            });
+
+        imp.apps_show_char_read_total_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_char_read_total(switch_row.is_active());
+            });
+
+        imp.apps_show_char_write_speed_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_char_write_speed(switch_row.is_active());
+            });
+
+        imp.apps_show_char_write_total_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_char_write_total(switch_row.is_active());
+            });
+
+        imp.apps_show_read_syscalls_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_read_syscalls(switch_row.is_active());
+            });
+
+        imp.apps_show_write_syscalls_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_write_syscalls(switch_row.is_active());
+            });
+
+        imp.apps_show_cancelled_write_total_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_cancelled_write_total(switch_row.is_active());
+            });
+
         imp.processes_niceness.connect_active_notify(|switch_row| {
             let _ = SETTINGS.set_detailed_priority(switch_row.is_active());
         });
 
+        imp.memory_metric_combo_row
+            .connect_selected_item_notify(|combo_row| {
+                if let Some(memory_metric) = MemoryMetric::from_repr(combo_row.selected() as u8) {
+                    let _ = SETTINGS.set_memory_metric(memory_metric);
+                }
+            });
+
+        imp.processes_show_tree_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_tree(switch_row.is_active());
+            });
+
+        imp.processes_show_subtree_usage_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_subtree_usage(switch_row.is_active());
+            });
+
+        imp.processes_elevated_monitoring_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_elevated_monitoring(switch_row.is_active());
+            });
+
         imp.processes_show_id_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_processes_show_id(switch_row.is_active());
@@ -472,6 +627,71 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_processes_show_combined_memory(switch_row.is_active());
             });
 
+        imp.processes_show_state_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_state(switch_row.is_active());
+            });
+
+        imp.processes_show_major_fault_rate_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_major_fault_rate(switch_row.is_active());
+            });
+
+        imp.processes_show_context_switch_rate_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_context_switch_rate(switch_row.is_active());
+            });
+
+        imp.processes_show_char_read_speed_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_char_read_speed(switch_row.is_active());
+            });
+
+        imp.processes_show_char_read_total_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_char_read_total(switch_row.is_active());
+            });
+
+        imp.processes_show_char_write_speed_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_char_write_speed(switch_row.is_active());
+            });
+
+        imp.processes_show_char_write_total_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_char_write_total(switch_row.is_active());
+            });
+
+        imp.processes_show_read_syscalls_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_read_syscalls(switch_row.is_active());
+            });
+
+        imp.processes_show_write_syscalls_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_write_syscalls(switch_row.is_active());
+            });
+
+        imp.processes_show_cancelled_write_total_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_cancelled_write_total(switch_row.is_active());
+            });
+
+        imp.processes_show_io_priority_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_io_priority(switch_row.is_active());
+            });
+
+        imp.processes_show_scheduling_policy_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_scheduling_policy(switch_row.is_active());
+            });
+
+        imp.processes_show_oom_score_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_oom_score(switch_row.is_active());
+            });
+
         imp.show_virtual_drives_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_show_virtual_drives(switch_row.is_active());
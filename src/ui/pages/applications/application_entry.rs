@@ -1,11 +1,9 @@
-use gtk::glib::{self};
-use log::trace;
-use process_data::Containerization;
-
 use crate::{
-    i18n::i18n,
+    ui::pages::containerization_label,
     utils::app::{App, AppsContext},
 };
+use gtk::glib::{self};
+use log::trace;
 
 mod imp {
     use std::cell::Cell;
@@ -61,6 +59,27 @@ mod imp {
         #[property(get, set)]
         write_total: Cell<u64>,
 
+        #[property(get, set)]
+        read_chars_speed: Cell<f64>,
+
+        #[property(get, set)]
+        read_chars_total: Cell<u64>,
+
+        #[property(get, set)]
+        write_chars_speed: Cell<f64>,
+
+        #[property(get, set)]
+        write_chars_total: Cell<u64>,
+
+        #[property(get, set)]
+        read_syscall_rate: Cell<f64>,
+
+        #[property(get, set)]
+        write_syscall_rate: Cell<f64>,
+
+        #[property(get, set)]
+        cancelled_write_total: Cell<u64>,
+
         #[property(get, set)]
         gpu_usage: Cell<f32>,
 
@@ -79,6 +98,9 @@ mod imp {
         #[property(get = Self::containerization, set = Self::set_containerization)]
         containerization: Cell<glib::GString>,
 
+        #[property(get = Self::cgroup_path, set = Self::set_cgroup_path)]
+        cgroup_path: Cell<Option<glib::GString>>,
+
         #[property(get, set)]
         running_processes: Cell<u32>,
 
@@ -103,6 +125,13 @@ mod imp {
                 read_total: Cell::new(0),
                 write_speed: Cell::new(0.0),
                 write_total: Cell::new(0),
+                read_chars_speed: Cell::new(0.0),
+                read_chars_total: Cell::new(0),
+                write_chars_speed: Cell::new(0.0),
+                write_chars_total: Cell::new(0),
+                read_syscall_rate: Cell::new(0.0),
+                write_syscall_rate: Cell::new(0.0),
+                cancelled_write_total: Cell::new(0),
                 gpu_usage: Cell::new(0.0),
                 enc_usage: Cell::new(0.0),
                 dec_usage: Cell::new(0.0),
@@ -110,6 +139,7 @@ mod imp {
                 symbolic: Cell::new(false),
                 running_since: Cell::new(None),
                 containerization: Cell::new(glib::GString::default()),
+                cgroup_path: Cell::new(None),
                 running_processes: Cell::new(0),
             }
         }
@@ -118,7 +148,7 @@ mod imp {
     impl ApplicationEntry {
         gstring_getter_setter!(name, containerization);
 
-        gstring_option_getter_setter!(description, id, running_since);
+        gstring_option_getter_setter!(description, id, running_since, cgroup_path);
 
         pub fn icon(&self) -> Icon {
             let icon = self.icon.replace(ThemedIcon::new("generic-process").into());
@@ -186,11 +216,7 @@ impl ApplicationEntry {
     pub fn new(app: &App, apps_context: &AppsContext) -> Self {
         trace!("Creating ApplicationEntry ({}) GObject…", app.display_name);
 
-        let containerization = match app.containerization {
-            Containerization::None => i18n("No"),
-            Containerization::Flatpak => i18n("Yes (Flatpak)"),
-            Containerization::Snap => i18n("Yes (Snap)"),
-        };
+        let containerization = containerization_label(&app.containerization);
 
         let this: Self = glib::Object::builder()
             .property("name", &app.display_name)
@@ -218,10 +244,18 @@ impl ApplicationEntry {
         self.set_read_total(app.read_total(apps_context));
         self.set_write_speed(app.write_speed(apps_context));
         self.set_write_total(app.write_total(apps_context));
+        self.set_read_chars_speed(app.read_chars_speed(apps_context));
+        self.set_read_chars_total(app.read_chars_total(apps_context));
+        self.set_write_chars_speed(app.write_chars_speed(apps_context));
+        self.set_write_chars_total(app.write_chars_total(apps_context));
+        self.set_read_syscall_rate(app.read_syscall_rate(apps_context));
+        self.set_write_syscall_rate(app.write_syscall_rate(apps_context));
+        self.set_cancelled_write_total(app.cancelled_write_total(apps_context));
         self.set_gpu_usage(app.gpu_usage(apps_context));
         self.set_enc_usage(app.enc_usage(apps_context));
         self.set_dec_usage(app.dec_usage(apps_context));
         self.set_gpu_mem_usage(app.gpu_mem_usage(apps_context));
         self.set_running_processes(app.running_processes() as u32);
+        self.set_cgroup_path(app.cgroup_path(apps_context).as_deref());
     }
 }
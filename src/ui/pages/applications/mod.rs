@@ -15,6 +15,7 @@ use gtk::{
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
 use crate::ui::dialogs::app_dialog::ResAppDialog;
+use crate::ui::pages::rate_label;
 use crate::ui::window::{Action, MainWindow};
 use crate::utils::NUM_CPUS;
 use crate::utils::app::AppsContext;
@@ -369,12 +370,27 @@ impl ResApplications {
         columns.push(self.add_read_total_column(&column_view));
         columns.push(self.add_write_speed_column(&column_view));
         columns.push(self.add_write_total_column(&column_view));
+        let char_read_speed_col = self.add_char_read_speed_column(&column_view);
+        let char_read_total_col = self.add_char_read_total_column(&column_view);
+        let char_write_speed_col = self.add_char_write_speed_column(&column_view);
+        let char_write_total_col = self.add_char_write_total_column(&column_view);
+        let read_syscalls_col = self.add_read_syscalls_column(&column_view);
+        let write_syscalls_col = self.add_write_syscalls_column(&column_view);
+        let cancelled_write_total_col = self.add_cancelled_write_total_column(&column_view);
         columns.push(self.add_gpu_column(&column_view));
         columns.push(self.add_gpu_mem_column(&column_view));
         columns.push(self.add_encoder_column(&column_view));
         columns.push(self.add_decoder_column(&column_view));
         columns.push(self.add_swap_column(&column_view));
         columns.push(self.add_combined_memory_column(&column_view));
+        // the sort column is saved as an index into `columns`, so newer columns go last to keep it valid
+        columns.push(char_read_speed_col);
+        columns.push(char_read_total_col);
+        columns.push(char_write_speed_col);
+        columns.push(char_write_total_col);
+        columns.push(read_syscalls_col);
+        columns.push(write_syscalls_col);
+        columns.push(cancelled_write_total_col);
 
         let store = gio::ListStore::new::<ApplicationEntry>();
 
@@ -1169,6 +1185,440 @@ impl ResApplications {
         write_total_col
     }
 
+    fn add_char_read_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let char_read_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let char_read_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Character Read")),
+            Some(char_read_speed_col_factory.clone()),
+        );
+
+        char_read_speed_col.set_resizable(true);
+
+        char_read_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ApplicationEntry>("read_chars_speed")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, read_chars_speed: f64| {
+                            convert_speed(read_chars_speed, false)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        char_read_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let char_read_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ApplicationEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_chars_speed",
+            ))
+            .build();
+
+        char_read_speed_col.set_sorter(Some(&char_read_speed_col_sorter));
+        char_read_speed_col.set_visible(SETTINGS.apps_show_char_read_speed());
+
+        column_view.append_column(&char_read_speed_col);
+
+        SETTINGS.connect_apps_show_char_read_speed(clone!(
+            #[weak]
+            char_read_speed_col,
+            move |visible| char_read_speed_col.set_visible(visible)
+        ));
+
+        char_read_speed_col
+    }
+
+    fn add_char_read_total_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let char_read_total_col_factory = gtk::SignalListItemFactory::new();
+
+        let char_read_total_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Character Read Total")),
+            Some(char_read_total_col_factory.clone()),
+        );
+
+        char_read_total_col.set_resizable(true);
+
+        char_read_total_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ApplicationEntry>("read_chars_total")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, read_chars_total: u64| {
+                            convert_storage(read_chars_total as f64, false)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        char_read_total_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let char_read_total_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ApplicationEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_chars_total",
+            ))
+            .build();
+
+        char_read_total_col.set_sorter(Some(&char_read_total_col_sorter));
+        char_read_total_col.set_visible(SETTINGS.apps_show_char_read_total());
+
+        column_view.append_column(&char_read_total_col);
+
+        SETTINGS.connect_apps_show_char_read_total(clone!(
+            #[weak]
+            char_read_total_col,
+            move |visible| char_read_total_col.set_visible(visible)
+        ));
+
+        char_read_total_col
+    }
+
+    fn add_char_write_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let char_write_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let char_write_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Character Write")),
+            Some(char_write_speed_col_factory.clone()),
+        );
+
+        char_write_speed_col.set_resizable(true);
+
+        char_write_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ApplicationEntry>("write_chars_speed")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, write_chars_speed: f64| {
+                            convert_speed(write_chars_speed, false)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        char_write_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let char_write_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ApplicationEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_chars_speed",
+            ))
+            .build();
+
+        char_write_speed_col.set_sorter(Some(&char_write_speed_col_sorter));
+        char_write_speed_col.set_visible(SETTINGS.apps_show_char_write_speed());
+
+        column_view.append_column(&char_write_speed_col);
+
+        SETTINGS.connect_apps_show_char_write_speed(clone!(
+            #[weak]
+            char_write_speed_col,
+            move |visible| char_write_speed_col.set_visible(visible)
+        ));
+
+        char_write_speed_col
+    }
+
+    fn add_char_write_total_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let char_write_total_col_factory = gtk::SignalListItemFactory::new();
+
+        let char_write_total_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Character Write Total")),
+            Some(char_write_total_col_factory.clone()),
+        );
+
+        char_write_total_col.set_resizable(true);
+
+        char_write_total_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ApplicationEntry>("write_chars_total")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, write_chars_total: u64| {
+                            convert_storage(write_chars_total as f64, false)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        char_write_total_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let char_write_total_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ApplicationEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_chars_total",
+            ))
+            .build();
+
+        char_write_total_col.set_sorter(Some(&char_write_total_col_sorter));
+        char_write_total_col.set_visible(SETTINGS.apps_show_char_write_total());
+
+        column_view.append_column(&char_write_total_col);
+
+        SETTINGS.connect_apps_show_char_write_total(clone!(
+            #[weak]
+            char_write_total_col,
+            move |visible| char_write_total_col.set_visible(visible)
+        ));
+
+        char_write_total_col
+    }
+
+    fn add_read_syscalls_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let read_syscalls_col_factory = gtk::SignalListItemFactory::new();
+
+        let read_syscalls_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Read Syscalls")),
+            Some(read_syscalls_col_factory.clone()),
+        );
+
+        read_syscalls_col.set_resizable(true);
+
+        read_syscalls_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ApplicationEntry>("read_syscall_rate")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, read_syscall_rate: f64| {
+                            rate_label(read_syscall_rate)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        read_syscalls_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let read_syscalls_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ApplicationEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_syscall_rate",
+            ))
+            .build();
+
+        read_syscalls_col.set_sorter(Some(&read_syscalls_col_sorter));
+        read_syscalls_col.set_visible(SETTINGS.apps_show_read_syscalls());
+
+        column_view.append_column(&read_syscalls_col);
+
+        SETTINGS.connect_apps_show_read_syscalls(clone!(
+            #[weak]
+            read_syscalls_col,
+            move |visible| read_syscalls_col.set_visible(visible)
+        ));
+
+        read_syscalls_col
+    }
+
+    fn add_write_syscalls_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let write_syscalls_col_factory = gtk::SignalListItemFactory::new();
+
+        let write_syscalls_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Write Syscalls")),
+            Some(write_syscalls_col_factory.clone()),
+        );
+
+        write_syscalls_col.set_resizable(true);
+
+        write_syscalls_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ApplicationEntry>("write_syscall_rate")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, write_syscall_rate: f64| {
+                            rate_label(write_syscall_rate)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        write_syscalls_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let write_syscalls_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ApplicationEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_syscall_rate",
+            ))
+            .build();
+
+        write_syscalls_col.set_sorter(Some(&write_syscalls_col_sorter));
+        write_syscalls_col.set_visible(SETTINGS.apps_show_write_syscalls());
+
+        column_view.append_column(&write_syscalls_col);
+
+        SETTINGS.connect_apps_show_write_syscalls(clone!(
+            #[weak]
+            write_syscalls_col,
+            move |visible| write_syscalls_col.set_visible(visible)
+        ));
+
+        write_syscalls_col
+    }
+
+    fn add_cancelled_write_total_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let cancelled_write_total_col_factory = gtk::SignalListItemFactory::new();
+
+        let cancelled_write_total_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Cancelled Write Total")),
+            Some(cancelled_write_total_col_factory.clone()),
+        );
+
+        cancelled_write_total_col.set_resizable(true);
+
+        cancelled_write_total_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ApplicationEntry>("cancelled_write_total")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, cancelled_write_total: u64| {
+                            convert_storage(cancelled_write_total as f64, false)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        cancelled_write_total_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let cancelled_write_total_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ApplicationEntry::static_type(),
+                None::<&gtk::Expression>,
+                "cancelled_write_total",
+            ))
+            .build();
+
+        cancelled_write_total_col.set_sorter(Some(&cancelled_write_total_col_sorter));
+        cancelled_write_total_col.set_visible(SETTINGS.apps_show_cancelled_write_total());
+
+        column_view.append_column(&cancelled_write_total_col);
+
+        SETTINGS.connect_apps_show_cancelled_write_total(clone!(
+            #[weak]
+            cancelled_write_total_col,
+            move |visible| cancelled_write_total_col.set_visible(visible)
+        ));
+
+        cancelled_write_total_col
+    }
+
     fn add_gpu_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
         let gpu_col_factory = gtk::SignalListItemFactory::new();
 
@@ -1529,6 +1979,7 @@ fn get_action_name(action: ProcessAction, name: &str) -> String {
         ProcessAction::STOP => i18n_f("Halt {}?", &[name]),
         ProcessAction::KILL => i18n_f("Kill {}?", &[name]),
         ProcessAction::CONT => i18n_f("Continue {}?", &[name]),
+        ProcessAction::Signal(signal) => i18n_f("Send {} to {}?", &[signal.as_str(), name]),
     }
 }
 
@@ -1542,6 +1993,9 @@ fn get_action_warning(action: ProcessAction) -> String {
             "Killing an app can come with serious risks such as losing data and security implications. Use with caution.",
         ),
         ProcessAction::CONT => String::new(),
+        ProcessAction::Signal(_) => i18n(
+            "Depending on the signal and how the app handles it, this might end the app or cause unexpected behavior.",
+        ),
     }
 }
 
@@ -1551,5 +2005,6 @@ fn get_action_description(action: ProcessAction) -> String {
         ProcessAction::STOP => i18n("Halt App"),
         ProcessAction::KILL => i18n("Kill App"),
         ProcessAction::CONT => i18n("Continue App"),
+        ProcessAction::Signal(_) => i18n("Send Signal"),
     }
 }
@@ -0,0 +1,140 @@
+use gtk::glib::{self};
+use log::trace;
+use process_data::socket::Connection;
+
+use crate::{i18n::i18n, utils::app::AppsContext};
+
+use super::socket_state_label;
+
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use super::*;
+
+    #[derive(Properties, Default)]
+    #[properties(wrapper_type = super::ConnectionEntry)]
+    pub struct ConnectionEntry {
+        #[property(get, set)]
+        inode: Cell<u64>,
+
+        #[property(get = Self::protocol, set = Self::set_protocol, type = glib::GString)]
+        protocol: Cell<glib::GString>,
+
+        #[property(get = Self::local_address, set = Self::set_local_address, type = glib::GString)]
+        local_address: Cell<glib::GString>,
+
+        #[property(get = Self::remote_address, set = Self::set_remote_address, type = glib::GString)]
+        remote_address: Cell<glib::GString>,
+
+        #[property(get = Self::state, set = Self::set_state, type = glib::GString)]
+        state: Cell<glib::GString>,
+
+        /// Will be 0 if the owning process is unknown
+        #[property(get, set)]
+        pid: Cell<i32>,
+
+        #[property(get = Self::process_name, set = Self::set_process_name, type = glib::GString)]
+        process_name: Cell<glib::GString>,
+
+        #[property(get = Self::app_name, set = Self::set_app_name, type = glib::GString)]
+        app_name: Cell<glib::GString>,
+    }
+
+    impl ConnectionEntry {
+        gstring_getter_setter!(
+            protocol,
+            local_address,
+            remote_address,
+            state,
+            process_name,
+            app_name
+        );
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ConnectionEntry {
+        const NAME: &'static str = "ConnectionEntry";
+        type Type = super::ConnectionEntry;
+    }
+
+    impl ObjectImpl for ConnectionEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct ConnectionEntry(ObjectSubclass<imp::ConnectionEntry>);
+}
+
+impl ConnectionEntry {
+    pub fn new(connection: &Connection, apps_context: &AppsContext) -> Self {
+        trace!(
+            "Creating ConnectionEntry GObject ({})…",
+            connection.socket.inode
+        );
+
+        let socket = &connection.socket;
+
+        let this: Self = glib::Object::builder()
+            .property("inode", socket.inode)
+            .property("protocol", socket.protocol.to_string())
+            .property(
+                "local_address",
+                socket
+                    .local_address
+                    .map_or_else(|| i18n("N/A"), |address| address.to_string()),
+            )
+            .property(
+                "remote_address",
+                socket
+                    .remote_address
+                    .map_or_else(|| i18n("N/A"), |address| address.to_string()),
+            )
+            .build();
+        this.update(connection, apps_context);
+        this
+    }
+
+    pub fn update(&self, connection: &Connection, apps_context: &AppsContext) {
+        trace!("Refreshing ConnectionEntry ({})…", connection.socket.inode);
+
+        self.set_state(socket_state_label(connection.socket.state));
+
+        let process = connection.pid.and_then(|pid| apps_context.get_process(pid));
+
+        if let Some(process) = process {
+            self.set_pid(process.data.pid);
+            self.set_process_name(process.display_name.as_str());
+            self.set_app_name(
+                apps_context
+                    .get_app_of_process(process.data.pid)
+                    .map(|app| app.display_name.as_str())
+                    .unwrap_or_default(),
+            );
+        } else {
+            self.set_pid(0);
+            self.set_process_name(i18n("N/A"));
+            self.set_app_name(i18n("N/A"));
+        }
+    }
+}
@@ -0,0 +1,455 @@
+pub mod connection_entry;
+
+use std::collections::HashMap;
+
+use adw::{prelude::*, subclass::prelude::*};
+use async_channel::Sender;
+use gtk::glib::{self, MainContext, Object, clone};
+use gtk::{
+    ColumnView, ColumnViewColumn, EventControllerKey, FilterChange, SortType, StringSorter, gio,
+};
+use process_data::socket::{Connection, SocketState};
+
+use crate::config::PROFILE;
+use crate::i18n::{i18n, i18n_f, pi18n};
+use crate::ui::window::Action;
+use crate::utils::app::AppsContext;
+
+use self::connection_entry::ConnectionEntry;
+
+pub const TAB_ID: &str = "connections";
+
+mod imp {
+    use std::{
+        cell::{Cell, RefCell},
+        sync::OnceLock,
+    };
+
+    use crate::ui::{pages::CONNECTIONS_PRIMARY_ORD, window::Action};
+
+    use super::*;
+
+    use gtk::{
+        CompositeTemplate,
+        gio::{Icon, ThemedIcon},
+        glib::{ParamSpec, Properties, Value},
+    };
+
+    #[derive(CompositeTemplate, Properties)]
+    #[properties(wrapper_type = super::ResConnections)]
+    #[template(resource = "/net/nokyan/Resources/ui/pages/connections.ui")]
+    pub struct ResConnections {
+        #[template_child]
+        pub search_bar: TemplateChild<gtk::SearchBar>,
+        #[template_child]
+        pub search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub connections_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub show_process_button: TemplateChild<gtk::Button>,
+        pub store: RefCell<gio::ListStore>,
+        pub selection_model: RefCell<gtk::SingleSelection>,
+        pub filter_model: RefCell<gtk::FilterListModel>,
+        pub column_view: RefCell<gtk::ColumnView>,
+
+        pub sender: OnceLock<Sender<Action>>,
+
+        #[property(get)]
+        uses_progress_bar: Cell<bool>,
+
+        #[property(get)]
+        icon: RefCell<Icon>,
+
+        #[property(get = Self::tab_name, type = glib::GString)]
+        tab_name: Cell<glib::GString>,
+
+        #[property(get = Self::tab_detail_string, type = glib::GString)]
+        tab_detail_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_usage_string, set = Self::set_tab_usage_string, type = glib::GString)]
+        tab_usage_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_id, type = glib::GString)]
+        tab_id: Cell<glib::GString>,
+
+        #[property(get)]
+        graph_locked_max_y: Cell<bool>,
+
+        #[property(get)]
+        primary_ord: Cell<u32>,
+
+        #[property(get)]
+        secondary_ord: Cell<u32>,
+    }
+
+    impl ResConnections {
+        gstring_getter_setter!(tab_name, tab_detail_string, tab_usage_string, tab_id);
+    }
+
+    impl Default for ResConnections {
+        fn default() -> Self {
+            Self {
+                search_bar: Default::default(),
+                search_entry: Default::default(),
+                connections_scrolled_window: Default::default(),
+                show_process_button: Default::default(),
+                store: gio::ListStore::new::<ConnectionEntry>().into(),
+                selection_model: RefCell::new(glib::object::Object::new::<gtk::SingleSelection>()),
+                filter_model: Default::default(),
+                column_view: Default::default(),
+                sender: Default::default(),
+                uses_progress_bar: Cell::new(false),
+                icon: RefCell::new(ThemedIcon::new("ethernet-symbolic").into()),
+                tab_name: Cell::new(glib::GString::from(i18n("Connections"))),
+                tab_detail_string: Cell::new(glib::GString::new()),
+                tab_usage_string: Cell::new(glib::GString::new()),
+                tab_id: Cell::new(glib::GString::from(TAB_ID)),
+                graph_locked_max_y: Cell::new(true),
+                primary_ord: Cell::new(CONNECTIONS_PRIMARY_ORD),
+                secondary_ord: Default::default(),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResConnections {
+        const NAME: &'static str = "ResConnections";
+        type Type = super::ResConnections;
+        type ParentType = adw::Bin;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResConnections {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+
+    impl WidgetImpl for ResConnections {}
+    impl BinImpl for ResConnections {}
+}
+
+glib::wrapper! {
+    pub struct ResConnections(ObjectSubclass<imp::ResConnections>)
+        @extends gtk::Widget, adw::Bin;
+}
+
+impl Default for ResConnections {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResConnections {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    pub fn toggle_search(&self) {
+        let imp = self.imp();
+        imp.search_bar
+            .set_search_mode(!imp.search_bar.is_search_mode());
+    }
+
+    pub fn close_search(&self) {
+        let imp = self.imp();
+        imp.search_bar.set_search_mode(false);
+    }
+
+    pub fn init(&self, sender: Sender<Action>) {
+        let imp = self.imp();
+        imp.sender.set(sender).unwrap();
+
+        self.setup_widgets();
+        self.setup_signals();
+    }
+
+    pub fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        *imp.column_view.borrow_mut() = gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = imp.column_view.borrow();
+
+        self.add_string_column(&column_view, &i18n("Protocol"), "protocol");
+        let local_address_col =
+            self.add_string_column(&column_view, &i18n("Local Address"), "local_address");
+        self.add_string_column(&column_view, &i18n("Remote Address"), "remote_address");
+        self.add_string_column(&column_view, &i18n("State"), "state");
+        let process_col = self.add_string_column(&column_view, &i18n("Process"), "process_name");
+        self.add_string_column(&column_view, &i18n("App"), "app_name");
+
+        process_col.set_expand(true);
+
+        let store = gio::ListStore::new::<ConnectionEntry>();
+
+        let filter_model = gtk::FilterListModel::new(
+            Some(store.clone()),
+            Some(gtk::CustomFilter::new(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |obj| this.search_filter(obj)
+            ))),
+        );
+
+        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
+
+        let selection_model = gtk::SingleSelection::new(Some(sort_model));
+        selection_model.set_autoselect(false);
+        selection_model.set_can_unselect(true);
+
+        column_view.set_model(Some(&selection_model));
+
+        column_view.sort_by_column(Some(&local_address_col), SortType::Ascending);
+
+        column_view.add_css_class("resources-columnview");
+
+        *imp.store.borrow_mut() = store;
+        *imp.selection_model.borrow_mut() = selection_model;
+        *imp.filter_model.borrow_mut() = filter_model;
+
+        imp.connections_scrolled_window
+            .set_child(Some(&*column_view));
+    }
+
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.selection_model
+            .borrow()
+            .connect_selection_changed(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_, _, _| {
+                    let imp = this.imp();
+                    imp.show_process_button.set_sensitive(
+                        this.get_selected_connection_entry()
+                            .is_some_and(|entry| entry.pid() != 0),
+                    );
+                }
+            ));
+
+        imp.column_view.borrow().connect_activate(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_, _| {
+                this.show_selected_process();
+            }
+        ));
+
+        imp.show_process_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                this.show_selected_process();
+            }
+        ));
+
+        imp.search_bar
+            .set_key_capture_widget(self.parent().as_ref());
+
+        imp.search_entry.connect_search_changed(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+            }
+        ));
+
+        let event_controller = EventControllerKey::new();
+        event_controller.connect_key_released(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_, key, _, _| {
+                if key.name().unwrap_or_default() == "Escape" {
+                    this.close_search();
+                }
+            }
+        ));
+        imp.search_entry.add_controller(event_controller);
+    }
+
+    pub fn search_bar(&self) -> &gtk::SearchBar {
+        &self.imp().search_bar
+    }
+
+    fn search_filter(&self, obj: &Object) -> bool {
+        let imp = self.imp();
+        let item = obj.downcast_ref::<ConnectionEntry>().unwrap();
+        let search_string = imp.search_entry.text().to_string().to_lowercase();
+        !imp.search_bar.is_search_mode()
+            || [
+                item.protocol(),
+                item.local_address(),
+                item.remote_address(),
+                item.state(),
+                item.process_name(),
+                item.app_name(),
+            ]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&search_string))
+            || item.pid().to_string() == search_string
+    }
+
+    pub fn get_selected_connection_entry(&self) -> Option<ConnectionEntry> {
+        self.imp()
+            .selection_model
+            .borrow()
+            .selected_item()
+            .and_downcast::<ConnectionEntry>()
+    }
+
+    fn show_selected_process(&self) {
+        let Some(pid) = self
+            .get_selected_connection_entry()
+            .map(|entry| entry.pid())
+            .filter(|pid| *pid != 0)
+        else {
+            return;
+        };
+
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                let _ = this
+                    .imp()
+                    .sender
+                    .get()
+                    .unwrap()
+                    .send(Action::ShowProcess(pid))
+                    .await;
+            }
+        ));
+    }
+
+    pub fn refresh_connections_list(&self, connections: &[Connection], apps_context: &AppsContext) {
+        let imp = self.imp();
+
+        let store = imp.store.borrow_mut();
+
+        let mut connections: HashMap<u64, &Connection> = connections
+            .iter()
+            .map(|connection| (connection.socket.inode, connection))
+            .collect();
+
+        // update the connections that have existed before and remove the ones that are gone
+        store.retain(|object| {
+            let entry = object.downcast_ref::<ConnectionEntry>().unwrap();
+            if let Some(connection) = connections.remove(&entry.inode()) {
+                entry.update(connection, apps_context);
+                true
+            } else {
+                false
+            }
+        });
+
+        // add the new connections
+        let items: Vec<ConnectionEntry> = connections
+            .into_values()
+            .map(|connection| ConnectionEntry::new(connection, apps_context))
+            .collect();
+        store.extend_from_slice(&items);
+
+        if let Some(sorter) = imp.column_view.borrow().sorter() {
+            sorter.changed(gtk::SorterChange::Different);
+        }
+
+        self.set_tab_usage_string(i18n_f(
+            "Open Connections: {}",
+            &[&(store.n_items()).to_string()],
+        ));
+    }
+
+    fn add_string_column(
+        &self,
+        column_view: &ColumnView,
+        title: &str,
+        property: &'static str,
+    ) -> ColumnViewColumn {
+        let col_factory = gtk::SignalListItemFactory::new();
+
+        let col = gtk::ColumnViewColumn::new(Some(title), Some(col_factory.clone()));
+
+        col.set_resizable(true);
+
+        col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+
+            item.set_child(Some(&row));
+
+            item.property_expression("item")
+                .chain_property::<ConnectionEntry>(property)
+                .bind(&row, "text", gtk::Widget::NONE);
+        });
+
+        col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ConnectionEntry::static_type(),
+                None::<&gtk::Expression>,
+                property,
+            ))
+            .build();
+
+        col.set_sorter(Some(&col_sorter));
+
+        column_view.append_column(&col);
+
+        col
+    }
+}
+
+pub fn socket_state_label(state: SocketState) -> String {
+    match state {
+        SocketState::Established => pi18n("socket state", "Established"),
+        SocketState::SynSent => pi18n("socket state", "SYN Sent"),
+        SocketState::SynRecv => pi18n("socket state", "SYN Received"),
+        SocketState::FinWait1 => pi18n("socket state", "FIN Wait 1"),
+        SocketState::FinWait2 => pi18n("socket state", "FIN Wait 2"),
+        SocketState::TimeWait => pi18n("socket state", "Time Wait"),
+        SocketState::Close => pi18n("socket state", "Closed"),
+        SocketState::CloseWait => pi18n("socket state", "Close Wait"),
+        SocketState::LastAck => pi18n("socket state", "Last ACK"),
+        SocketState::Listen => pi18n("socket state", "Listening"),
+        SocketState::Closing => pi18n("socket state", "Closing"),
+        SocketState::NewSynRecv => pi18n("socket state", "New SYN Received"),
+        SocketState::Unknown => i18n("N/A"),
+    }
+}
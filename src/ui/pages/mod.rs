@@ -1,11 +1,13 @@
 use std::{collections::HashMap, sync::LazyLock};
 
-use process_data::Niceness;
+use process_data::{Containerization, Niceness};
 
-use crate::i18n::pi18n;
+use crate::i18n::{i18n, i18n_f, pi18n};
+use crate::utils::short_container_id;
 
 pub mod applications;
 pub mod battery;
+pub mod connections;
 pub mod cpu;
 pub mod drive;
 pub mod gpu;
@@ -16,13 +18,14 @@ pub mod processes;
 
 const APPLICATIONS_PRIMARY_ORD: u32 = 0;
 const PROCESSES_PRIMARY_ORD: u32 = 1;
-const CPU_PRIMARY_ORD: u32 = 2;
-const MEMORY_PRIMARY_ORD: u32 = 3;
-const GPU_PRIMARY_ORD: u32 = 4;
-const NPU_PRIMARY_ORD: u32 = 5;
-const DRIVE_PRIMARY_ORD: u32 = 6;
-const NETWORK_PRIMARY_ORD: u32 = 7;
-const BATTERY_PRIMARY_ORD: u32 = 8;
+const CONNECTIONS_PRIMARY_ORD: u32 = 2;
+const CPU_PRIMARY_ORD: u32 = 3;
+const MEMORY_PRIMARY_ORD: u32 = 4;
+const GPU_PRIMARY_ORD: u32 = 5;
+const NPU_PRIMARY_ORD: u32 = 6;
+const DRIVE_PRIMARY_ORD: u32 = 7;
+const NETWORK_PRIMARY_ORD: u32 = 8;
+const BATTERY_PRIMARY_ORD: u32 = 9;
 
 pub static NICE_TO_LABEL: LazyLock<HashMap<Niceness, (String, u32)>> = LazyLock::new(|| {
     let mut hash_map = HashMap::new();
@@ -64,3 +67,24 @@ pub static NICE_TO_LABEL: LazyLock<HashMap<Niceness, (String, u32)>> = LazyLock:
 
     hash_map
 });
+
+pub fn containerization_label(containerization: &Containerization) -> String {
+    match containerization {
+        Containerization::None => i18n("No"),
+        Containerization::Flatpak => i18n("Yes (Flatpak)"),
+        Containerization::Snap => i18n("Yes (Snap)"),
+        Containerization::Docker(id) => i18n_f("Yes (Docker: {})", &[&short_container_id(id)]),
+        Containerization::Podman(id) => i18n_f("Yes (Podman: {})", &[&short_container_id(id)]),
+        Containerization::Lxc(name) => i18n_f("Yes (LXC: {})", &[name]),
+        Containerization::Nspawn(name) => i18n_f("Yes (systemd-nspawn: {})", &[name]),
+    }
+}
+
+/// Formats an events-per-second rate such as page faults, -1.0 means that the rate is unknown
+pub fn rate_label(rate: f64) -> String {
+    if rate == -1.0 {
+        i18n("N/A")
+    } else {
+        i18n_f("{}/s", &[&format!("{rate:.1}")])
+    }
+}
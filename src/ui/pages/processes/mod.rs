@@ -1,7 +1,7 @@
 pub mod process_entry;
 mod process_name_cell;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
 
 use adw::ResponseAppearance;
@@ -10,15 +10,16 @@ use async_channel::Sender;
 use gtk::glib::{self, MainContext, Object, clone, closure};
 use gtk::{
     BitsetIter, ColumnView, ColumnViewColumn, EventControllerKey, FilterChange, ListItem,
-    NumericSorter, SortType, StringSorter, Widget, gio,
+    NumericSorter, SortType, StringSorter, TreeListRow, Widget, gio,
 };
-use process_data::Niceness;
+use nix::sys::signal::Signal;
+use process_data::{Niceness, pidfd::ProcessIdentity};
 
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f, ni18n_f};
 use crate::ui::dialogs::process_dialog::ResProcessDialog;
 use crate::ui::dialogs::process_options_dialog::ResProcessOptionsDialog;
-use crate::ui::pages::NICE_TO_LABEL;
+use crate::ui::pages::{NICE_TO_LABEL, rate_label};
 use crate::ui::window::{Action, MainWindow};
 use crate::utils::NUM_CPUS;
 use crate::utils::app::AppsContext;
@@ -114,6 +115,12 @@ mod imp {
 
         pub columns: RefCell<Vec<ColumnViewColumn>>,
 
+        /// Whether processes are currently arranged below their parents, which is not the case while searching
+        pub tree_view_active: Cell<bool>,
+
+        /// PIDs of the processes whose parents aren't listed, these are the top-level rows of the tree view
+        pub tree_roots: RefCell<HashSet<libc::pid_t>>,
+
         #[property(get)]
         uses_progress_bar: Cell<bool>,
 
@@ -178,6 +185,8 @@ mod imp {
                 tab_id: Cell::new(glib::GString::from(TAB_ID)),
                 popped_over_process: Default::default(),
                 columns: Default::default(),
+                tree_view_active: Default::default(),
+                tree_roots: Default::default(),
                 graph_locked_max_y: Cell::new(true),
                 primary_ord: Cell::new(PROCESSES_PRIMARY_ORD),
                 secondary_ord: Default::default(),
@@ -252,6 +261,32 @@ mod imp {
                 },
             );
 
+            klass.install_action(
+                "processes.context-kill-process-tree",
+                None,
+                move |res_processes, _, _| {
+                    if let Some(process_entry) =
+                        res_processes.imp().popped_over_process.borrow().as_ref()
+                    {
+                        res_processes.open_kill_process_tree_dialog(process_entry);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "processes.context-send-signal",
+                Some(glib::VariantTy::STRING),
+                move |res_processes, _, parameter| {
+                    if let (Some(process_entry), Some(action)) = (
+                        res_processes.imp().popped_over_process.borrow().as_ref(),
+                        signal_action_from_parameter(parameter),
+                    ) {
+                        res_processes
+                            .open_process_action_dialog(vec![process_entry.clone()], action);
+                    }
+                },
+            );
+
             klass.install_action(
                 "processes.context-information",
                 None,
@@ -316,6 +351,30 @@ mod imp {
                 },
             );
 
+            klass.install_action(
+                "processes.kill-process-tree",
+                None,
+                move |res_processes, _, _| {
+                    if let [process_entry] = res_processes.get_selected_process_entries().as_slice()
+                    {
+                        res_processes.open_kill_process_tree_dialog(process_entry);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "processes.send-signal",
+                Some(glib::VariantTy::STRING),
+                move |res_processes, _, parameter| {
+                    let selected = res_processes.get_selected_process_entries();
+                    if let Some(action) = signal_action_from_parameter(parameter) {
+                        if !selected.is_empty() {
+                            res_processes.open_process_action_dialog(selected, action);
+                        }
+                    }
+                },
+            );
+
             Self::bind_template(klass);
         }
 
@@ -380,6 +439,41 @@ impl ResProcesses {
         imp.search_bar.set_search_mode(false);
     }
 
+    /// Selects the process with the PID `pid` (if it's currently listed) and scrolls to it
+    pub fn select_process(&self, pid: libc::pid_t) {
+        let imp = self.imp();
+
+        self.close_search();
+
+        let selection_model = imp.selection_model.borrow();
+
+        let position = (0..selection_model.n_items()).find(|position| {
+            selection_model
+                .item(*position)
+                .and_then(process_entry_from_row)
+                .is_some_and(|entry| !entry.is_thread() && entry.pid() == pid)
+        });
+
+        let Some(position) = position else {
+            return;
+        };
+
+        selection_model.select_item(position, true);
+
+        // ColumnView::scroll_to() requires GTK 4.12, so ask the inner list view to scroll instead
+        let column_view = imp.column_view.borrow();
+        let mut child = column_view.first_child();
+        while let Some(widget) = child {
+            if widget
+                .activate_action("list.scroll-to-item", Some(&position.to_variant()))
+                .is_ok()
+            {
+                break;
+            }
+            child = widget.next_sibling();
+        }
+    }
+
     pub fn init(&self, sender: Sender<Action>) {
         let imp = self.imp();
         imp.sender.set(sender).unwrap();
@@ -401,7 +495,11 @@ impl ResProcesses {
             #[weak(rename_to = this)]
             self,
             move |_, _, x, y| {
-                if let Some(entry) = item.item().and_downcast::<ProcessEntry>() {
+                if let Some(entry) = item
+                    .item()
+                    .and_then(process_entry_from_row)
+                    .filter(|entry| !entry.is_thread())
+                {
                     let imp = this.imp();
 
                     let selected = this.get_selected_process_entries();
@@ -412,6 +510,18 @@ impl ResProcesses {
                         &imp.popover_menu
                     };
 
+                    // only offer to continue processes that are actually stopped
+                    this.action_set_enabled(
+                        "processes.context-continue-process",
+                        entry.process_state().is_stopped(),
+                    );
+                    this.action_set_enabled(
+                        "processes.continue-process",
+                        selected
+                            .iter()
+                            .any(|entry| entry.process_state().is_stopped()),
+                    );
+
                     *imp.popped_over_process.borrow_mut() = Some(entry);
 
                     let position = widget
@@ -454,6 +564,13 @@ impl ResProcesses {
         columns.push(self.add_read_total_column(&column_view));
         columns.push(self.add_write_speed_column(&column_view));
         columns.push(self.add_write_total_column(&column_view));
+        let char_read_speed_col = self.add_char_read_speed_column(&column_view);
+        let char_read_total_col = self.add_char_read_total_column(&column_view);
+        let char_write_speed_col = self.add_char_write_speed_column(&column_view);
+        let char_write_total_col = self.add_char_write_total_column(&column_view);
+        let read_syscalls_col = self.add_read_syscalls_column(&column_view);
+        let write_syscalls_col = self.add_write_syscalls_column(&column_view);
+        let cancelled_write_total_col = self.add_cancelled_write_total_column(&column_view);
         columns.push(self.add_gpu_column(&column_view));
         columns.push(self.add_gpu_mem_column(&column_view));
         columns.push(self.add_encoder_column(&column_view));
@@ -461,9 +578,28 @@ impl ResProcesses {
         columns.push(self.add_total_cpu_time_column(&column_view));
         columns.push(self.add_user_cpu_time_column(&column_view));
         columns.push(self.add_system_cpu_time_column(&column_view));
+        let major_fault_rate_col = self.add_major_fault_rate_column(&column_view);
+        let context_switch_rate_col = self.add_context_switch_rate_column(&column_view);
         columns.push(self.add_priority_column(&column_view));
+        let io_priority_col = self.add_io_priority_column(&column_view);
+        let scheduling_policy_col = self.add_scheduling_policy_column(&column_view);
+        let oom_score_col = self.add_oom_score_column(&column_view);
         columns.push(self.add_swap_column(&column_view));
         columns.push(self.add_combined_memory_column(&column_view));
+        columns.push(self.add_state_column(&column_view));
+        // the sort column is saved as an index into `columns`, so newer columns go last to keep it valid
+        columns.push(major_fault_rate_col);
+        columns.push(context_switch_rate_col);
+        columns.push(char_read_speed_col);
+        columns.push(char_read_total_col);
+        columns.push(char_write_speed_col);
+        columns.push(char_write_total_col);
+        columns.push(read_syscalls_col);
+        columns.push(write_syscalls_col);
+        columns.push(cancelled_write_total_col);
+        columns.push(io_priority_col);
+        columns.push(scheduling_policy_col);
+        columns.push(oom_score_col);
 
         let store = gio::ListStore::new::<ProcessEntry>();
 
@@ -472,11 +608,17 @@ impl ResProcesses {
             Some(gtk::CustomFilter::new(clone!(
                 #[strong(rename_to = this)]
                 self,
-                move |obj| this.search_filter(obj)
+                move |obj| this.search_filter(obj) && this.tree_filter(obj)
             ))),
         );
 
-        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
+        imp.tree_view_active.set(SETTINGS.processes_show_tree());
+        let tree_model = Self::tree_list_model(&filter_model, imp.tree_view_active.get());
+
+        let sort_model = gtk::SortListModel::new(
+            Some(tree_model),
+            Some(gtk::TreeListRowSorter::new(column_view.sorter())),
+        );
 
         let selection_model = gtk::MultiSelection::new(Some(sort_model.clone()));
 
@@ -510,15 +652,24 @@ impl ResProcesses {
             .connect_selection_changed(clone!(
                 #[weak(rename_to = this)]
                 self,
-                move |model, _, _| {
+                move |_, _, _| {
                     let imp = this.imp();
-                    let bitset = model.selection();
+                    // threads can be selected, but they can't be manipulated on their own
+                    let selected_entries = this.get_selected_process_entries();
+                    let selected = selected_entries.len();
 
-                    imp.information_button.set_sensitive(bitset.size() == 1);
-                    imp.options_button.set_sensitive(bitset.size() == 1);
-                    imp.end_process_button.set_sensitive(bitset.size() > 0);
+                    this.action_set_enabled(
+                        "processes.continue-process",
+                        selected_entries
+                            .iter()
+                            .any(|entry| entry.process_state().is_stopped()),
+                    );
+
+                    imp.information_button.set_sensitive(selected == 1);
+                    imp.options_button.set_sensitive(selected == 1);
+                    imp.end_process_button.set_sensitive(selected > 0);
 
-                    if bitset.size() <= 1 {
+                    if selected <= 1 {
                         imp.end_process_button.set_label(&i18n("End Process"));
                         imp.end_process_button
                             .set_menu_model(Some(&imp.end_process_menu.get()));
@@ -538,12 +689,34 @@ impl ResProcesses {
             self,
             move |_| {
                 let imp = this.imp();
-                if let Some(filter) = imp.filter_model.borrow().filter() {
+                // search results are shown as a flat list, so the tree has to be rebuilt when starting or
+                // stopping to search
+                if imp.tree_view_active.get() != this.tree_view_wanted() {
+                    this.rebuild_tree_list_model();
+                } else if let Some(filter) = imp.filter_model.borrow().filter() {
                     filter.changed(FilterChange::Different);
                 }
             }
         ));
 
+        SETTINGS.connect_processes_show_tree(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.rebuild_tree_list_model()
+        ));
+
+        SETTINGS.connect_processes_show_subtree_usage(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                this.update_process_tree(&imp.store.borrow());
+                if let Some(sorter) = imp.column_view.borrow().sorter() {
+                    sorter.changed(gtk::SorterChange::Different);
+                }
+            }
+        ));
+
         let event_controller = EventControllerKey::new();
         event_controller.connect_key_released(clone!(
             #[weak(rename_to = this)]
@@ -560,15 +733,9 @@ impl ResProcesses {
             #[weak(rename_to = this)]
             self,
             move |_| {
-                let imp = this.imp();
-                let bitset = imp.selection_model.borrow().selection();
-                let selection_option = imp
-                    .selection_model
-                    .borrow()
-                    .item(bitset.maximum()) // the info button is only available when only 1 item is selected, so this should be fine
-                    .map(|object| object.downcast::<ProcessEntry>().unwrap());
-                if let Some(selection) = selection_option {
-                    this.open_options_dialog(&selection);
+                // the options button is only available when only 1 process is selected, so this should be fine
+                if let Some(selection) = this.get_selected_process_entries().first() {
+                    this.open_options_dialog(selection);
                 }
             }
         ));
@@ -577,15 +744,9 @@ impl ResProcesses {
             #[weak(rename_to = this)]
             self,
             move |_| {
-                let imp = this.imp();
-                let bitset = imp.selection_model.borrow().selection();
-                let selection_option = imp
-                    .selection_model
-                    .borrow()
-                    .item(bitset.maximum()) // the info button is only available when only 1 item is selected, so this should be fine
-                    .map(|object| object.downcast::<ProcessEntry>().unwrap());
-                if let Some(selection) = selection_option {
-                    this.open_info_dialog(&selection);
+                // the info button is only available when only 1 process is selected, so this should be fine
+                if let Some(selection) = this.get_selected_process_entries().first() {
+                    this.open_info_dialog(selection);
                 }
             }
         ));
@@ -682,6 +843,14 @@ impl ResProcesses {
 
         dialog.init(process, process.user());
 
+        let processes: Vec<ProcessEntry> = imp
+            .store
+            .borrow()
+            .iter::<ProcessEntry>()
+            .flatten()
+            .collect();
+        dialog.set_namespace_peers(process, &processes);
+
         dialog.connect_closed(clone!(
             #[weak(rename_to = this)]
             self,
@@ -702,6 +871,7 @@ impl ResProcesses {
         !imp.search_bar.is_search_mode()
             || item.name().to_lowercase().contains(&search_string)
             || item.commandline().to_lowercase().contains(&search_string)
+            || item.state().to_lowercase().contains(&search_string)
     }
 
     pub fn get_selected_process_entries(&self) -> Vec<ProcessEntry> {
@@ -715,7 +885,7 @@ impl ResProcesses {
                     imp.selection_model
                         .borrow()
                         .item(position)
-                        .map(|object| object.downcast::<ProcessEntry>().unwrap())
+                        .and_then(process_entry_from_row)
                 })
                 .collect();
 
@@ -723,11 +893,13 @@ impl ResProcesses {
                 .selection_model
                 .borrow()
                 .item(first)
-                .map(|object| object.downcast::<ProcessEntry>().unwrap())
+                .and_then(process_entry_from_row)
             {
                 return_vec.insert(0, first_process);
             }
 
+            return_vec.retain(|entry| !entry.is_thread());
+
             return_vec
         } else {
             Vec::default()
@@ -751,6 +923,8 @@ impl ResProcesses {
         let mut info_dialog_opt = imp.open_info_dialog.borrow_mut();
         let mut options_dialog_opt = imp.open_options_dialog.borrow_mut();
 
+        let memory_metric = apps_context.memory_metric();
+
         let mut pids_to_remove = HashSet::new();
         let mut already_existing_pids = HashSet::new();
 
@@ -758,7 +932,7 @@ impl ResProcesses {
         store.iter::<ProcessEntry>().flatten().for_each(|object| {
             let item_pid = object.pid();
             if let Some(process) = apps_context.get_process(item_pid) {
-                object.update(process);
+                object.update(process, memory_metric);
                 if let Some((dialog_pid, dialog)) = &*info_dialog_opt {
                     if *dialog_pid == item_pid {
                         dialog.update(&object);
@@ -800,10 +974,12 @@ impl ResProcesses {
                 !already_existing_pids.contains(&process.data.pid)
                     && !pids_to_remove.contains(&process.data.pid)
             })
-            .map(ProcessEntry::new)
+            .map(|process| ProcessEntry::new(process, memory_metric))
             .collect();
         store.extend_from_slice(&items);
 
+        self.update_process_tree(&store);
+
         if let Some(sorter) = imp.column_view.borrow().sorter() {
             sorter.changed(gtk::SorterChange::Different);
         }
@@ -815,14 +991,20 @@ impl ResProcesses {
     }
 
     pub fn open_process_action_dialog(&self, processes: Vec<ProcessEntry>, action: ProcessAction) {
+        // the processes are pinned down now, so that a PID that is recycled while the dialog is open isn't acted upon
+        let identities: Vec<ProcessIdentity> =
+            processes.iter().map(ProcessEntry::identity).collect();
+        let display_name = processes
+            .first()
+            .map(|process| process.name().to_string())
+            .unwrap_or_default();
+
         // Nothing too bad can happen on Continue so dont show the dialog
         if action == ProcessAction::CONT {
             let main_context = MainContext::default();
             main_context.spawn_local(clone!(
                 #[weak(rename_to = this)]
                 self,
-                #[strong]
-                processes,
                 async move {
                     let imp = this.imp();
                     let _ = imp
@@ -831,10 +1013,8 @@ impl ResProcesses {
                         .unwrap()
                         .send(Action::ManipulateProcesses(
                             action,
-                            processes
-                                .iter()
-                                .map(process_entry::ProcessEntry::pid)
-                                .collect(),
+                            identities,
+                            display_name,
                             imp.toast_overlay.get(),
                         ))
                         .await;
@@ -868,8 +1048,6 @@ impl ResProcesses {
             clone!(
                 #[weak(rename_to = this)]
                 self,
-                #[strong]
-                processes,
                 move |_, response| {
                     if response == "yes" {
                         let main_context = MainContext::default();
@@ -877,7 +1055,9 @@ impl ResProcesses {
                             #[weak]
                             this,
                             #[strong]
-                            processes,
+                            identities,
+                            #[strong]
+                            display_name,
                             async move {
                                 let imp = this.imp();
                                 let _ = imp
@@ -886,10 +1066,242 @@ impl ResProcesses {
                                     .unwrap()
                                     .send(Action::ManipulateProcesses(
                                         action,
-                                        processes
-                                            .iter()
-                                            .map(process_entry::ProcessEntry::pid)
-                                            .collect(),
+                                        identities,
+                                        display_name,
+                                        imp.toast_overlay.get(),
+                                    ))
+                                    .await;
+                            }
+                        ));
+                    }
+                }
+            ),
+        );
+
+        dialog.present(Some(&MainWindow::default()));
+    }
+
+    fn tree_view_wanted(&self) -> bool {
+        SETTINGS.processes_show_tree() && self.imp().search_entry.text().is_empty()
+    }
+
+    /// Creates the model of the column view's rows. If `tree_view` is true, processes can be expanded to show their
+    /// child processes, otherwise processes with more than one thread can be expanded to show their threads.
+    fn tree_list_model(filter_model: &gtk::FilterListModel, tree_view: bool) -> gtk::TreeListModel {
+        gtk::TreeListModel::new(filter_model.clone(), false, tree_view, move |object| {
+            let entry = object.downcast_ref::<ProcessEntry>()?;
+            if tree_view {
+                // children might be added later on, so always offer the model
+                Some(entry.children().upcast())
+            } else {
+                (!entry.is_thread() && entry.threads().n_items() > 1)
+                    .then(|| entry.threads().upcast())
+            }
+        })
+    }
+
+    /// Switches between the flat list and the tree view according to the settings and the search
+    fn rebuild_tree_list_model(&self) {
+        let imp = self.imp();
+
+        imp.tree_view_active.set(self.tree_view_wanted());
+        self.update_process_tree(&imp.store.borrow());
+
+        let tree_model =
+            Self::tree_list_model(&imp.filter_model.borrow(), imp.tree_view_active.get());
+        imp.sort_model.borrow().set_model(Some(&tree_model));
+
+        if let Some(filter) = imp.filter_model.borrow().filter() {
+            filter.changed(FilterChange::Different);
+        }
+    }
+
+    /// Only lets the roots of the process tree through while the tree view is active, their descendants are part of
+    /// the models of their parents then
+    fn tree_filter(&self, obj: &Object) -> bool {
+        let imp = self.imp();
+
+        !imp.tree_view_active.get()
+            || obj
+                .downcast_ref::<ProcessEntry>()
+                .is_some_and(|entry| imp.tree_roots.borrow().contains(&entry.pid()))
+    }
+
+    /// Updates the children of all entries in `store` and, if enabled, their combined usages for the tree view
+    fn update_process_tree(&self, store: &gio::ListStore) {
+        let imp = self.imp();
+
+        let entries: Vec<ProcessEntry> = store.iter::<ProcessEntry>().flatten().collect();
+
+        if !imp.tree_view_active.get() {
+            for entry in &entries {
+                if entry.children().n_items() > 0 {
+                    entry.set_children(&[]);
+                }
+                entry.set_tree_leaf(false);
+                entry.set_tree_cpu_usage(entry.cpu_usage());
+                entry.set_tree_memory_usage(entry.memory_usage());
+            }
+            return;
+        }
+
+        let pids: HashSet<libc::pid_t> = entries.iter().map(ProcessEntry::pid).collect();
+
+        let mut children: HashMap<libc::pid_t, Vec<ProcessEntry>> = HashMap::new();
+        let mut tree_roots = HashSet::new();
+        for entry in &entries {
+            if pids.contains(&entry.parent_pid()) && entry.parent_pid() != entry.pid() {
+                children
+                    .entry(entry.parent_pid())
+                    .or_default()
+                    .push(entry.clone());
+            } else {
+                tree_roots.insert(entry.pid());
+            }
+        }
+
+        for entry in &entries {
+            let entry_children = children.get(&entry.pid()).map_or(&[][..], Vec::as_slice);
+            entry.set_children(entry_children);
+            entry.set_tree_leaf(entry_children.is_empty());
+        }
+
+        if SETTINGS.processes_show_subtree_usage() {
+            let mut subtree_usages = HashMap::new();
+            for entry in &entries {
+                let (cpu_usage, memory_usage) =
+                    subtree_usage(entry, &children, &mut subtree_usages);
+                entry.set_tree_cpu_usage(cpu_usage);
+                entry.set_tree_memory_usage(memory_usage);
+            }
+        } else {
+            for entry in &entries {
+                entry.set_tree_cpu_usage(entry.cpu_usage());
+                entry.set_tree_memory_usage(entry.memory_usage());
+            }
+        }
+
+        let roots_changed = *imp.tree_roots.borrow() != tree_roots;
+        if roots_changed {
+            *imp.tree_roots.borrow_mut() = tree_roots;
+            if let Some(filter) = imp.filter_model.borrow().filter() {
+                filter.changed(FilterChange::Different);
+            }
+        }
+    }
+
+    /// Returns `root` and all of its descendants in depth-first order, each paired with its depth below `root`
+    fn process_tree(&self, root: &ProcessEntry) -> Vec<(usize, ProcessEntry)> {
+        let mut children: HashMap<i32, Vec<ProcessEntry>> = HashMap::new();
+        for entry in self.imp().store.borrow().iter::<ProcessEntry>().flatten() {
+            children.entry(entry.parent_pid()).or_default().push(entry);
+        }
+
+        let mut tree = Vec::new();
+        let mut stack = vec![(0, root.clone())];
+        while let Some((depth, entry)) = stack.pop() {
+            if let Some(entry_children) = children.get(&entry.pid()) {
+                stack.extend(
+                    entry_children
+                        .iter()
+                        .rev()
+                        .map(|child| (depth + 1, child.clone())),
+                );
+            }
+            tree.push((depth, entry));
+        }
+
+        tree
+    }
+
+    pub fn open_kill_process_tree_dialog(&self, root: &ProcessEntry) {
+        let tree = self.process_tree(root);
+        let descendants = tree.len().saturating_sub(1);
+
+        let heading = if descendants == 0 {
+            get_action_name(ProcessAction::KILL, &root.name())
+        } else {
+            ni18n_f(
+                "Kill {} and its descendant?",
+                "Kill {} and its {} descendants?",
+                descendants as u32,
+                &[&root.name(), &descendants.to_string()],
+            )
+        };
+
+        let tree_string = tree
+            .iter()
+            .map(|(depth, entry)| {
+                format!(
+                    "{}{} ({})",
+                    "    ".repeat(*depth),
+                    entry.name(),
+                    entry.pid()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let tree_label = gtk::Label::builder()
+            .label(tree_string)
+            .xalign(0.0)
+            .selectable(true)
+            .css_classes(["monospace"])
+            .build();
+
+        let tree_scrolled_window = gtk::ScrolledWindow::builder()
+            .child(&tree_label)
+            .propagate_natural_height(true)
+            .max_content_height(300)
+            .build();
+
+        // children are killed before their parents so that they can't be reparented or respawned in between
+        let mut leaf_first = tree;
+        leaf_first.sort_by(|(depth_a, _), (depth_b, _)| depth_b.cmp(depth_a));
+        let identities: Vec<ProcessIdentity> = leaf_first
+            .iter()
+            .map(|(_, entry)| entry.identity())
+            .collect();
+        let root_name = root.name().to_string();
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(heading)
+            .body(get_action_warning(ProcessAction::KILL))
+            .extra_child(&tree_scrolled_window)
+            .build();
+
+        dialog.add_response("yes", &i18n("Kill Process Tree"));
+        dialog.set_response_appearance("yes", ResponseAppearance::Destructive);
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.set_default_response(Some("no"));
+        dialog.set_close_response("no");
+
+        dialog.connect_response(
+            None,
+            clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_, response| {
+                    if response == "yes" {
+                        let main_context = MainContext::default();
+                        main_context.spawn_local(clone!(
+                            #[weak]
+                            this,
+                            #[strong]
+                            identities,
+                            #[strong]
+                            root_name,
+                            async move {
+                                let imp = this.imp();
+                                let _ = imp
+                                    .sender
+                                    .get()
+                                    .unwrap()
+                                    .send(Action::ManipulateProcessTree(
+                                        ProcessAction::KILL,
+                                        identities,
+                                        root_name,
                                         imp.toast_overlay.get(),
                                     ))
                                     .await;
@@ -921,31 +1333,58 @@ impl ResProcesses {
 
                 let row = ResProcessNameCell::new();
 
-                item.set_child(Some(&row));
+                let expander = gtk::TreeExpander::new();
+                expander.set_child(Some(&row));
+
+                item.set_child(Some(&expander));
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("name")
                     .bind(&row, "name", Widget::NONE);
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("icon")
                     .bind(&row, "icon", Widget::NONE);
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("commandline")
                     .bind(&row, "tooltip", Widget::NONE);
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("symbolic")
                     .bind(&row, "symbolic", Widget::NONE);
 
+                // rows of the tree view are always expandable since children might appear later on
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("tree_leaf")
+                    .bind(&expander, "hide-expander", Widget::NONE);
+
                 this.add_gestures(item);
             }
         ));
 
+        name_col_factory.connect_bind(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            if let Some(expander) = item.child().and_downcast::<gtk::TreeExpander>() {
+                expander.set_list_row(item.item().and_downcast_ref::<TreeListRow>());
+            }
+        });
+
+        name_col_factory.connect_unbind(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            if let Some(expander) = item.child().and_downcast::<gtk::TreeExpander>() {
+                expander.set_list_row(None);
+            }
+        });
+
         name_col_factory.connect_teardown(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
-            item.set_child(None::<&ResProcessNameCell>);
+            item.set_child(None::<&gtk::TreeExpander>);
         });
 
         let name_col_sorter = StringSorter::builder()
@@ -982,6 +1421,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("pid")
                     .bind(&row, "text", Widget::NONE);
 
@@ -1036,6 +1476,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("user")
                     .bind(&row, "text", Widget::NONE);
 
@@ -1091,7 +1532,8 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
-                    .chain_property::<ProcessEntry>("memory_usage")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("tree_memory_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
                         convert_storage(memory_usage as f64, false)
                     }))
@@ -1111,7 +1553,7 @@ impl ResProcesses {
             .expression(gtk::PropertyExpression::new(
                 ProcessEntry::static_type(),
                 None::<&gtk::Expression>,
-                "memory_usage",
+                "tree_memory_usage",
             ))
             .build();
 
@@ -1149,7 +1591,8 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
-                    .chain_property::<ProcessEntry>("cpu_usage")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("tree_cpu_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
                         let mut percentage = cpu_usage * 100.0;
                         if !SETTINGS.normalize_cpu_usage() {
@@ -1174,7 +1617,7 @@ impl ResProcesses {
             .expression(gtk::PropertyExpression::new(
                 ProcessEntry::static_type(),
                 None::<&gtk::Expression>,
-                "cpu_usage",
+                "tree_cpu_usage",
             ))
             .build();
 
@@ -1214,6 +1657,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("read_speed")
                     .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
                         if read_speed == -1.0 {
@@ -1280,6 +1724,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("read_total")
                     .chain_closure::<String>(closure!(|_: Option<Object>, read_total: i64| {
                         if read_total == -1 {
@@ -1346,6 +1791,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("write_speed")
                     .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
                         if write_speed == -1.0 {
@@ -1412,6 +1858,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("write_total")
                     .chain_closure::<String>(closure!(|_: Option<Object>, write_total: i64| {
                         if write_total == -1 {
@@ -1456,170 +1903,634 @@ impl ResProcesses {
         write_total_col
     }
 
-    fn add_gpu_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
-        let gpu_col_factory = gtk::SignalListItemFactory::new();
+    fn add_char_read_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let char_read_speed_col_factory = gtk::SignalListItemFactory::new();
 
-        let gpu_col = gtk::ColumnViewColumn::new(Some(&i18n("GPU")), Some(gpu_col_factory.clone()));
+        let char_read_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Character Read")),
+            Some(char_read_speed_col_factory.clone()),
+        );
 
-        gpu_col.set_resizable(true);
+        char_read_speed_col.set_resizable(true);
 
-        gpu_col_factory.connect_setup(clone!(
+        char_read_speed_col_factory.connect_setup(clone!(
             #[weak(rename_to = this)]
             self,
             move |_factory, item| {
                 let item = item.downcast_ref::<gtk::ListItem>().unwrap();
 
                 let row = gtk::Inscription::new(None);
-                row.set_min_chars(7);
+                row.set_min_chars(11);
 
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
-                    .chain_property::<ProcessEntry>("gpu_usage")
-                    .chain_closure::<String>(closure!(|_: Option<Object>, gpu_usage: f32| {
-                        format!("{:.1} %", gpu_usage * 100.0)
-                    }))
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("read_chars_speed")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, read_chars_speed: f64| {
+                            if read_chars_speed == -1.0 {
+                                i18n("N/A")
+                            } else {
+                                convert_speed(read_chars_speed, false)
+                            }
+                        }
+                    ))
                     .bind(&row, "text", Widget::NONE);
 
                 this.add_gestures(item);
             }
         ));
 
-        gpu_col_factory.connect_teardown(move |_factory, item| {
+        char_read_speed_col_factory.connect_teardown(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             item.set_child(None::<&gtk::Inscription>);
         });
 
-        let gpu_col_sorter = NumericSorter::builder()
+        let char_read_speed_col_sorter = NumericSorter::builder()
             .sort_order(SortType::Ascending)
             .expression(gtk::PropertyExpression::new(
                 ProcessEntry::static_type(),
                 None::<&gtk::Expression>,
-                "gpu_usage",
+                "read_chars_speed",
             ))
             .build();
 
-        gpu_col.set_sorter(Some(&gpu_col_sorter));
-        gpu_col.set_visible(SETTINGS.processes_show_gpu());
+        char_read_speed_col.set_sorter(Some(&char_read_speed_col_sorter));
+        char_read_speed_col.set_visible(SETTINGS.processes_show_char_read_speed());
 
-        column_view.append_column(&gpu_col);
+        column_view.append_column(&char_read_speed_col);
 
-        SETTINGS.connect_processes_show_gpu(clone!(
+        SETTINGS.connect_processes_show_char_read_speed(clone!(
             #[weak]
-            gpu_col,
-            move |visible| gpu_col.set_visible(visible)
+            char_read_speed_col,
+            move |visible| char_read_speed_col.set_visible(visible)
         ));
 
-        gpu_col
+        char_read_speed_col
     }
 
-    fn add_encoder_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
-        let encoder_col_factory = gtk::SignalListItemFactory::new();
+    fn add_char_read_total_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let char_read_total_col_factory = gtk::SignalListItemFactory::new();
 
-        let encoder_col = gtk::ColumnViewColumn::new(
-            Some(&i18n("Video Encoder")),
-            Some(encoder_col_factory.clone()),
+        let char_read_total_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Character Read Total")),
+            Some(char_read_total_col_factory.clone()),
         );
 
-        encoder_col.set_resizable(true);
+        char_read_total_col.set_resizable(true);
 
-        encoder_col_factory.connect_setup(clone!(
+        char_read_total_col_factory.connect_setup(clone!(
             #[weak(rename_to = this)]
             self,
             move |_factory, item| {
                 let item = item.downcast_ref::<gtk::ListItem>().unwrap();
 
                 let row = gtk::Inscription::new(None);
-                row.set_min_chars(7);
+                row.set_min_chars(9);
 
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
-                    .chain_property::<ProcessEntry>("enc_usage")
-                    .chain_closure::<String>(closure!(|_: Option<Object>, enc_usage: f32| {
-                        format!("{:.1} %", enc_usage * 100.0)
-                    }))
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("read_chars_total")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, read_chars_total: i64| {
+                            if read_chars_total == -1 {
+                                i18n("N/A")
+                            } else {
+                                convert_storage(read_chars_total as f64, false)
+                            }
+                        }
+                    ))
                     .bind(&row, "text", Widget::NONE);
 
                 this.add_gestures(item);
             }
         ));
 
-        encoder_col_factory.connect_teardown(move |_factory, item| {
+        char_read_total_col_factory.connect_teardown(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             item.set_child(None::<&gtk::Inscription>);
         });
 
-        let encoder_col_sorter = NumericSorter::builder()
+        let char_read_total_col_sorter = NumericSorter::builder()
             .sort_order(SortType::Ascending)
             .expression(gtk::PropertyExpression::new(
                 ProcessEntry::static_type(),
                 None::<&gtk::Expression>,
-                "enc_usage",
+                "read_chars_total",
             ))
             .build();
 
-        encoder_col.set_sorter(Some(&encoder_col_sorter));
-        encoder_col.set_visible(SETTINGS.processes_show_encoder());
+        char_read_total_col.set_sorter(Some(&char_read_total_col_sorter));
+        char_read_total_col.set_visible(SETTINGS.processes_show_char_read_total());
 
-        column_view.append_column(&encoder_col);
+        column_view.append_column(&char_read_total_col);
 
-        SETTINGS.connect_processes_show_encoder(clone!(
+        SETTINGS.connect_processes_show_char_read_total(clone!(
             #[weak]
-            encoder_col,
-            move |visible| encoder_col.set_visible(visible)
+            char_read_total_col,
+            move |visible| char_read_total_col.set_visible(visible)
         ));
 
-        encoder_col
+        char_read_total_col
     }
 
-    fn add_decoder_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
-        let decoder_col_factory = gtk::SignalListItemFactory::new();
+    fn add_char_write_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let char_write_speed_col_factory = gtk::SignalListItemFactory::new();
 
-        let decoder_col = gtk::ColumnViewColumn::new(
-            Some(&i18n("Video Decoder")),
-            Some(decoder_col_factory.clone()),
+        let char_write_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Character Write")),
+            Some(char_write_speed_col_factory.clone()),
         );
 
-        decoder_col.set_resizable(true);
+        char_write_speed_col.set_resizable(true);
 
-        decoder_col_factory.connect_setup(clone!(
+        char_write_speed_col_factory.connect_setup(clone!(
             #[weak(rename_to = this)]
             self,
             move |_factory, item| {
                 let item = item.downcast_ref::<gtk::ListItem>().unwrap();
 
                 let row = gtk::Inscription::new(None);
-                row.set_min_chars(7);
+                row.set_min_chars(11);
 
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
-                    .chain_property::<ProcessEntry>("dec_usage")
-                    .chain_closure::<String>(closure!(|_: Option<Object>, dec_usage: f32| {
-                        format!("{:.1} %", dec_usage * 100.0)
-                    }))
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("write_chars_speed")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, write_chars_speed: f64| {
+                            if write_chars_speed == -1.0 {
+                                i18n("N/A")
+                            } else {
+                                convert_speed(write_chars_speed, false)
+                            }
+                        }
+                    ))
                     .bind(&row, "text", Widget::NONE);
 
                 this.add_gestures(item);
             }
         ));
 
-        decoder_col_factory.connect_teardown(move |_factory, item| {
+        char_write_speed_col_factory.connect_teardown(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             item.set_child(None::<&gtk::Inscription>);
         });
 
-        let decoder_col_sorter = NumericSorter::builder()
+        let char_write_speed_col_sorter = NumericSorter::builder()
             .sort_order(SortType::Ascending)
             .expression(gtk::PropertyExpression::new(
                 ProcessEntry::static_type(),
                 None::<&gtk::Expression>,
-                "dec_usage",
+                "write_chars_speed",
             ))
             .build();
 
-        decoder_col.set_sorter(Some(&decoder_col_sorter));
+        char_write_speed_col.set_sorter(Some(&char_write_speed_col_sorter));
+        char_write_speed_col.set_visible(SETTINGS.processes_show_char_write_speed());
+
+        column_view.append_column(&char_write_speed_col);
+
+        SETTINGS.connect_processes_show_char_write_speed(clone!(
+            #[weak]
+            char_write_speed_col,
+            move |visible| char_write_speed_col.set_visible(visible)
+        ));
+
+        char_write_speed_col
+    }
+
+    fn add_char_write_total_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let char_write_total_col_factory = gtk::SignalListItemFactory::new();
+
+        let char_write_total_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Character Write Total")),
+            Some(char_write_total_col_factory.clone()),
+        );
+
+        char_write_total_col.set_resizable(true);
+
+        char_write_total_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("write_chars_total")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, write_chars_total: i64| {
+                            if write_chars_total == -1 {
+                                i18n("N/A")
+                            } else {
+                                convert_storage(write_chars_total as f64, false)
+                            }
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        char_write_total_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let char_write_total_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_chars_total",
+            ))
+            .build();
+
+        char_write_total_col.set_sorter(Some(&char_write_total_col_sorter));
+        char_write_total_col.set_visible(SETTINGS.processes_show_char_write_total());
+
+        column_view.append_column(&char_write_total_col);
+
+        SETTINGS.connect_processes_show_char_write_total(clone!(
+            #[weak]
+            char_write_total_col,
+            move |visible| char_write_total_col.set_visible(visible)
+        ));
+
+        char_write_total_col
+    }
+
+    fn add_read_syscalls_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let read_syscalls_col_factory = gtk::SignalListItemFactory::new();
+
+        let read_syscalls_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Read Syscalls")),
+            Some(read_syscalls_col_factory.clone()),
+        );
+
+        read_syscalls_col.set_resizable(true);
+
+        read_syscalls_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("read_syscall_rate")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, read_syscall_rate: f64| {
+                            rate_label(read_syscall_rate)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        read_syscalls_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let read_syscalls_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_syscall_rate",
+            ))
+            .build();
+
+        read_syscalls_col.set_sorter(Some(&read_syscalls_col_sorter));
+        read_syscalls_col.set_visible(SETTINGS.processes_show_read_syscalls());
+
+        column_view.append_column(&read_syscalls_col);
+
+        SETTINGS.connect_processes_show_read_syscalls(clone!(
+            #[weak]
+            read_syscalls_col,
+            move |visible| read_syscalls_col.set_visible(visible)
+        ));
+
+        read_syscalls_col
+    }
+
+    fn add_write_syscalls_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let write_syscalls_col_factory = gtk::SignalListItemFactory::new();
+
+        let write_syscalls_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Write Syscalls")),
+            Some(write_syscalls_col_factory.clone()),
+        );
+
+        write_syscalls_col.set_resizable(true);
+
+        write_syscalls_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("write_syscall_rate")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, write_syscall_rate: f64| {
+                            rate_label(write_syscall_rate)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        write_syscalls_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let write_syscalls_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_syscall_rate",
+            ))
+            .build();
+
+        write_syscalls_col.set_sorter(Some(&write_syscalls_col_sorter));
+        write_syscalls_col.set_visible(SETTINGS.processes_show_write_syscalls());
+
+        column_view.append_column(&write_syscalls_col);
+
+        SETTINGS.connect_processes_show_write_syscalls(clone!(
+            #[weak]
+            write_syscalls_col,
+            move |visible| write_syscalls_col.set_visible(visible)
+        ));
+
+        write_syscalls_col
+    }
+
+    fn add_cancelled_write_total_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let cancelled_write_total_col_factory = gtk::SignalListItemFactory::new();
+
+        let cancelled_write_total_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Cancelled Write Total")),
+            Some(cancelled_write_total_col_factory.clone()),
+        );
+
+        cancelled_write_total_col.set_resizable(true);
+
+        cancelled_write_total_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("cancelled_write_total")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, cancelled_write_total: i64| {
+                            if cancelled_write_total == -1 {
+                                i18n("N/A")
+                            } else {
+                                convert_storage(cancelled_write_total as f64, false)
+                            }
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        cancelled_write_total_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let cancelled_write_total_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "cancelled_write_total",
+            ))
+            .build();
+
+        cancelled_write_total_col.set_sorter(Some(&cancelled_write_total_col_sorter));
+        cancelled_write_total_col.set_visible(SETTINGS.processes_show_cancelled_write_total());
+
+        column_view.append_column(&cancelled_write_total_col);
+
+        SETTINGS.connect_processes_show_cancelled_write_total(clone!(
+            #[weak]
+            cancelled_write_total_col,
+            move |visible| cancelled_write_total_col.set_visible(visible)
+        ));
+
+        cancelled_write_total_col
+    }
+
+    fn add_gpu_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let gpu_col_factory = gtk::SignalListItemFactory::new();
+
+        let gpu_col = gtk::ColumnViewColumn::new(Some(&i18n("GPU")), Some(gpu_col_factory.clone()));
+
+        gpu_col.set_resizable(true);
+
+        gpu_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("gpu_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, gpu_usage: f32| {
+                        format!("{:.1} %", gpu_usage * 100.0)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        gpu_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let gpu_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "gpu_usage",
+            ))
+            .build();
+
+        gpu_col.set_sorter(Some(&gpu_col_sorter));
+        gpu_col.set_visible(SETTINGS.processes_show_gpu());
+
+        column_view.append_column(&gpu_col);
+
+        SETTINGS.connect_processes_show_gpu(clone!(
+            #[weak]
+            gpu_col,
+            move |visible| gpu_col.set_visible(visible)
+        ));
+
+        gpu_col
+    }
+
+    fn add_encoder_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let encoder_col_factory = gtk::SignalListItemFactory::new();
+
+        let encoder_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Video Encoder")),
+            Some(encoder_col_factory.clone()),
+        );
+
+        encoder_col.set_resizable(true);
+
+        encoder_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("enc_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, enc_usage: f32| {
+                        format!("{:.1} %", enc_usage * 100.0)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        encoder_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let encoder_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "enc_usage",
+            ))
+            .build();
+
+        encoder_col.set_sorter(Some(&encoder_col_sorter));
+        encoder_col.set_visible(SETTINGS.processes_show_encoder());
+
+        column_view.append_column(&encoder_col);
+
+        SETTINGS.connect_processes_show_encoder(clone!(
+            #[weak]
+            encoder_col,
+            move |visible| encoder_col.set_visible(visible)
+        ));
+
+        encoder_col
+    }
+
+    fn add_decoder_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let decoder_col_factory = gtk::SignalListItemFactory::new();
+
+        let decoder_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Video Decoder")),
+            Some(decoder_col_factory.clone()),
+        );
+
+        decoder_col.set_resizable(true);
+
+        decoder_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("dec_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, dec_usage: f32| {
+                        format!("{:.1} %", dec_usage * 100.0)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        decoder_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let decoder_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "dec_usage",
+            ))
+            .build();
+
+        decoder_col.set_sorter(Some(&decoder_col_sorter));
         decoder_col.set_visible(SETTINGS.processes_show_decoder());
 
         column_view.append_column(&decoder_col);
@@ -1654,6 +2565,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("gpu_mem_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, gpu_mem: u64| {
                         convert_storage(gpu_mem as f64, false)
@@ -1713,6 +2625,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("total_cpu_time")
                     .chain_closure::<String>(closure!(|_: Option<Object>, total_cpu_time: f64| {
                         format_time(total_cpu_time)
@@ -1772,6 +2685,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("user_cpu_time")
                     .chain_closure::<String>(closure!(|_: Option<Object>, user_cpu_time: f64| {
                         format_time(user_cpu_time)
@@ -1831,6 +2745,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("system_cpu_time")
                     .chain_closure::<String>(closure!(|_: Option<Object>, system_cpu_time: f64| {
                         format_time(system_cpu_time)
@@ -1869,6 +2784,128 @@ impl ResProcesses {
         system_cpu_time_col
     }
 
+    fn add_major_fault_rate_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let major_fault_rate_col_factory = gtk::SignalListItemFactory::new();
+
+        let major_fault_rate_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Major Page Faults")),
+            Some(major_fault_rate_col_factory.clone()),
+        );
+
+        major_fault_rate_col.set_resizable(true);
+
+        major_fault_rate_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("major_fault_rate")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, major_fault_rate: f64| { rate_label(major_fault_rate) }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        major_fault_rate_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let major_fault_rate_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "major_fault_rate",
+            ))
+            .build();
+
+        major_fault_rate_col.set_sorter(Some(&major_fault_rate_col_sorter));
+        major_fault_rate_col.set_visible(SETTINGS.processes_show_major_fault_rate());
+
+        column_view.append_column(&major_fault_rate_col);
+
+        SETTINGS.connect_processes_show_major_fault_rate(clone!(
+            #[weak]
+            major_fault_rate_col,
+            move |visible| major_fault_rate_col.set_visible(visible)
+        ));
+
+        major_fault_rate_col
+    }
+
+    fn add_context_switch_rate_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let context_switch_rate_col_factory = gtk::SignalListItemFactory::new();
+
+        let context_switch_rate_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Context Switches")),
+            Some(context_switch_rate_col_factory.clone()),
+        );
+
+        context_switch_rate_col.set_resizable(true);
+
+        context_switch_rate_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("context_switch_rate")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, context_switch_rate: f64| {
+                            rate_label(context_switch_rate)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        context_switch_rate_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let context_switch_rate_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "context_switch_rate",
+            ))
+            .build();
+
+        context_switch_rate_col.set_sorter(Some(&context_switch_rate_col_sorter));
+        context_switch_rate_col.set_visible(SETTINGS.processes_show_context_switch_rate());
+
+        column_view.append_column(&context_switch_rate_col);
+
+        SETTINGS.connect_processes_show_context_switch_rate(clone!(
+            #[weak]
+            context_switch_rate_col,
+            move |visible| context_switch_rate_col.set_visible(visible)
+        ));
+
+        context_switch_rate_col
+    }
+
     fn add_priority_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
         let priority_col_factory = gtk::SignalListItemFactory::new();
 
@@ -1888,6 +2925,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("niceness")
                     .chain_closure::<String>(closure!(|_: Option<Object>, niceness: i8| {
                         if SETTINGS.detailed_priority() {
@@ -1956,6 +2994,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("swap_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, swap_usage: u64| {
                         convert_storage(swap_usage as f64, false)
@@ -2016,6 +3055,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
                     .chain_property::<ProcessEntry>("combined_memory_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, swap_usage: u64| {
                         convert_storage(swap_usage as f64, false)
@@ -2053,6 +3093,284 @@ impl ResProcesses {
 
         combined_memory_col
     }
+
+    fn add_state_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let state_col_factory = gtk::SignalListItemFactory::new();
+
+        let state_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("State")), Some(state_col_factory.clone()));
+
+        state_col.set_resizable(true);
+
+        state_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("state")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        state_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let state_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "state",
+            ))
+            .build();
+
+        state_col.set_sorter(Some(&state_col_sorter));
+        state_col.set_visible(SETTINGS.processes_show_state());
+
+        column_view.append_column(&state_col);
+
+        SETTINGS.connect_processes_show_state(clone!(
+            #[weak]
+            state_col,
+            move |visible| state_col.set_visible(visible)
+        ));
+
+        state_col
+    }
+
+    fn add_io_priority_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let io_priority_col_factory = gtk::SignalListItemFactory::new();
+
+        let io_priority_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("I/O Priority")),
+            Some(io_priority_col_factory.clone()),
+        );
+
+        io_priority_col.set_resizable(true);
+
+        io_priority_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("io_priority")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        io_priority_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let io_priority_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "io_priority",
+            ))
+            .build();
+
+        io_priority_col.set_sorter(Some(&io_priority_col_sorter));
+        io_priority_col.set_visible(SETTINGS.processes_show_io_priority());
+
+        column_view.append_column(&io_priority_col);
+
+        SETTINGS.connect_processes_show_io_priority(clone!(
+            #[weak]
+            io_priority_col,
+            move |visible| io_priority_col.set_visible(visible)
+        ));
+
+        io_priority_col
+    }
+
+    fn add_scheduling_policy_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let scheduling_policy_col_factory = gtk::SignalListItemFactory::new();
+
+        let scheduling_policy_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Scheduling Policy")),
+            Some(scheduling_policy_col_factory.clone()),
+        );
+
+        scheduling_policy_col.set_resizable(true);
+
+        scheduling_policy_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("scheduling_policy")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        scheduling_policy_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let scheduling_policy_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "scheduling_policy",
+            ))
+            .build();
+
+        scheduling_policy_col.set_sorter(Some(&scheduling_policy_col_sorter));
+        scheduling_policy_col.set_visible(SETTINGS.processes_show_scheduling_policy());
+
+        column_view.append_column(&scheduling_policy_col);
+
+        SETTINGS.connect_processes_show_scheduling_policy(clone!(
+            #[weak]
+            scheduling_policy_col,
+            move |visible| scheduling_policy_col.set_visible(visible)
+        ));
+
+        scheduling_policy_col
+    }
+
+    fn add_oom_score_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let oom_score_col_factory = gtk::SignalListItemFactory::new();
+
+        let oom_score_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("OOM Score")),
+            Some(oom_score_col_factory.clone()),
+        );
+
+        oom_score_col.set_resizable(true);
+
+        oom_score_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(5);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("oom_score")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, oom_score: i64| {
+                        if oom_score == -1 {
+                            i18n("N/A")
+                        } else {
+                            oom_score.to_string()
+                        }
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        oom_score_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let oom_score_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "oom_score",
+            ))
+            .build();
+
+        oom_score_col.set_sorter(Some(&oom_score_col_sorter));
+        oom_score_col.set_visible(SETTINGS.processes_show_oom_score());
+
+        column_view.append_column(&oom_score_col);
+
+        SETTINGS.connect_processes_show_oom_score(clone!(
+            #[weak]
+            oom_score_col,
+            move |visible| oom_score_col.set_visible(visible)
+        ));
+
+        oom_score_col
+    }
+}
+
+/// Unwraps the `ProcessEntry` out of a `TreeListRow` of the processes column view
+fn process_entry_from_row(object: Object) -> Option<ProcessEntry> {
+    object
+        .downcast::<TreeListRow>()
+        .ok()
+        .and_then(|row| row.item())
+        .and_downcast::<ProcessEntry>()
+}
+
+/// Sums up the processor and memory usage of `entry` and all of its descendants, memoizing the results of every
+/// visited process in `subtree_usages`
+fn subtree_usage(
+    entry: &ProcessEntry,
+    children: &HashMap<libc::pid_t, Vec<ProcessEntry>>,
+    subtree_usages: &mut HashMap<libc::pid_t, (f32, u64)>,
+) -> (f32, u64) {
+    if let Some(usage) = subtree_usages.get(&entry.pid()) {
+        return *usage;
+    }
+
+    let mut usage = (entry.cpu_usage(), entry.memory_usage());
+
+    // guards against cycles caused by PIDs that have been reused in the meantime
+    subtree_usages.insert(entry.pid(), usage);
+
+    for child in children.get(&entry.pid()).into_iter().flatten() {
+        let (child_cpu_usage, child_memory_usage) = subtree_usage(child, children, subtree_usages);
+        usage.0 += child_cpu_usage;
+        usage.1 = usage.1.saturating_add(child_memory_usage);
+    }
+
+    subtree_usages.insert(entry.pid(), usage);
+
+    usage
+}
+
+/// Parses the target of the "Send Signal" menu items, e.g. `HUP`
+fn signal_action_from_parameter(parameter: Option<&glib::Variant>) -> Option<ProcessAction> {
+    parameter
+        .and_then(glib::Variant::get::<String>)
+        .and_then(|name| format!("SIG{name}").parse::<Signal>().ok())
+        .map(ProcessAction::Signal)
 }
 
 fn get_action_name(action: ProcessAction, name: &str) -> String {
@@ -2061,6 +3379,7 @@ fn get_action_name(action: ProcessAction, name: &str) -> String {
         ProcessAction::STOP => i18n_f("Halt {}?", &[name]),
         ProcessAction::KILL => i18n_f("Kill {}?", &[name]),
         ProcessAction::CONT => i18n_f("Continue {}?", &[name]),
+        ProcessAction::Signal(signal) => i18n_f("Send {} to {}?", &[signal.as_str(), name]),
     }
 }
 
@@ -2090,6 +3409,12 @@ fn get_action_name_multiple(action: ProcessAction, count: usize) -> String {
             count as u32,
             &[&count.to_string()],
         ),
+        ProcessAction::Signal(signal) => ni18n_f(
+            "Send {} to process?",
+            "Send {} to {} processes?",
+            count as u32,
+            &[signal.as_str(), &count.to_string()],
+        ),
     }
 }
 
@@ -2103,6 +3428,9 @@ fn get_action_warning(action: ProcessAction) -> String {
             "Killing a process can come with serious risks such as losing data and security implications. Use with caution.",
         ),
         ProcessAction::CONT => String::new(),
+        ProcessAction::Signal(_) => i18n(
+            "Depending on the signal and how the process handles it, this might end the process or cause unexpected behavior.",
+        ),
     }
 }
 
@@ -2112,5 +3440,6 @@ fn get_action_description(action: ProcessAction) -> String {
         ProcessAction::STOP => i18n("Halt Process"),
         ProcessAction::KILL => i18n("Kill Process"),
         ProcessAction::CONT => i18n("Continue Process"),
+        ProcessAction::Signal(_) => i18n("Send Signal"),
     }
 }
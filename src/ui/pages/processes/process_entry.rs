@@ -1,13 +1,24 @@
+use std::collections::{HashMap, HashSet};
+
 use gtk::{
+    gio,
     glib::{self, GString},
+    prelude::*,
     subclass::prelude::ObjectSubclassIsExt,
 };
 use log::trace;
-use process_data::Containerization;
+use process_data::{
+    OomScoreAdj, ProcessState, ThreadData,
+    namespaces::Namespaces,
+    pidfd::ProcessIdentity,
+    scheduling::{IoPriority, IoPriorityClass, SchedulingPolicy},
+    security::SecurityContext,
+};
 
 use crate::{
-    i18n::i18n,
-    utils::{TICK_RATE, process::Process},
+    i18n::{i18n, i18n_f, pi18n},
+    ui::pages::containerization_label,
+    utils::{TICK_RATE, process::Process, settings::MemoryMetric},
 };
 
 mod imp {
@@ -40,12 +51,33 @@ mod imp {
         #[property(get, set)]
         pid: Cell<i32>,
 
+        /// In clock ticks after boot, see `ProcessIdentity`
+        #[property(get, set)]
+        start_time: Cell<u64>,
+
+        #[property(get, set)]
+        parent_pid: Cell<i32>,
+
         #[property(get, set)]
         cpu_usage: Cell<f32>,
 
         #[property(get, set)]
         memory_usage: Cell<u64>,
 
+        /// The processor usage of this process and all of its descendants if the tree view shows combined usages,
+        /// otherwise the same as `cpu_usage`
+        #[property(get, set)]
+        tree_cpu_usage: Cell<f32>,
+
+        /// The memory usage of this process and all of its descendants if the tree view shows combined usages,
+        /// otherwise the same as `memory_usage`
+        #[property(get, set)]
+        tree_memory_usage: Cell<u64>,
+
+        /// Whether the tree view is active and this process has no children, used to hide its expander
+        #[property(get, set)]
+        tree_leaf: Cell<bool>,
+
         #[property(get, set)]
         swap_usage: Cell<u64>,
 
@@ -64,6 +96,27 @@ mod imp {
         #[property(get, set)]
         write_total: Cell<i64>, // will be -1 if write data is not available
 
+        #[property(get, set)]
+        read_chars_speed: Cell<f64>, // will be -1.0 if read data is not available
+
+        #[property(get, set)]
+        read_chars_total: Cell<i64>, // will be -1 if read data is not available
+
+        #[property(get, set)]
+        write_chars_speed: Cell<f64>, // will be -1.0 if write data is not available
+
+        #[property(get, set)]
+        write_chars_total: Cell<i64>, // will be -1 if write data is not available
+
+        #[property(get, set)]
+        read_syscall_rate: Cell<f64>, // will be -1.0 if syscall data is not available
+
+        #[property(get, set)]
+        write_syscall_rate: Cell<f64>, // will be -1.0 if syscall data is not available
+
+        #[property(get, set)]
+        cancelled_write_total: Cell<i64>, // will be -1 if write data is not available
+
         #[property(get, set)]
         gpu_usage: Cell<f32>,
 
@@ -85,24 +138,73 @@ mod imp {
         #[property(get, set)]
         system_cpu_time: Cell<f64>,
 
+        /// CPU time of waited-for children, in seconds
+        #[property(get, set)]
+        children_cpu_time: Cell<f64>,
+
+        #[property(get, set)]
+        num_threads: Cell<u64>,
+
+        #[property(get, set)]
+        major_fault_rate: Cell<f64>, // will be -1.0 if fault data is not available
+
+        #[property(get, set)]
+        context_switch_rate: Cell<f64>, // will be -1.0 if context switch data is not available
+
         #[property(get, set)]
         niceness: Cell<i8>,
 
+        #[property(get, set)]
+        oom_score: Cell<i64>, // will be -1 if the OOM score is not available
+
+        #[property(get = Self::state, set = Self::set_state)]
+        state: Cell<glib::GString>,
+
+        #[property(get = Self::io_priority, set = Self::set_io_priority)]
+        io_priority: Cell<glib::GString>,
+
+        #[property(get = Self::scheduling_policy, set = Self::set_scheduling_policy)]
+        scheduling_policy: Cell<glib::GString>,
+
         #[property(get = Self::cgroup, set = Self::set_cgroup)]
         cgroup: Cell<Option<glib::GString>>,
 
+        #[property(get = Self::cgroup_path, set = Self::set_cgroup_path)]
+        cgroup_path: Cell<Option<glib::GString>>,
+
         #[property(get = Self::containerization, set = Self::set_containerization)]
         containerization: Cell<glib::GString>,
 
         #[property(get = Self::running_since, set = Self::set_running_since)]
         running_since: Cell<Option<glib::GString>>,
 
+        /// Whether this entry represents a thread of another `ProcessEntry` instead of a process
+        #[property(get, set)]
+        is_thread: Cell<bool>,
+
         // TODO: Make this properly dynamic, don't use a variable that's never read
         #[property(get = Self::symbolic)]
         #[allow(dead_code)]
         symbolic: Cell<bool>,
 
         pub affinity: RefCell<Vec<bool>>,
+
+        pub namespaces: Cell<Namespaces>,
+
+        pub security: RefCell<SecurityContext>,
+
+        pub process_state: Cell<ProcessState>,
+
+        pub io_priority_value: Cell<Option<IoPriority>>,
+
+        pub scheduling_policy_value: Cell<Option<SchedulingPolicy>>,
+
+        pub oom_score_adj: Cell<Option<OomScoreAdj>>,
+
+        pub threads: gio::ListStore,
+
+        /// Only kept up to date while the tree view is active
+        pub children: gio::ListStore,
     }
 
     impl Default for ProcessEntry {
@@ -113,14 +215,26 @@ mod imp {
                 user: Cell::new(glib::GString::default()),
                 icon: Cell::new(ThemedIcon::new("generic-process").into()),
                 pid: Cell::new(0),
+                start_time: Cell::new(0),
+                parent_pid: Cell::new(0),
                 cpu_usage: Cell::new(0.0),
                 memory_usage: Cell::new(0),
+                tree_cpu_usage: Cell::new(0.0),
+                tree_memory_usage: Cell::new(0),
+                tree_leaf: Cell::new(false),
                 swap_usage: Cell::new(0),
                 combined_memory_usage: Cell::new(0),
                 read_speed: Cell::new(0.0),
                 read_total: Cell::new(0),
                 write_speed: Cell::new(0.0),
                 write_total: Cell::new(0),
+                read_chars_speed: Cell::new(0.0),
+                read_chars_total: Cell::new(0),
+                write_chars_speed: Cell::new(0.0),
+                write_chars_total: Cell::new(0),
+                read_syscall_rate: Cell::new(0.0),
+                write_syscall_rate: Cell::new(0.0),
+                cancelled_write_total: Cell::new(0),
                 gpu_usage: Cell::new(0.0),
                 enc_usage: Cell::new(0.0),
                 dec_usage: Cell::new(0.0),
@@ -128,19 +242,45 @@ mod imp {
                 total_cpu_time: Cell::new(0.0),
                 user_cpu_time: Cell::new(0.0),
                 system_cpu_time: Cell::new(0.0),
+                children_cpu_time: Cell::new(0.0),
+                num_threads: Cell::new(0),
+                major_fault_rate: Cell::new(0.0),
+                context_switch_rate: Cell::new(0.0),
                 niceness: Cell::new(0),
+                oom_score: Cell::new(0),
+                state: Cell::new(glib::GString::default()),
+                io_priority: Cell::new(glib::GString::default()),
+                scheduling_policy: Cell::new(glib::GString::default()),
                 cgroup: Cell::new(None),
+                cgroup_path: Cell::new(None),
                 containerization: Cell::new(glib::GString::default()),
                 running_since: Cell::new(None),
+                is_thread: Cell::new(false),
                 symbolic: Cell::new(false),
                 affinity: Default::default(),
+                namespaces: Default::default(),
+                security: Default::default(),
+                process_state: Cell::new(ProcessState::default()),
+                io_priority_value: Cell::new(None),
+                scheduling_policy_value: Cell::new(None),
+                oom_score_adj: Cell::new(None),
+                threads: gio::ListStore::new::<super::ProcessEntry>(),
+                children: gio::ListStore::new::<super::ProcessEntry>(),
             }
         }
     }
 
     impl ProcessEntry {
-        gstring_getter_setter!(user, commandline, name, containerization);
-        gstring_option_getter_setter!(cgroup, running_since);
+        gstring_getter_setter!(
+            user,
+            commandline,
+            name,
+            containerization,
+            state,
+            io_priority,
+            scheduling_policy
+        );
+        gstring_option_getter_setter!(cgroup, cgroup_path, running_since);
 
         pub fn icon(&self) -> Icon {
             let icon = self.icon.replace(ThemedIcon::new("generic-process").into());
@@ -204,14 +344,10 @@ glib::wrapper! {
 }
 
 impl ProcessEntry {
-    pub fn new(process: &Process) -> Self {
+    pub fn new(process: &Process, memory_metric: MemoryMetric) -> Self {
         trace!("Creating ProcessEntry GObject ({})…", process.data.pid);
 
-        let containerization = match process.data.containerization {
-            Containerization::None => i18n("No"),
-            Containerization::Flatpak => i18n("Yes (Flatpak)"),
-            Containerization::Snap => i18n("Yes (Snap)"),
-        };
+        let containerization = containerization_label(&process.data.containerization);
 
         let this: Self = glib::Object::builder()
             .property("name", &process.display_name)
@@ -219,24 +355,41 @@ impl ProcessEntry {
             .property("user", &process.data.user)
             .property("icon", &process.icon)
             .property("pid", process.data.pid)
+            .property("start_time", process.data.starttime)
             .property("cgroup", process.data.cgroup.clone().map(GString::from))
+            .property(
+                "cgroup_path",
+                process.data.cgroup_path.clone().map(GString::from),
+            )
             .property("containerization", containerization)
             .property("running_since", process.running_since().ok())
             .build();
-        this.update(process);
+        this.update(process, memory_metric);
         this
     }
 
-    pub fn update(&self, process: &Process) {
+    /// Returns the PID together with the start time of this process, so that actions on it that are carried out later
+    /// can't hit another process that has been given the same PID in the meantime
+    pub fn identity(&self) -> ProcessIdentity {
+        ProcessIdentity {
+            pid: self.pid(),
+            start_time: self.start_time(),
+        }
+    }
+
+    pub fn update(&self, process: &Process, memory_metric: MemoryMetric) {
         trace!("Refreshing ProcessEntry ({})…", process.data.pid);
 
+        self.set_parent_pid(process.data.parent_pid);
         self.set_cpu_usage(process.cpu_time_ratio());
-        self.set_memory_usage(process.data.memory_usage as u64);
+        self.set_memory_usage(process.memory_usage(memory_metric) as u64);
+        // might be replaced by the combined usages of the subtree later on
+        self.set_tree_cpu_usage(self.cpu_usage());
+        self.set_tree_memory_usage(self.memory_usage());
         self.set_swap_usage(process.data.swap_usage as u64);
         self.set_combined_memory_usage(
             process
-                .data
-                .memory_usage
+                .memory_usage(memory_metric)
                 .saturating_add(process.data.swap_usage) as u64,
         );
         self.set_read_speed(process.read_speed().unwrap_or(-1.0));
@@ -253,6 +406,28 @@ impl ProcessEntry {
                 .write_bytes
                 .map_or(-1, |write_total| write_total as i64),
         );
+        self.set_read_chars_speed(process.read_chars_speed().unwrap_or(-1.0));
+        self.set_read_chars_total(
+            process
+                .data
+                .read_chars
+                .map_or(-1, |read_chars| read_chars as i64),
+        );
+        self.set_write_chars_speed(process.write_chars_speed().unwrap_or(-1.0));
+        self.set_write_chars_total(
+            process
+                .data
+                .write_chars
+                .map_or(-1, |write_chars| write_chars as i64),
+        );
+        self.set_read_syscall_rate(process.read_syscall_rate().unwrap_or(-1.0));
+        self.set_write_syscall_rate(process.write_syscall_rate().unwrap_or(-1.0));
+        self.set_cancelled_write_total(
+            process
+                .data
+                .cancelled_write_bytes
+                .map_or(-1, |cancelled_write_bytes| cancelled_write_bytes as i64),
+        );
         self.set_gpu_usage(process.gpu_usage());
         self.set_enc_usage(process.enc_usage());
         self.set_dec_usage(process.dec_usage());
@@ -260,11 +435,234 @@ impl ProcessEntry {
         self.set_user_cpu_time((process.data.user_cpu_time as f64) / (*TICK_RATE as f64));
         self.set_system_cpu_time((process.data.system_cpu_time as f64) / (*TICK_RATE as f64));
         self.set_total_cpu_time(self.user_cpu_time() + self.system_cpu_time());
+        self.set_children_cpu_time(
+            (process
+                .data
+                .children_user_cpu_time
+                .saturating_add(process.data.children_system_cpu_time) as f64)
+                / (*TICK_RATE as f64),
+        );
+        self.set_num_threads(process.data.num_threads);
+        self.set_major_fault_rate(process.major_fault_rate());
+        self.set_context_switch_rate(process.context_switch_rate().unwrap_or(-1.0));
         self.set_niceness(*process.data.niceness);
+        self.set_io_priority_value(process.data.io_priority);
+        self.set_scheduling_policy_value(process.data.scheduling_policy);
+        self.set_oom_score(process.data.oom_score.map_or(-1, i64::from));
+        self.imp().oom_score_adj.set(process.data.oom_score_adj);
+        self.set_process_state(process.data.state);
         (*self.imp().affinity.borrow_mut()).clone_from(&process.data.affinity);
+        self.imp().namespaces.set(process.data.namespaces);
+        (*self.imp().security.borrow_mut()).clone_from(&process.data.security);
+
+        self.update_threads(process);
+    }
+
+    fn new_thread(process: &Process, thread: &ThreadData) -> Self {
+        trace!(
+            "Creating ProcessEntry GObject for thread {} of {}…",
+            thread.tid, process.data.pid
+        );
+
+        let this: Self = glib::Object::builder()
+            .property("name", &thread.comm)
+            .property("commandline", process.data.commandline.replace('\0', " "))
+            .property("user", &process.data.user)
+            .property("icon", &process.icon)
+            .property("pid", thread.tid)
+            .property("is_thread", true)
+            .build();
+        this.update_thread(process, thread);
+        this
+    }
+
+    fn update_thread(&self, process: &Process, thread: &ThreadData) {
+        self.set_cpu_usage(process.thread_cpu_time_ratio(thread));
+        self.set_tree_cpu_usage(self.cpu_usage());
+        self.set_user_cpu_time((thread.user_cpu_time as f64) / (*TICK_RATE as f64));
+        self.set_system_cpu_time((thread.system_cpu_time as f64) / (*TICK_RATE as f64));
+        self.set_total_cpu_time(self.user_cpu_time() + self.system_cpu_time());
+        // we don't collect these per thread
+        self.set_major_fault_rate(-1.0);
+        self.set_context_switch_rate(-1.0);
+        self.set_read_chars_speed(-1.0);
+        self.set_read_chars_total(-1);
+        self.set_write_chars_speed(-1.0);
+        self.set_write_chars_total(-1);
+        self.set_read_syscall_rate(-1.0);
+        self.set_write_syscall_rate(-1.0);
+        self.set_cancelled_write_total(-1);
+        self.set_io_priority_value(None);
+        self.set_scheduling_policy_value(None);
+        // the OOM killer always kills whole processes
+        self.set_oom_score(-1);
+        self.set_niceness(*thread.niceness);
+        self.set_process_state(thread.state);
+    }
+
+    fn update_threads(&self, process: &Process) {
+        let threads = &self.imp().threads;
+
+        let threads_by_tid: HashMap<libc::pid_t, &ThreadData> = process
+            .data
+            .threads
+            .iter()
+            .map(|thread| (thread.tid, thread))
+            .collect();
+
+        let mut already_existing_tids = HashSet::new();
+
+        threads.retain(|object| {
+            let entry = object.downcast_ref::<ProcessEntry>().unwrap();
+            if let Some(thread) = threads_by_tid.get(&entry.pid()) {
+                entry.update_thread(process, thread);
+                already_existing_tids.insert(thread.tid);
+                true
+            } else {
+                false
+            }
+        });
+
+        let new_threads: Vec<ProcessEntry> = process
+            .data
+            .threads
+            .iter()
+            .filter(|thread| !already_existing_tids.contains(&thread.tid))
+            .map(|thread| ProcessEntry::new_thread(process, thread))
+            .collect();
+        threads.extend_from_slice(&new_threads);
+    }
+
+    pub fn process_state(&self) -> ProcessState {
+        self.imp().process_state.get()
+    }
+
+    fn set_process_state(&self, process_state: ProcessState) {
+        if self.imp().process_state.replace(process_state) != process_state
+            || self.state().is_empty()
+        {
+            self.set_state(state_label(process_state));
+        }
+    }
+
+    fn set_io_priority_value(&self, io_priority: Option<IoPriority>) {
+        if self.imp().io_priority_value.replace(io_priority) != io_priority
+            || self.io_priority().is_empty()
+        {
+            self.set_io_priority(io_priority_label(io_priority));
+        }
+    }
+
+    pub fn io_priority_value(&self) -> Option<IoPriority> {
+        self.imp().io_priority_value.get()
+    }
+
+    fn set_scheduling_policy_value(&self, scheduling_policy: Option<SchedulingPolicy>) {
+        if self
+            .imp()
+            .scheduling_policy_value
+            .replace(scheduling_policy)
+            != scheduling_policy
+            || self.scheduling_policy().is_empty()
+        {
+            self.set_scheduling_policy(scheduling_policy_label(scheduling_policy));
+        }
+    }
+
+    pub fn scheduling_policy_value(&self) -> Option<SchedulingPolicy> {
+        self.imp().scheduling_policy_value.get()
+    }
+
+    pub fn oom_score_adj(&self) -> Option<OomScoreAdj> {
+        self.imp().oom_score_adj.get()
+    }
+
+    /// Returns a model containing a `ProcessEntry` for each thread of this process
+    pub fn threads(&self) -> gio::ListStore {
+        self.imp().threads.clone()
+    }
+
+    /// Returns a model containing the `ProcessEntry` of each child process, see `set_children()`
+    pub fn children(&self) -> gio::ListStore {
+        self.imp().children.clone()
+    }
+
+    /// Replaces the child processes of this entry while keeping the ones that are still children in place
+    pub fn set_children(&self, children: &[ProcessEntry]) {
+        let store = &self.imp().children;
+
+        let pids: HashSet<libc::pid_t> = children.iter().map(ProcessEntry::pid).collect();
+
+        let mut already_existing_pids = HashSet::new();
+        store.retain(|object| {
+            let pid = object.downcast_ref::<ProcessEntry>().unwrap().pid();
+            already_existing_pids.insert(pid);
+            pids.contains(&pid)
+        });
+
+        let new_children: Vec<ProcessEntry> = children
+            .iter()
+            .filter(|child| !already_existing_pids.contains(&child.pid()))
+            .cloned()
+            .collect();
+        store.extend_from_slice(&new_children);
     }
 
     pub fn affinity(&self) -> Vec<bool> {
         self.imp().affinity.borrow().clone()
     }
+
+    pub fn namespaces(&self) -> Namespaces {
+        self.imp().namespaces.get()
+    }
+
+    pub fn security(&self) -> SecurityContext {
+        self.imp().security.borrow().clone()
+    }
+}
+
+fn state_label(process_state: ProcessState) -> String {
+    match process_state {
+        ProcessState::Running => pi18n("process state", "Running"),
+        ProcessState::Sleeping => pi18n("process state", "Sleeping"),
+        ProcessState::DiskSleep => pi18n("process state", "Waiting for Disk"),
+        ProcessState::Zombie => pi18n("process state", "Zombie"),
+        ProcessState::Stopped => pi18n("process state", "Stopped"),
+        ProcessState::TracingStop => pi18n("process state", "Traced"),
+        ProcessState::Dead => pi18n("process state", "Dead"),
+        ProcessState::Idle => pi18n("process state", "Idle"),
+        ProcessState::Unknown => i18n("N/A"),
+    }
+}
+
+fn io_priority_label(io_priority: Option<IoPriority>) -> String {
+    let Some(io_priority) = io_priority else {
+        return i18n("N/A");
+    };
+
+    match io_priority.class {
+        IoPriorityClass::None => pi18n("I/O priority", "Default"),
+        IoPriorityClass::RealTime => i18n_f("Real-Time ({})", &[&io_priority.level.to_string()]),
+        IoPriorityClass::BestEffort => {
+            i18n_f("Best Effort ({})", &[&io_priority.level.to_string()])
+        }
+        IoPriorityClass::Idle => pi18n("I/O priority", "Idle"),
+    }
+}
+
+fn scheduling_policy_label(scheduling_policy: Option<SchedulingPolicy>) -> String {
+    let Some(scheduling_policy) = scheduling_policy else {
+        return i18n("N/A");
+    };
+
+    match scheduling_policy {
+        SchedulingPolicy::Other => pi18n("scheduling policy", "Normal"),
+        SchedulingPolicy::Batch => pi18n("scheduling policy", "Batch"),
+        SchedulingPolicy::Idle => pi18n("scheduling policy", "Idle"),
+        SchedulingPolicy::Fifo(priority) => i18n_f("FIFO ({})", &[&priority.to_string()]),
+        SchedulingPolicy::RoundRobin(priority) => {
+            i18n_f("Round Robin ({})", &[&priority.to_string()])
+        }
+        SchedulingPolicy::Deadline => pi18n("scheduling policy", "Deadline"),
+    }
 }
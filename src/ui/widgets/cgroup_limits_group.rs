@@ -0,0 +1,266 @@
+use std::cell::{Cell, RefCell};
+
+use adw::{prelude::*, subclass::prelude::*};
+use gtk::gio;
+use gtk::glib::{self, MainContext, clone};
+use log::{trace, warn};
+use process_data::cgroup::{CgroupLimit, CgroupLimits, InheritedLimit, IoMax};
+
+use crate::i18n::{i18n, i18n_f};
+use crate::utils::process::Process;
+use crate::utils::units::{convert_speed, convert_storage};
+
+mod imp {
+    use super::*;
+
+    use gtk::CompositeTemplate;
+
+    #[derive(Debug, CompositeTemplate, Default)]
+    #[template(resource = "/net/nokyan/Resources/ui/widgets/cgroup_limits_group.ui")]
+    pub struct ResCgroupLimitsGroup {
+        #[template_child]
+        pub path: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_current: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_high: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_max: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub cpu_max: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub cpu_weight: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub pids: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub io_max: TemplateChild<adw::ActionRow>,
+
+        pub cgroup_path: RefCell<Option<String>>,
+        pub loading: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResCgroupLimitsGroup {
+        const NAME: &'static str = "ResCgroupLimitsGroup";
+        type Type = super::ResCgroupLimitsGroup;
+        type ParentType = adw::PreferencesGroup;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResCgroupLimitsGroup {}
+
+    impl WidgetImpl for ResCgroupLimitsGroup {}
+
+    impl PreferencesGroupImpl for ResCgroupLimitsGroup {}
+}
+
+glib::wrapper! {
+    pub struct ResCgroupLimitsGroup(ObjectSubclass<imp::ResCgroupLimitsGroup>)
+        @extends gtk::Widget, adw::PreferencesGroup;
+}
+
+impl Default for ResCgroupLimitsGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResCgroupLimitsGroup {
+    pub fn new() -> Self {
+        trace!("Creating ResCgroupLimitsGroup GObject…");
+
+        glib::Object::new::<Self>()
+    }
+
+    /// Sets the cgroup v2 path whose limits should be shown and reads them
+    pub fn set_cgroup_path(&self, cgroup_path: Option<&str>) {
+        let imp = self.imp();
+
+        if imp.cgroup_path.borrow().as_deref() == cgroup_path {
+            return;
+        }
+
+        *imp.cgroup_path.borrow_mut() = cgroup_path.map(str::to_string);
+
+        let path = cgroup_path.map_or_else(|| i18n("N/A"), str::to_string);
+        imp.path.set_subtitle(&path);
+        imp.path.set_tooltip_text(Some(&path));
+
+        self.set_limits(None);
+        self.refresh();
+    }
+
+    /// Re-reads the limits of the current cgroup in the background
+    pub fn refresh(&self) {
+        let imp = self.imp();
+
+        let Some(cgroup_path) = imp.cgroup_path.borrow().clone() else {
+            return;
+        };
+
+        // the previous refresh is still running, there's no need to queue up another one
+        if imp.loading.replace(true) {
+            return;
+        }
+
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                let path = cgroup_path.clone();
+                let limits = gio::spawn_blocking(move || Process::cgroup_limits(path)).await;
+
+                this.imp().loading.set(false);
+
+                // the cgroup might have changed while we were reading, its refresh was skipped then
+                if this.imp().cgroup_path.borrow().as_ref() != Some(&cgroup_path) {
+                    this.refresh();
+                    return;
+                }
+
+                match limits {
+                    Ok(Ok(limits)) => this.set_limits(Some(&limits)),
+                    Ok(Err(error)) => {
+                        warn!("Unable to read limits of cgroup {cgroup_path}: {error}");
+                        this.set_limits(None);
+                    }
+                    Err(_) => this.set_limits(None),
+                }
+            }
+        ));
+    }
+
+    fn set_limits(&self, limits: Option<&CgroupLimits>) {
+        let imp = self.imp();
+
+        let own_path = limits
+            .map(|limits| limits.path.as_str())
+            .unwrap_or_default();
+
+        imp.memory_current.set_subtitle(
+            &limits.and_then(|limits| limits.memory_current).map_or_else(
+                || i18n("N/A"),
+                |memory_current| convert_storage(memory_current as f64, false),
+            ),
+        );
+
+        imp.memory_high.set_subtitle(&inherited_limit_label(
+            limits.and_then(|limits| limits.memory_high.as_ref()),
+            own_path,
+            |limit| cgroup_limit_label(*limit, |bytes| convert_storage(bytes as f64, false)),
+        ));
+
+        imp.memory_max.set_subtitle(&inherited_limit_label(
+            limits.and_then(|limits| limits.memory_max.as_ref()),
+            own_path,
+            |limit| cgroup_limit_label(*limit, |bytes| convert_storage(bytes as f64, false)),
+        ));
+
+        imp.cpu_max.set_subtitle(&inherited_limit_label(
+            limits.and_then(|limits| limits.cpu_max.as_ref()),
+            own_path,
+            |cpu_max| {
+                cpu_max.cpus().map_or_else(
+                    || i18n("Unlimited"),
+                    |cpus| i18n_f("{} CPUs", &[&format!("{cpus:.2}")]),
+                )
+            },
+        ));
+
+        imp.cpu_weight.set_subtitle(
+            &limits
+                .and_then(|limits| limits.cpu_weight)
+                .map_or_else(|| i18n("N/A"), |cpu_weight| cpu_weight.to_string()),
+        );
+
+        let pids_current = limits.and_then(|limits| limits.pids_current);
+        let pids_max = limits.and_then(|limits| limits.pids_max.as_ref());
+        imp.pids.set_subtitle(&match (pids_current, pids_max) {
+            (Some(pids_current), Some(_)) => i18n_f(
+                "{} (limit: {})",
+                &[
+                    &pids_current.to_string(),
+                    &inherited_limit_label(pids_max, own_path, |limit| {
+                        cgroup_limit_label(*limit, |pids| pids.to_string())
+                    }),
+                ],
+            ),
+            (Some(pids_current), None) => pids_current.to_string(),
+            _ => i18n("N/A"),
+        });
+
+        imp.io_max.set_subtitle(
+            &limits
+                .and_then(|limits| limits.io_max.as_ref())
+                .map_or_else(|| i18n("N/A"), |io_max| io_max_label(io_max)),
+        );
+    }
+}
+
+fn cgroup_limit_label<F: Fn(u64) -> String>(limit: CgroupLimit, format: F) -> String {
+    match limit {
+        CgroupLimit::Unlimited => i18n("Unlimited"),
+        CgroupLimit::Limited(limit) => format(limit),
+    }
+}
+
+/// Formats `limit` using `format` and mentions the cgroup it is set in if that's not the inspected cgroup itself
+fn inherited_limit_label<T, F: Fn(&T) -> String>(
+    limit: Option<&InheritedLimit<T>>,
+    own_path: &str,
+    format: F,
+) -> String {
+    match limit {
+        Some(inherited) if inherited.cgroup != own_path => i18n_f(
+            "{} (set by {})",
+            &[&format(&inherited.limit), &inherited.cgroup],
+        ),
+        Some(inherited) => format(&inherited.limit),
+        None => i18n("N/A"),
+    }
+}
+
+fn io_max_label(io_max: &[IoMax]) -> String {
+    if io_max.is_empty() {
+        return i18n("Unlimited");
+    }
+
+    io_max
+        .iter()
+        .map(|io_max| {
+            let mut limits = Vec::new();
+
+            if let Some(bytes) = io_max.read_bytes.limited() {
+                limits.push(i18n_f("Read {}", &[&convert_speed(bytes as f64, false)]));
+            }
+
+            if let Some(bytes) = io_max.write_bytes.limited() {
+                limits.push(i18n_f("Write {}", &[&convert_speed(bytes as f64, false)]));
+            }
+
+            if let Some(ios) = io_max.read_ios.limited() {
+                limits.push(i18n_f("Read {} IOPS", &[&ios.to_string()]));
+            }
+
+            if let Some(ios) = io_max.write_ios.limited() {
+                limits.push(i18n_f("Write {} IOPS", &[&ios.to_string()]));
+            }
+
+            if limits.is_empty() {
+                limits.push(i18n("Unlimited"));
+            }
+
+            format!("{}: {}", io_max.device, limits.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
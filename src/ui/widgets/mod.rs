@@ -1,3 +1,4 @@
+pub mod cgroup_limits_group;
 pub mod double_graph_box;
 pub mod graph;
 pub mod graph_box;
@@ -1,5 +1,12 @@
-use process_data::{Niceness, ProcessData};
+use process_data::{
+    Niceness, OomScoreAdj, ProcessData, ProcessDataOptions,
+    pidfd::ProcessIdentity,
+    scheduling::{IoPriority, SchedulingPolicy},
+    socket::Connection,
+};
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
 use adw::{Toast, ToastOverlay};
@@ -15,10 +22,12 @@ use crate::gui::ARGS;
 use crate::i18n::{i18n, i18n_f, ni18n_f};
 use crate::ui::pages::applications::ResApplications;
 use crate::ui::pages::battery::ResBattery;
+use crate::ui::pages::connections::ResConnections;
 use crate::ui::pages::drive::ResDrive;
 use crate::ui::pages::processes::ResProcesses;
 use crate::utils::app::AppsContext;
 use crate::utils::battery::{Battery, BatteryData};
+use crate::utils::companion::{self, CompanionProblem};
 use crate::utils::cpu::{self, CpuData};
 use crate::utils::drive::{Drive, DriveData};
 use crate::utils::gpu::{Gpu, GpuData};
@@ -28,16 +37,29 @@ use crate::utils::npu::{Npu, NpuData};
 use crate::utils::process::{Process, ProcessAction};
 use crate::utils::settings::SETTINGS;
 
-use super::pages::applications;
 use super::pages::gpu::ResGPU;
 use super::pages::network::ResNetwork;
 use super::pages::npu::ResNPU;
+use super::pages::{applications, processes};
 
 #[derive(Debug, Clone)]
 pub enum Action {
-    ManipulateProcesses(ProcessAction, Vec<libc::pid_t>, ToastOverlay),
+    /// The name is that of the first process, it's shown if there's only one
+    ManipulateProcesses(ProcessAction, Vec<ProcessIdentity>, String, ToastOverlay),
+    /// The processes are ordered leaf-first with the root of the tree last, the name is that of the root
+    ManipulateProcessTree(ProcessAction, Vec<ProcessIdentity>, String, ToastOverlay),
     ManipulateApp(ProcessAction, String, ToastOverlay),
-    AdjustProcess(libc::pid_t, Niceness, Vec<bool>, String, ToastOverlay),
+    AdjustProcess(
+        ProcessIdentity,
+        Niceness,
+        Vec<bool>,
+        Option<IoPriority>,
+        Option<SchedulingPolicy>,
+        Option<OomScoreAdj>,
+        String,
+        ToastOverlay,
+    ),
+    ShowProcess(libc::pid_t),
 }
 
 mod imp {
@@ -50,8 +72,8 @@ mod imp {
         config::VERSION,
         ui::{
             pages::{
-                applications::ResApplications, cpu::ResCPU, memory::ResMemory,
-                processes::ResProcesses,
+                applications::ResApplications, connections::ResConnections, cpu::ResCPU,
+                memory::ResMemory, processes::ResProcesses,
             },
             widgets::stack_sidebar::ResStackSidebar,
         },
@@ -68,6 +90,8 @@ mod imp {
     #[derive(Debug, CompositeTemplate)]
     #[template(resource = "/net/nokyan/Resources/ui/window.ui")]
     pub struct MainWindow {
+        #[template_child]
+        pub companion_banner: TemplateChild<adw::Banner>,
         #[template_child]
         pub split_view: TemplateChild<adw::OverlaySplitView>,
         #[template_child]
@@ -93,6 +117,12 @@ mod imp {
         #[template_child]
         pub processes_search_button: TemplateChild<gtk::ToggleButton>,
         #[template_child]
+        pub connections: TemplateChild<ResConnections>,
+        #[template_child]
+        pub connections_page: TemplateChild<gtk::StackPage>,
+        #[template_child]
+        pub connections_search_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
         pub memory: TemplateChild<ResMemory>,
         #[template_child]
         pub memory_page: TemplateChild<gtk::StackPage>,
@@ -111,6 +141,9 @@ mod imp {
 
         pub pause_updates: Cell<bool>,
 
+        /// Connections are only gathered while their page is visible since that is rather expensive
+        pub connections_visible: Arc<AtomicBool>,
+
         pub sender: Sender<Action>,
         pub receiver: RefCell<Option<Receiver<Action>>>,
     }
@@ -124,6 +157,7 @@ mod imp {
                 drive_pages: RefCell::default(),
                 network_pages: RefCell::default(),
                 battery_pages: RefCell::default(),
+                companion_banner: TemplateChild::default(),
                 split_view: TemplateChild::default(),
                 resources_sidebar: TemplateChild::default(),
                 content_stack: TemplateChild::default(),
@@ -133,12 +167,16 @@ mod imp {
                 processes: TemplateChild::default(),
                 processes_page: TemplateChild::default(),
                 processes_search_button: TemplateChild::default(),
+                connections: TemplateChild::default(),
+                connections_page: TemplateChild::default(),
+                connections_search_button: TemplateChild::default(),
                 cpu: TemplateChild::default(),
                 cpu_page: TemplateChild::default(),
                 memory: TemplateChild::default(),
                 memory_page: TemplateChild::default(),
                 apps_context: Default::default(),
                 pause_updates: Default::default(),
+                connections_visible: Default::default(),
                 sender,
                 receiver,
                 processor_window_title: TemplateChild::default(),
@@ -220,7 +258,9 @@ struct RefreshData {
     network_data: Vec<NetworkData>,
     battery_paths: Vec<PathBuf>,
     battery_data: Vec<BatteryData>,
-    process_data: Vec<ProcessData>,
+    /// `None` if there hasn't been a new snapshot since the last refresh
+    process_data: Option<Vec<ProcessData>>,
+    connections: Option<Vec<Connection>>,
 }
 
 impl MainWindow {
@@ -267,6 +307,7 @@ impl MainWindow {
         if ARGS.disable_process_monitoring {
             self.remove_page(imp.apps_page.child().downcast_ref().unwrap());
             self.remove_page(imp.processes_page.child().downcast_ref().unwrap());
+            self.remove_page(imp.connections_page.child().downcast_ref().unwrap());
         } else {
             *imp.apps_context.borrow_mut() = AppsContext::new(
                 gpus.iter()
@@ -276,6 +317,7 @@ impl MainWindow {
             );
             imp.apps.init(imp.sender.clone());
             imp.processes.init(imp.sender.clone());
+            imp.connections.init(imp.sender.clone());
         }
 
         if ARGS.disable_cpu_monitoring {
@@ -337,10 +379,29 @@ impl MainWindow {
             .bidirectional()
             .build();
 
+        imp.connections
+            .search_bar()
+            .bind_property(
+                "search-mode-enabled",
+                &imp.connections_search_button.get(),
+                "active",
+            )
+            .sync_create()
+            .bidirectional()
+            .build();
+
         imp.content_stack.connect_visible_child_notify(clone!(
+            #[weak(rename_to = this)]
+            self,
             #[weak]
             imp,
             move |_| {
+                imp.connections_visible.store(
+                    this.get_selected_page()
+                        .is_some_and(|page| page.is::<ResConnections>()),
+                    Ordering::Relaxed,
+                );
+
                 // close the split view if we're in "mobile layout" and the user has clicked on an element
                 if imp.split_view.is_collapsed() {
                     imp.split_view.set_show_sidebar(false);
@@ -391,6 +452,8 @@ impl MainWindow {
             imp.apps.toggle_search();
         } else if selected_page.is::<ResProcesses>() {
             imp.processes.toggle_search();
+        } else if selected_page.is::<ResConnections>() {
+            imp.connections.toggle_search();
         }
     }
 
@@ -502,7 +565,24 @@ impl MainWindow {
         npus
     }
 
-    fn gather_refresh_data(logical_cpus: usize, gpus: &[Gpu], npus: &[Npu]) -> RefreshData {
+    /// Switches to the processes page and selects the process with the PID `pid`
+    pub fn show_process(&self, pid: libc::pid_t) {
+        let imp = self.imp();
+
+        let processes_page = imp.processes_page.child();
+        imp.content_stack.set_visible_child(&processes_page);
+        imp.resources_sidebar
+            .set_selected_list_item_by_tab_id(processes::TAB_ID);
+
+        imp.processes.select_process(pid);
+    }
+
+    fn gather_refresh_data(
+        logical_cpus: usize,
+        gpus: &[Gpu],
+        npus: &[Npu],
+        gather_connections: bool,
+    ) -> RefreshData {
         let start = Instant::now();
 
         trace!("Gathering refresh data of all devices…");
@@ -564,18 +644,35 @@ impl MainWindow {
         }
 
         let process_data = if ARGS.disable_process_monitoring {
-            Vec::new()
+            Some(Vec::new())
         } else {
-            Process::all_data()
+            let interval = Duration::from_secs_f32(SETTINGS.refresh_speed().ui_refresh_interval());
+
+            let options = ProcessDataOptions {
+                smaps_rollup: SETTINGS.memory_metric().needs_smaps_rollup(),
+            };
+
+            Process::latest_data(interval, options)
                 .inspect_err(|e| {
                     warn!(
                         "Unable to update process and app data! Is resources-processes running?\n{e}\n{}",
                         e.backtrace()
                     );
                 })
+                // keep showing the last known processes, the banner tells the user that they might be outdated
                 .unwrap_or_default()
         };
 
+        let connections = if ARGS.disable_process_monitoring || !gather_connections {
+            None
+        } else {
+            Process::connections()
+                .inspect_err(|e| {
+                    warn!("Unable to update connections!\n{e}\n{}", e.backtrace());
+                })
+                .ok()
+        };
+
         let refresh_data = RefreshData {
             cpu_data,
             mem_data,
@@ -588,6 +685,7 @@ impl MainWindow {
             battery_paths,
             battery_data,
             process_data,
+            connections,
         };
 
         trace!("Finished gathering refresh data in {:.2?}", start.elapsed());
@@ -614,20 +712,30 @@ impl MainWindow {
             battery_paths,
             battery_data,
             process_data,
+            connections,
         } = refresh_data;
 
         /*
          * Apps and processes
          */
 
+        self.refresh_companion_banner();
+
         let mut apps_context = imp.apps_context.borrow_mut();
-        apps_context.refresh(process_data);
+        if let Some(process_data) = process_data {
+            apps_context.refresh(process_data);
+        }
 
         // if CTRL is held, don't update apps and processes like Windows Task Manager
         if !imp.pause_updates.get() {
             trace!("Skipping visual apps and processes updates");
             imp.apps.refresh_apps_list(&apps_context);
             imp.processes.refresh_processes_list(&apps_context);
+
+            if let Some(connections) = connections {
+                imp.connections
+                    .refresh_connections_list(&connections, &apps_context);
+            }
         }
 
         /*
@@ -793,6 +901,8 @@ impl MainWindow {
 
         let logical_cpus = imp.cpu.imp().logical_cpus_amount.get();
 
+        let connections_visible = imp.connections_visible.clone();
+
         let (tx_data, rx_data) = std::sync::mpsc::sync_channel(1);
         let (tx_wait, rx_wait) = std::sync::mpsc::sync_channel(1);
 
@@ -800,7 +910,12 @@ impl MainWindow {
             trace!("Spawning refresh thread");
 
             loop {
-                let data = Self::gather_refresh_data(logical_cpus, &gpus, &npus);
+                let data = Self::gather_refresh_data(
+                    logical_cpus,
+                    &gpus,
+                    &npus,
+                    connections_visible.load(Ordering::Relaxed),
+                );
                 tx_data.send(data).unwrap();
 
                 // Wait on delay so we don't gather data multiple times in a short time span
@@ -859,6 +974,36 @@ impl MainWindow {
         }
     }
 
+    /// Tells the user if process data can't be gathered, the details are logged when the problem is detected
+    fn refresh_companion_banner(&self) {
+        let imp = self.imp();
+
+        let title = match companion::problem() {
+            None if companion::elevation_denied() => i18n(
+                "Elevated monitoring hasn't been authorized, the usage of processes of other users might be incomplete",
+            ),
+            None => {
+                imp.companion_banner.set_revealed(false);
+                return;
+            }
+            Some(CompanionProblem::Unavailable(_)) => i18n(
+                "The process monitoring helper isn't running, apps and processes might be outdated",
+            ),
+            Some(CompanionProblem::Incompatible(_)) => i18n(
+                "The process monitoring helper belongs to an incompatible version of Resources",
+            ),
+            Some(CompanionProblem::Failing(_)) => i18n(
+                "The process monitoring helper is failing, apps and processes might be outdated",
+            ),
+            Some(CompanionProblem::Fallback(_)) => i18n(
+                "The process monitoring helper keeps failing, Resources is gathering process data on its own",
+            ),
+        };
+
+        imp.companion_banner.set_title(&title);
+        imp.companion_banner.set_revealed(true);
+    }
+
     /// Wrapper to remove page, and check if removed page was visible with global default behavior
     fn remove_page(&self, page: &ToolbarView) {
         trace!("Removing page {:?}…", page);
@@ -1090,49 +1235,42 @@ impl MainWindow {
     fn process_action(&self, action: Action) {
         let apps_context = self.imp().apps_context.borrow();
         match action {
-            Action::ManipulateProcesses(action, pids, toast_overlay) => {
-                let mut processes_unsuccessful: usize = 0;
-
-                let mut first_process = None;
-
-                for (i, pid) in pids.iter().enumerate() {
-                    if let Some(process) = apps_context.get_process(*pid) {
-                        if i == 0 {
-                            first_process = Some(process);
-                        }
-                        if process.execute_process_action(action).is_err() {
-                            processes_unsuccessful += 1;
-                        }
-                    }
-                }
+            // the identities have been taken when the user chose the action, they must not be looked up again since
+            // the PIDs might belong to other processes by now
+            Action::ManipulateProcesses(action, identities, display_name, toast_overlay) => {
+                let processes_unsuccessful = identities
+                    .iter()
+                    .filter(|identity| {
+                        Process::execute_process_action_on([**identity], action).is_err()
+                    })
+                    .count();
 
                 let toast_message = if processes_unsuccessful > 0 {
-                    if pids.len() == 1 {
-                        if let Some(display_name) =
-                            first_process.map(|process| &process.display_name)
-                        {
-                            get_named_action_failure(action, display_name)
-                        } else {
-                            // this should never happen
-                            get_action_failure(action, 1)
-                        }
+                    if identities.len() == 1 {
+                        get_named_action_failure(action, &display_name)
                     } else {
                         get_action_failure(action, processes_unsuccessful)
                     }
-                } else if pids.len() == 1 {
-                    if let Some(display_name) = first_process.map(|process| &process.display_name) {
-                        get_action_success(action, display_name)
-                    } else {
-                        // this should never happen
-                        get_processes_success(action, 1)
-                    }
+                } else if identities.len() == 1 {
+                    get_action_success(action, &display_name)
                 } else {
-                    get_processes_success(action, pids.len())
+                    get_processes_success(action, identities.len())
                 };
 
                 toast_overlay.add_toast(Toast::new(&toast_message));
             }
 
+            Action::ManipulateProcessTree(action, identities, root_name, toast_overlay) => {
+                // processes that had exited already aren't counted
+                let toast_message =
+                    match Process::execute_process_action_on(identities.iter().copied(), action) {
+                        Ok(signalled) => get_processes_success(action, signalled),
+                        Err(_) => get_named_action_failure(action, &root_name),
+                    };
+
+                toast_overlay.add_toast(Toast::new(&toast_message));
+            }
+
             Action::ManipulateApp(action, id, toast_overlay) => {
                 let app = apps_context.get_app(&Some(id.clone())).unwrap();
                 let result = app.execute_process_action(&apps_context, action);
@@ -1150,17 +1288,33 @@ impl MainWindow {
                 toast_overlay.add_toast(Toast::new(&toast_message));
             }
 
-            Action::AdjustProcess(pid, niceness, affinity, display_name, toast_overlay) => {
-                if let Some(process) = apps_context.get_process(pid) {
-                    let result = process.adjust(niceness, affinity);
+            Action::AdjustProcess(
+                identity,
+                niceness,
+                affinity,
+                io_priority,
+                scheduling_policy,
+                oom_score_adj,
+                display_name,
+                toast_overlay,
+            ) => {
+                let result = Process::adjust(
+                    identity,
+                    niceness,
+                    affinity,
+                    io_priority,
+                    scheduling_policy,
+                    oom_score_adj,
+                );
 
-                    let toast_message = match result {
-                        Ok(()) => i18n_f("Successfully adjusted {}", &[&display_name]),
-                        Err(_) => i18n_f("There was a problem adjusting {}", &[&display_name]),
-                    };
-                    toast_overlay.add_toast(Toast::new(&toast_message));
-                }
+                let toast_message = match result {
+                    Ok(()) => i18n_f("Successfully adjusted {}", &[&display_name]),
+                    Err(_) => i18n_f("There was a problem adjusting {}", &[&display_name]),
+                };
+                toast_overlay.add_toast(Toast::new(&toast_message));
             }
+
+            Action::ShowProcess(pid) => self.show_process(pid),
         };
     }
 
@@ -1242,6 +1396,9 @@ fn get_action_success(action: ProcessAction, name: &str) -> String {
         ProcessAction::STOP => i18n_f("Successfully halted {}", &[name]),
         ProcessAction::KILL => i18n_f("Successfully killed {}", &[name]),
         ProcessAction::CONT => i18n_f("Successfully continued {}", &[name]),
+        ProcessAction::Signal(signal) => {
+            i18n_f("Successfully sent {} to {}", &[signal.as_str(), name])
+        }
     }
 }
 
@@ -1271,6 +1428,12 @@ fn get_processes_success(action: ProcessAction, count: usize) -> String {
             count as u32,
             &[&count.to_string()],
         ),
+        ProcessAction::Signal(signal) => ni18n_f(
+            "Successfully sent {} to the process",
+            "Successfully sent {} to {} processes",
+            count as u32,
+            &[signal.as_str(), &count.to_string()],
+        ),
     }
 }
 
@@ -1300,6 +1463,12 @@ fn get_action_failure(action: ProcessAction, count: usize) -> String {
             count as u32,
             &[&count.to_string()],
         ),
+        ProcessAction::Signal(signal) => ni18n_f(
+            "There was a problem sending {} to a process",
+            "There were problems sending {} to {} processes",
+            count as u32,
+            &[signal.as_str(), &count.to_string()],
+        ),
     }
 }
 
@@ -1309,5 +1478,9 @@ pub fn get_named_action_failure(action: ProcessAction, name: &str) -> String {
         ProcessAction::STOP => i18n_f("There was a problem halting {}", &[name]),
         ProcessAction::KILL => i18n_f("There was a problem killing {}", &[name]),
         ProcessAction::CONT => i18n_f("There was a problem continuing {}", &[name]),
+        ProcessAction::Signal(signal) => i18n_f(
+            "There was a problem sending {} to {}",
+            &[signal.as_str(), name],
+        ),
     }
 }
@@ -14,11 +14,13 @@ use lazy_regex::{Lazy, Regex, lazy_regex};
 use log::{debug, info, trace};
 use process_data::{Containerization, GpuIdentifier, ProcessData};
 
-use crate::i18n::i18n;
+use crate::i18n::{i18n, i18n_f};
 
 use super::{
     FiniteOr, boot_time,
     process::{Process, ProcessAction},
+    settings::{MemoryMetric, SETTINGS},
+    short_container_id,
 };
 
 /// This contains the cgroups of desktop environments. If a process has this as its cgroup, its parent's cgroup will be
@@ -163,6 +165,8 @@ pub struct AppsContext {
     apps: HashMap<Option<String>, App>,
     processes: HashMap<i32, Process>,
     gpus_with_combined_media_engine: Vec<GpuIdentifier>,
+    /// The memory metric of the last refresh, so that the settings aren't queried for every single process
+    memory_metric: MemoryMetric,
 }
 
 /// Represents an application installed on the system. It doesn't
@@ -178,6 +182,9 @@ pub struct App {
     pub id: Option<String>,
     pub read_bytes_from_dead_processes: u64,
     pub write_bytes_from_dead_processes: u64,
+    pub read_chars_from_dead_processes: u64,
+    pub write_chars_from_dead_processes: u64,
+    pub cancelled_write_bytes_from_dead_processes: u64,
     pub containerization: Containerization,
 }
 
@@ -225,6 +232,9 @@ impl App {
             id: None,
             read_bytes_from_dead_processes: 0,
             write_bytes_from_dead_processes: 0,
+            read_chars_from_dead_processes: 0,
+            write_chars_from_dead_processes: 0,
+            cancelled_write_bytes_from_dead_processes: 0,
             containerization: Containerization::None,
         });
 
@@ -367,10 +377,66 @@ impl App {
             id,
             read_bytes_from_dead_processes: 0,
             write_bytes_from_dead_processes: 0,
+            read_chars_from_dead_processes: 0,
+            write_chars_from_dead_processes: 0,
+            cancelled_write_bytes_from_dead_processes: 0,
             containerization,
         })
     }
 
+    /// Creates an `App` that groups all processes running inside of the container described by `containerization`
+    pub fn from_container(containerization: &Containerization) -> App {
+        let container_id = containerization.container_id().unwrap_or_default();
+
+        let display_name = match containerization {
+            Containerization::Docker(id) => {
+                i18n_f("Docker Container {}", &[&short_container_id(id)])
+            }
+            Containerization::Podman(id) => {
+                i18n_f("Podman Container {}", &[&short_container_id(id)])
+            }
+            Containerization::Lxc(name) => i18n_f("LXC Container {}", &[name]),
+            Containerization::Nspawn(name) => i18n_f("systemd-nspawn Container {}", &[name]),
+            _ => container_id.to_string(),
+        };
+
+        debug!("Found container \"{display_name}\" (ID: {container_id})");
+
+        App {
+            processes: Vec::new(),
+            commandline: None,
+            executable_name: None,
+            display_name,
+            description: Some(container_id.to_string()),
+            icon: ThemedIcon::new("system-processes").into(),
+            id: Self::container_app_id(containerization),
+            read_bytes_from_dead_processes: 0,
+            write_bytes_from_dead_processes: 0,
+            read_chars_from_dead_processes: 0,
+            write_chars_from_dead_processes: 0,
+            cancelled_write_bytes_from_dead_processes: 0,
+            containerization: containerization.clone(),
+        }
+    }
+
+    /// Returns the ID of the `App` grouping the processes of the container described by `containerization` or `None`
+    /// if `containerization` is not a container runtime
+    pub fn container_app_id(containerization: &Containerization) -> Option<String> {
+        let runtime = match containerization {
+            Containerization::None | Containerization::Flatpak | Containerization::Snap => {
+                return None;
+            }
+            Containerization::Docker(_) => "docker",
+            Containerization::Podman(_) => "podman",
+            Containerization::Lxc(_) => "lxc",
+            Containerization::Nspawn(_) => "nspawn",
+        };
+
+        containerization
+            .container_id()
+            .map(|id| format!("{runtime}:{id}"))
+    }
+
     /// Adds a process to the processes `HashMap` and also
     /// updates the `Process`' icon to the one of this
     /// `App`
@@ -407,7 +473,7 @@ impl App {
     #[must_use]
     pub fn memory_usage(&self, apps: &AppsContext) -> usize {
         self.processes_iter(apps)
-            .map(|process| process.data.memory_usage)
+            .map(|process| process.memory_usage(apps.memory_metric()))
             .sum()
     }
 
@@ -455,6 +521,62 @@ impl App {
         )
     }
 
+    #[must_use]
+    pub fn read_chars_speed(&self, apps: &AppsContext) -> f64 {
+        self.processes_iter(apps)
+            .filter_map(Process::read_chars_speed)
+            .sum()
+    }
+
+    #[must_use]
+    pub fn read_chars_total(&self, apps: &AppsContext) -> u64 {
+        self.read_chars_from_dead_processes.saturating_add(
+            self.processes_iter(apps)
+                .filter_map(|process| process.data.read_chars)
+                .sum::<u64>(),
+        )
+    }
+
+    #[must_use]
+    pub fn write_chars_speed(&self, apps: &AppsContext) -> f64 {
+        self.processes_iter(apps)
+            .filter_map(Process::write_chars_speed)
+            .sum()
+    }
+
+    #[must_use]
+    pub fn write_chars_total(&self, apps: &AppsContext) -> u64 {
+        self.write_chars_from_dead_processes.saturating_add(
+            self.processes_iter(apps)
+                .filter_map(|process| process.data.write_chars)
+                .sum::<u64>(),
+        )
+    }
+
+    #[must_use]
+    pub fn read_syscall_rate(&self, apps: &AppsContext) -> f64 {
+        self.processes_iter(apps)
+            .filter_map(Process::read_syscall_rate)
+            .sum()
+    }
+
+    #[must_use]
+    pub fn write_syscall_rate(&self, apps: &AppsContext) -> f64 {
+        self.processes_iter(apps)
+            .filter_map(Process::write_syscall_rate)
+            .sum()
+    }
+
+    #[must_use]
+    pub fn cancelled_write_total(&self, apps: &AppsContext) -> u64 {
+        self.cancelled_write_bytes_from_dead_processes
+            .saturating_add(
+                self.processes_iter(apps)
+                    .filter_map(|process| process.data.cancelled_write_bytes)
+                    .sum::<u64>(),
+            )
+    }
+
     #[must_use]
     pub fn gpu_usage(&self, apps: &AppsContext) -> f32 {
         self.processes_iter(apps).map(Process::gpu_usage).sum()
@@ -503,6 +625,29 @@ impl App {
             .and_then(|time| time.format("%c").context("unable to format running_since"))
     }
 
+    /// Returns the innermost cgroup v2 path that contains all processes of this app
+    pub fn cgroup_path(&self, apps: &AppsContext) -> Option<String> {
+        let mut common: Option<Vec<&str>> = None;
+
+        for path in self
+            .processes_iter(apps)
+            .filter_map(|process| process.data.cgroup_path.as_deref())
+        {
+            let segments = path.split('/').filter(|segment| !segment.is_empty());
+            common = Some(match common {
+                None => segments.collect(),
+                Some(common) => common
+                    .into_iter()
+                    .zip(segments)
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect(),
+            });
+        }
+
+        common.map(|common| format!("/{}", common.join("/")))
+    }
+
     pub fn running_processes(&self) -> usize {
         self.processes.len()
     }
@@ -522,6 +667,7 @@ impl AppsContext {
             apps,
             processes: HashMap::new(),
             gpus_with_combined_media_engine,
+            memory_metric: SETTINGS.memory_metric(),
         }
     }
 
@@ -641,6 +787,15 @@ impl AppsContext {
 
     fn app_associated_with_process(&self, process: &Process) -> Option<String> {
         // TODO: tidy this up
+        // ↓ processes running in containers are grouped by their container
+        if let Some(container_app_id) = App::container_app_id(&process.data.containerization) {
+            debug!(
+                "Associating process {} with container {container_app_id}",
+                process.data.pid,
+            );
+            return Some(container_app_id);
+        }
+
         // ↓ look for whether we can find an ID in the cgroup
         if DESKTOP_ENVIRONMENT_CGROUPS.contains(&process.data.cgroup.as_deref().unwrap_or_default())
         {
@@ -733,6 +888,10 @@ impl AppsContext {
         }
     }
 
+    pub fn memory_metric(&self) -> MemoryMetric {
+        self.memory_metric
+    }
+
     pub fn get_process(&self, pid: i32) -> Option<&Process> {
         self.processes.get(&pid)
     }
@@ -741,6 +900,10 @@ impl AppsContext {
         self.apps.get(id)
     }
 
+    pub fn get_app_of_process(&self, pid: i32) -> Option<&App> {
+        self.apps.values().find(|app| app.processes.contains(&pid))
+    }
+
     #[must_use]
     pub fn processes_iter(&self) -> impl Iterator<Item = &Process> {
         self.processes.values()
@@ -770,6 +933,8 @@ impl AppsContext {
         trace!("Refreshing AppsContext…");
         let start = Instant::now();
 
+        self.memory_metric = SETTINGS.memory_metric();
+
         let mut updated_processes = HashSet::new();
 
         for mut process_data in new_process_data {
@@ -801,7 +966,24 @@ impl AppsContext {
                 old_process.timestamp_last = old_process.data.timestamp;
                 old_process.read_bytes_last = old_process.data.read_bytes;
                 old_process.write_bytes_last = old_process.data.write_bytes;
+                old_process.read_chars_last = old_process.data.read_chars;
+                old_process.write_chars_last = old_process.data.write_chars;
+                old_process.read_syscalls_last = old_process.data.read_syscalls;
+                old_process.write_syscalls_last = old_process.data.write_syscalls;
+                old_process.major_faults_last = old_process.data.major_faults;
+                old_process.context_switches_last = old_process.data.context_switches();
                 old_process.gpu_usage_stats_last = old_process.data.gpu_usage_stats.clone();
+                old_process.thread_cpu_time_last = old_process
+                    .data
+                    .threads
+                    .iter()
+                    .map(|thread| {
+                        (
+                            thread.tid,
+                            thread.user_cpu_time.saturating_add(thread.system_cpu_time),
+                        )
+                    })
+                    .collect();
 
                 old_process.data = process_data.clone();
             } else {
@@ -810,9 +992,11 @@ impl AppsContext {
 
                 let mut new_process = Process::from_process_data(process_data);
 
+                // apps for containers are created on demand since we can't know about them beforehand
+                let app_id = self.app_associated_with_process(&new_process);
                 self.apps
-                    .get_mut(&self.app_associated_with_process(&new_process))
-                    .unwrap()
+                    .entry(app_id)
+                    .or_insert_with(|| App::from_container(&new_process.data.containerization))
                     .add_process(&mut new_process);
 
                 self.processes.insert(new_process.data.pid, new_process);
@@ -846,6 +1030,24 @@ impl AppsContext {
             app.read_bytes_from_dead_processes += read_dead;
             app.write_bytes_from_dead_processes += write_dead;
 
+            // unlike above, each of these counters is kept even if the others are unavailable
+            for process in app
+                .processes
+                .iter()
+                .filter(|pid| !updated_processes.contains(*pid))
+                .filter_map(|pid| self.processes.get(pid))
+            {
+                app.read_chars_from_dead_processes = app
+                    .read_chars_from_dead_processes
+                    .saturating_add(process.data.read_chars.unwrap_or_default());
+                app.write_chars_from_dead_processes = app
+                    .write_chars_from_dead_processes
+                    .saturating_add(process.data.write_chars.unwrap_or_default());
+                app.cancelled_write_bytes_from_dead_processes = app
+                    .cancelled_write_bytes_from_dead_processes
+                    .saturating_add(process.data.cancelled_write_bytes.unwrap_or_default());
+            }
+
             if read_dead > 0 || write_dead > 0 {
                 trace!(
                     "{} has a process which died earlier, keeping I/O stats",
@@ -858,6 +1060,9 @@ impl AppsContext {
             if !app.is_running() {
                 app.read_bytes_from_dead_processes = 0;
                 app.write_bytes_from_dead_processes = 0;
+                app.read_chars_from_dead_processes = 0;
+                app.write_chars_from_dead_processes = 0;
+                app.cancelled_write_bytes_from_dead_processes = 0;
             }
         });
 
@@ -865,6 +1070,10 @@ impl AppsContext {
         self.processes
             .retain(|pid, _| updated_processes.contains(pid));
 
+        // containers come and go, so there's no need to remember those that have stopped
+        self.apps
+            .retain(|_, app| app.is_running() || app.containerization.container_id().is_none());
+
         trace!("AppsContext refresh done within {:.2?}", start.elapsed());
     }
 }
@@ -8,6 +8,7 @@ use crate::i18n::{i18n, i18n_f};
 use anyhow::{Context, Result, bail};
 use lazy_regex::{Lazy, Regex, lazy_regex};
 use log::trace;
+use process_data::fs_root::sysfs;
 
 use super::units::convert_energy;
 
@@ -164,7 +165,7 @@ pub struct Battery {
 impl Battery {
     pub fn get_sysfs_paths() -> Result<Vec<PathBuf>> {
         let mut list = Vec::new();
-        let entries = std::fs::read_dir("/sys/class/power_supply")?;
+        let entries = std::fs::read_dir(sysfs("class/power_supply"))?;
         for entry in entries {
             let entry = entry?;
             let path = entry.path();
@@ -390,8 +391,78 @@ impl Battery {
 
 #[cfg(test)]
 mod test {
-    use super::Battery;
+    use super::{Battery, Technology};
     use pretty_assertions::assert_eq;
+    use process_data::fs_root::{SYSFS_ROOT, SYSFS_ROOT_VAR};
+
+    fn write_power_supply(root: &std::path::Path, name: &str, files: &[(&str, &str)]) {
+        let path = root.join("class/power_supply").join(name);
+        std::fs::create_dir_all(&path).unwrap();
+
+        for (file, contents) in files {
+            std::fs::write(path.join(file), format!("{contents}\n")).unwrap();
+        }
+    }
+
+    #[test]
+    fn batteries_of_fixture_sysfs() {
+        let root =
+            std::env::temp_dir().join(format!("resources-battery-test-{}", std::process::id()));
+
+        write_power_supply(
+            &root,
+            "BAT0",
+            &[
+                ("type", "Battery"),
+                ("scope", "System"),
+                ("manufacturer", "ACME"),
+                ("model_name", "0x41 0x42 0x43"),
+                ("technology", "Li-ion"),
+                ("energy_full_design", "57000000"),
+            ],
+        );
+        write_power_supply(
+            &root,
+            "BAT1",
+            &[("type", "Battery"), ("technology", "Li-poly")],
+        );
+        write_power_supply(&root, "AC", &[("type", "Mains")]);
+        // e.g. a wireless mouse
+        write_power_supply(
+            &root,
+            "hid-00:11:22:33:44:55-battery",
+            &[("type", "Battery"), ("scope", "Device")],
+        );
+
+        // no other test of this crate reads sysfs, so the root hasn't been determined yet and nothing else reads the
+        // environment concurrently
+        unsafe { std::env::set_var(SYSFS_ROOT_VAR, &root) };
+        assert_eq!(*SYSFS_ROOT, root);
+
+        let mut paths = Battery::get_sysfs_paths().unwrap();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            vec![
+                root.join("class/power_supply/BAT0"),
+                root.join("class/power_supply/BAT1")
+            ]
+        );
+
+        let batteries: Vec<Battery> = paths.iter().map(Battery::from_sysfs).collect();
+
+        assert_eq!(batteries[0].manufacturer.as_deref(), Some("ACME"));
+        assert_eq!(batteries[0].model_name.as_deref(), Some("ABC"));
+        assert_eq!(batteries[0].technology, Technology::LithiumIon);
+        assert_eq!(batteries[0].design_capacity, Some(57.0));
+
+        assert_eq!(batteries[1].manufacturer, None);
+        assert_eq!(batteries[1].technology, Technology::LithiumPolymer);
+        assert_eq!(batteries[1].design_capacity, None);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
 
     #[test]
     fn dont_untangle_untangled_string() {
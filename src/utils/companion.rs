@@ -0,0 +1,850 @@
+use std::{
+    io::{Read, Write},
+    process::{Child, ChildStdout, Command, Stdio},
+    sync::{
+        Arc, Condvar, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use log::{debug, info, trace, warn};
+use process_data::{
+    COMPANION_CAPABILITY_STREAMING, COMPANION_PROTOCOL_VERSION, CompanionFrame, CompanionHandshake,
+    CompanionRequest, ProcessData, ProcessDataOptions,
+    fs_root::{self, PROCFS_ROOT, SYSFS_ROOT},
+};
+use serde::de::DeserializeOwned;
+
+use crate::config::LIBEXECDIR;
+
+use super::{FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK, settings::SETTINGS};
+
+/// How long to wait for the first snapshot of a freshly spawned stream before carrying on without it
+const FIRST_SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Consecutive failures of the companion process after which we gather process data ourselves (if we can)
+const MAX_FAILURES: u32 = 5;
+
+/// Delay before the second respawn of a companion process or stream, it doubles with every further failure, the first
+/// respawn happens immediately
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Exit codes of pkexec if the authentication dialog has been dismissed or the user isn't authorized
+const PKEXEC_DENIED: [i32; 2] = [126, 127];
+
+static SUPERVISOR: Mutex<Supervisor> = Mutex::new(Supervisor::new());
+
+/// The reason why the companion process can't be used right now, shown to the user
+static PROBLEM: Mutex<Option<CompanionProblem>> = Mutex::new(None);
+
+static STREAM_SUPERVISOR: Mutex<StreamSupervisor> = Mutex::new(StreamSupervisor::new());
+
+/// Set once the user refused to authenticate for elevated monitoring, cleared once elevated monitoring is disabled
+static ELEVATION_DENIED: AtomicBool = AtomicBool::new(false);
+
+pub fn companion_path() -> String {
+    if *IS_FLATPAK {
+        format!(
+            "{}/libexec/resources/resources-processes",
+            FLATPAK_APP_PATH.as_str()
+        )
+    } else {
+        format!("{LIBEXECDIR}/resources-processes")
+    }
+}
+
+/// Why process data can't be gathered using the companion process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompanionProblem {
+    /// The companion process couldn't be started or exited unexpectedly
+    Unavailable(String),
+    /// The companion process speaks another protocol, `None` if it's too old to even answer the handshake
+    Incompatible(Option<CompanionHandshake>),
+    /// The companion process runs, but answered with an error frame
+    Failing(String),
+    /// The companion process keeps failing for the given reason, so we're gathering process data ourselves
+    Fallback(String),
+}
+
+impl std::fmt::Display for CompanionProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompanionProblem::Unavailable(reason) => {
+                write!(f, "companion process is unavailable: {reason}")
+            }
+            CompanionProblem::Incompatible(Some(handshake)) => write!(
+                f,
+                "companion process of Resources {} speaks protocol version {}, expected {COMPANION_PROTOCOL_VERSION}",
+                handshake.version, handshake.protocol_version
+            ),
+            CompanionProblem::Incompatible(None) => write!(
+                f,
+                "companion process didn't answer the handshake, expected protocol version {COMPANION_PROTOCOL_VERSION}"
+            ),
+            CompanionProblem::Failing(reason) => {
+                write!(f, "companion process is failing: {reason}")
+            }
+            CompanionProblem::Fallback(reason) => write!(
+                f,
+                "companion process keeps failing ({reason}), gathering process data in-process"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CompanionProblem {}
+
+/// Returns the reason why process data can't be gathered at the moment, `None` if everything's fine
+pub fn problem() -> Option<CompanionProblem> {
+    PROBLEM.lock().unwrap().clone()
+}
+
+fn report_problem(problem: CompanionProblem) {
+    let mut current = PROBLEM.lock().unwrap();
+
+    if current.as_ref() != Some(&problem) {
+        warn!("{problem}");
+        *current = Some(problem);
+    }
+}
+
+/// Clears the current problem once process data could be gathered again
+fn report_success() {
+    let mut current = PROBLEM.lock().unwrap();
+
+    if current.take().is_some() {
+        debug!("Companion process has recovered");
+    }
+}
+
+/// Returns whether elevated monitoring is enabled but the user refused to authenticate for it, so process data is
+/// gathered without administrative privileges
+pub fn elevation_denied() -> bool {
+    SETTINGS.processes_elevated_monitoring() && ELEVATION_DENIED.load(Ordering::Relaxed)
+}
+
+/// Whether the companion process should run with administrative privileges, i.e. elevated monitoring is enabled and
+/// the user hasn't refused to authenticate for it since then
+fn elevation_wanted() -> bool {
+    if SETTINGS.processes_elevated_monitoring() {
+        !ELEVATION_DENIED.load(Ordering::Relaxed)
+    } else {
+        ELEVATION_DENIED.store(false, Ordering::Relaxed);
+        false
+    }
+}
+
+/// Exponential backoff between attempts to spawn a companion process, the first retry happens immediately
+struct Backoff {
+    /// Consecutive failed attempts
+    failures: u32,
+    /// No further attempt is made before this
+    retry_at: Option<Instant>,
+}
+
+impl Backoff {
+    const fn new() -> Self {
+        Self {
+            failures: 0,
+            retry_at: None,
+        }
+    }
+
+    /// Counts another failure and returns how long to wait before the next attempt
+    fn failed(&mut self) -> Duration {
+        self.failures = self.failures.saturating_add(1);
+
+        let backoff = if self.failures == 1 {
+            Duration::ZERO
+        } else {
+            INITIAL_BACKOFF
+                .saturating_mul(2_u32.saturating_pow(self.failures - 2))
+                .min(MAX_BACKOFF)
+        };
+
+        self.retry_at = Some(Instant::now() + backoff);
+
+        backoff
+    }
+
+    fn succeeded(&mut self) {
+        self.failures = 0;
+        self.retry_at = None;
+    }
+
+    /// Whether the next attempt has to wait a little longer
+    fn waiting(&self) -> bool {
+        self.retry_at
+            .is_some_and(|retry_at| Instant::now() < retry_at)
+    }
+}
+
+/// Keeps a companion process around to answer requests, respawning it with an exponential backoff whenever it dies
+struct Supervisor {
+    companion: Option<Companion>,
+    /// Failed attempts to spawn or talk to the companion process
+    backoff: Backoff,
+    /// What went wrong the last time
+    last_problem: Option<CompanionProblem>,
+    /// A companion process that's being spawned through pkexec, which waits for the user to authenticate. Until it's
+    /// done, requests are answered by an unprivileged companion process.
+    elevation: Option<JoinHandle<Result<Companion, CompanionProblem>>>,
+    /// Failed attempts to spawn a companion process through pkexec for reasons other than the user refusing to
+    /// authenticate
+    elevation_backoff: Backoff,
+}
+
+impl Supervisor {
+    const fn new() -> Self {
+        Self {
+            companion: None,
+            backoff: Backoff::new(),
+            last_problem: None,
+            elevation: None,
+            elevation_backoff: Backoff::new(),
+        }
+    }
+
+    /// Returns the running companion process, (re)spawning it if it isn't running and we're not backing off. If
+    /// elevated monitoring is enabled, a privileged companion process takes over once the user has authenticated,
+    /// and it's replaced by an unprivileged one when elevated monitoring is disabled again.
+    fn companion(&mut self) -> Result<&mut Companion, CompanionProblem> {
+        let elevated = elevation_wanted();
+
+        self.poll_elevation(elevated);
+
+        if !elevated
+            && self
+                .companion
+                .as_ref()
+                .is_some_and(|companion| companion.elevated)
+        {
+            info!("Elevated monitoring has been disabled, respawning companion process");
+            self.companion = None;
+            self.succeeded();
+        }
+
+        if self.companion.is_none() {
+            if self.backoff.waiting() {
+                return Err(self.last_problem.clone().unwrap_or_else(|| {
+                    CompanionProblem::Unavailable("waiting to be respawned".into())
+                }));
+            }
+
+            match Companion::spawn(false) {
+                Ok(companion) => {
+                    if self.backoff.failures > 0 {
+                        info!("Respawned companion process");
+                    }
+                    self.companion = Some(companion);
+                }
+                Err(problem) => {
+                    self.failed(problem.clone());
+                    return Err(problem);
+                }
+            }
+        }
+
+        Ok(self.companion.as_mut().unwrap())
+    }
+
+    /// Swaps in the privileged companion process once it has been spawned and starts spawning one in the background if
+    /// `elevated` monitoring is wanted, without ever waiting for the user to authenticate
+    fn poll_elevation(&mut self, elevated: bool) {
+        if self.elevation.as_ref().is_some_and(JoinHandle::is_finished) {
+            let spawned = self.elevation.take().unwrap().join().unwrap_or_else(|_| {
+                Err(CompanionProblem::Unavailable(
+                    "spawning thread has panicked".into(),
+                ))
+            });
+
+            match spawned {
+                Ok(companion) if elevated => {
+                    info!("Switching to companion process with elevated privileges");
+                    self.companion = Some(companion);
+                    self.succeeded();
+                    self.elevation_backoff.succeeded();
+                }
+                Ok(_) => debug!(
+                    "Elevated monitoring has been disabled during authentication, discarding privileged companion process"
+                ),
+                Err(_) if ELEVATION_DENIED.load(Ordering::Relaxed) => warn!(
+                    "Authentication for elevated monitoring has been denied, continuing without it"
+                ),
+                Err(problem) => {
+                    let backoff = self.elevation_backoff.failed();
+                    warn!(
+                        "Unable to spawn companion process with elevated privileges ({problem}), retrying in {backoff:.1?}"
+                    );
+                }
+            }
+        }
+
+        let running_elevated = self
+            .companion
+            .as_ref()
+            .is_some_and(|companion| companion.elevated);
+
+        // the user might have refused to authenticate just now
+        if !elevation_wanted()
+            || running_elevated
+            || self.elevation.is_some()
+            || self.elevation_backoff.waiting()
+        {
+            return;
+        }
+
+        info!(
+            "Elevated monitoring is enabled, spawning privileged companion process in the background"
+        );
+
+        match std::thread::Builder::new()
+            .name("companion-elevation".into())
+            .spawn(|| Companion::spawn(true))
+        {
+            Ok(handle) => self.elevation = Some(handle),
+            Err(error) => {
+                let backoff = self.elevation_backoff.failed();
+                warn!(
+                    "Unable to spawn thread for elevated companion process ({error}), retrying in {backoff:.1?}"
+                );
+            }
+        }
+    }
+
+    /// Gets rid of the current companion process and schedules the next attempt to spawn one
+    fn failed(&mut self, problem: CompanionProblem) {
+        self.companion = None;
+        let backoff = self.backoff.failed();
+
+        warn!(
+            "Companion process failed {} time(s) in a row ({problem}), respawning it in {backoff:.1?}",
+            self.backoff.failures
+        );
+
+        self.last_problem = Some(problem.clone());
+
+        report_problem(problem);
+    }
+
+    fn succeeded(&mut self) {
+        self.backoff.succeeded();
+        self.last_problem = None;
+    }
+
+    /// Whether the companion process has failed so often that we should gather process data ourselves
+    fn given_up(&self) -> bool {
+        self.backoff.failures >= MAX_FAILURES
+    }
+}
+
+/// The companion process that answers requests, after a successful handshake
+struct Companion {
+    /// Its stdin is kept in here so that it can be closed before waiting for the companion process to exit
+    child: Child,
+    stdout: ChildStdout,
+    handshake: CompanionHandshake,
+    /// Whether it has been started through pkexec
+    elevated: bool,
+}
+
+impl Companion {
+    /// Spawns a companion process and performs the handshake. If `elevated` is `true`, it's started through pkexec,
+    /// which asks the user to authenticate once for the whole lifetime of the companion process.
+    fn spawn(elevated: bool) -> Result<Self, CompanionProblem> {
+        let mut child = spawn(&[], elevated)
+            .map_err(|error| CompanionProblem::Unavailable(error.to_string()))?;
+
+        let mut companion = Self {
+            stdout: child.stdout.take().unwrap(),
+            child,
+            handshake: CompanionHandshake::current(),
+            elevated,
+        };
+
+        // versions predating the handshake exit when they receive this request, so we'd read EOF, the same happens if
+        // pkexec couldn't authenticate the user
+        let handshake = match companion
+            .exchange(&CompanionRequest::Handshake)
+            .and_then(|answer| {
+                rmp_serde::from_slice::<CompanionHandshake>(&answer)
+                    .context("unable to decode handshake")
+            }) {
+            Ok(handshake) => handshake,
+            Err(error) => {
+                debug!("Companion process handshake failed: {error:#}");
+
+                if elevated {
+                    drop(companion.child.stdin.take());
+
+                    if let Ok(status) = companion.child.wait() {
+                        if status
+                            .code()
+                            .is_some_and(|code| PKEXEC_DENIED.contains(&code))
+                        {
+                            ELEVATION_DENIED.store(true, Ordering::Relaxed);
+                            return Err(CompanionProblem::Unavailable(
+                                "authentication has been denied".into(),
+                            ));
+                        }
+                    }
+                }
+
+                return Err(CompanionProblem::Incompatible(None));
+            }
+        };
+
+        debug!(
+            "Companion process of Resources {} speaks protocol version {} with capabilities {:?}",
+            handshake.version, handshake.protocol_version, handshake.capabilities
+        );
+
+        if !handshake.is_compatible() {
+            return Err(CompanionProblem::Incompatible(Some(handshake)));
+        }
+
+        companion.handshake = handshake;
+
+        Ok(companion)
+    }
+
+    /// Writes `request` into the stdin of the companion process and returns its raw answer
+    fn exchange(&mut self, request: &CompanionRequest) -> Result<Vec<u8>> {
+        let request_bytes = rmp_serde::to_vec(request)?;
+
+        let stdin = self
+            .child
+            .stdin
+            .as_mut()
+            .context("companion process stdin has been closed")?;
+
+        trace!("Writing {request:?} into companion process stdin");
+        stdin.write_all(&request_bytes.len().to_le_bytes())?;
+        stdin.write_all(&request_bytes)?;
+        trace!("Flushing");
+        stdin.flush()?;
+
+        read_frame(&mut self.stdout)
+    }
+
+    /// Explains why talking to the companion process failed with `error`, preferably using its exit status
+    fn failure_reason(&mut self, error: &anyhow::Error) -> String {
+        failure_reason(&mut self.child, error)
+    }
+}
+
+impl Drop for Companion {
+    fn drop(&mut self) {
+        // in Flatpak mode, this only ends flatpak-spawn, and we're not allowed to kill a companion process that runs
+        // as root, but either way it exits as well once its stdin is closed
+        drop(self.child.stdin.take());
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn failure_reason(child: &mut Child, error: &anyhow::Error) -> String {
+    match child.try_wait() {
+        Ok(Some(status)) => format!("{status}"),
+        _ => format!("{error:#}"),
+    }
+}
+
+/// Spawns `resources-processes` with `args` (on the host if we're running as a Flatpak and through pkexec if
+/// `elevated`) with piped stdin and stdout
+fn spawn(args: &[&str], elevated: bool) -> std::io::Result<Child> {
+    let proxy_path = companion_path();
+
+    let mut program = Vec::new();
+
+    if *IS_FLATPAK {
+        program.extend([FLATPAK_SPAWN, "--host"]);
+    }
+
+    if elevated {
+        program.extend(["pkexec", "--disable-internal-agent"]);
+    }
+
+    program.push(proxy_path.as_str());
+
+    debug!(
+        "Spawning resources-processes in {} mode{}: {}",
+        if *IS_FLATPAK { "Flatpak" } else { "native" },
+        if elevated {
+            " with elevated privileges"
+        } else {
+            ""
+        },
+        program.join(" ")
+    );
+
+    let mut command = Command::new(program[0]);
+    command.args(&program[1..]).args(args);
+
+    // neither flatpak-spawn nor pkexec pass our environment on, so overridden roots have to be given explicitly, as
+    // absolute paths because the companion doesn't necessarily share our working directory
+    if fs_root::procfs_overridden() {
+        command
+            .arg("--procfs-root")
+            .arg(std::path::absolute(&*PROCFS_ROOT)?);
+    }
+
+    if fs_root::sysfs_overridden() {
+        command
+            .arg("--sysfs-root")
+            .arg(std::path::absolute(&*SYSFS_ROOT)?);
+    }
+
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+/// Reads a little-endian length followed by a payload of that length
+fn read_frame<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+    let mut len_bytes = [0_u8; (usize::BITS / 8) as usize];
+
+    trace!("Reading companion process output length as little-endian");
+    reader.read_exact(&mut len_bytes)?;
+
+    let len = usize::from_le_bytes(len_bytes);
+    trace!("Companion process output is {len} bytes long");
+
+    let mut output_bytes = vec![0; len];
+    trace!("Reading companion process output");
+    reader.read_exact(&mut output_bytes)?;
+
+    Ok(output_bytes)
+}
+
+/// Decodes a `CompanionFrame`, error frames are turned into `Err`
+fn decode_frame<T: DeserializeOwned>(frame: &[u8]) -> Result<T> {
+    rmp_serde::from_slice::<CompanionFrame<T>>(frame)
+        .context("unable to decode companion process output")?
+        .map_err(|error| anyhow!(error))
+}
+
+/// Sends `request` to the companion process and returns its decoded answer.
+///
+/// # Errors
+///
+/// Will return `Err` if the companion process can't be reached, if its answer can't be decoded or if it answered with
+/// an error frame
+pub fn request<T: DeserializeOwned>(request: CompanionRequest) -> Result<T> {
+    trace!("Acquiring companion process lock");
+    let mut supervisor = SUPERVISOR.lock().unwrap();
+
+    // if a companion process that used to work has died in the meantime, the first respawn happens right away, so
+    // give it a second chance before failing this request
+    let mut retried = false;
+
+    loop {
+        let companion = supervisor.companion().map_err(anyhow::Error::new)?;
+
+        match companion.exchange(&request) {
+            Ok(answer) => {
+                supervisor.succeeded();
+                return decode_frame(&answer);
+            }
+            Err(error) => {
+                let problem = CompanionProblem::Unavailable(companion.failure_reason(&error));
+                supervisor.failed(problem.clone());
+
+                if retried {
+                    return Err(anyhow::Error::new(problem));
+                }
+
+                retried = true;
+            }
+        }
+    }
+}
+
+/// Requests all `ProcessData` from the companion process, keeping track of whether it's failing. If it has failed too
+/// often, the data is gathered in-process instead, which isn't possible in Flatpak since the sandbox hides other
+/// processes.
+pub fn process_data(options: ProcessDataOptions) -> Result<Vec<ProcessData>> {
+    let process_data = request(CompanionRequest::ProcessData(options));
+
+    if process_data.is_err() && !*IS_FLATPAK {
+        let supervisor = SUPERVISOR.lock().unwrap();
+
+        if supervisor.given_up() {
+            let reason = supervisor
+                .last_problem
+                .as_ref()
+                .map_or_else(String::new, ToString::to_string);
+            drop(supervisor);
+
+            report_problem(CompanionProblem::Fallback(reason));
+
+            return ProcessData::all_process_data(options);
+        }
+    }
+
+    track_process_data(&process_data);
+    process_data
+}
+
+fn track_process_data<T>(process_data: &Result<T>) {
+    match process_data {
+        Ok(_) => report_success(),
+        // problems that prevent us from talking to the companion process at all have been reported already
+        Err(error) if error.downcast_ref::<CompanionProblem>().is_none() => {
+            report_problem(CompanionProblem::Failing(format!("{error:#}")));
+        }
+        Err(_) => {}
+    }
+}
+
+/// Returns whether process data can be streamed using `latest_process_data()`. This is never the case once a
+/// companion process with elevated privileges is running, since a second one would need its own authentication, nor
+/// while a failed stream waits to be restarted.
+pub fn streaming_supported() -> bool {
+    let (elevated, streaming) = match SUPERVISOR.lock().unwrap().companion() {
+        Ok(companion) => (
+            companion.elevated,
+            companion
+                .handshake
+                .has_capability(COMPANION_CAPABILITY_STREAMING),
+        ),
+        Err(_) => return false,
+    };
+
+    let mut stream_supervisor = STREAM_SUPERVISOR.lock().unwrap();
+
+    if elevated {
+        // a stream that has been started before the privileged companion process took over isn't needed anymore
+        stream_supervisor.stream = None;
+        return false;
+    }
+
+    streaming && !stream_supervisor.backoff.waiting()
+}
+
+/// Returns the newest `ProcessData` snapshot pushed by a companion process that gathers them on its own every
+/// `interval`, `Ok(None)` if there hasn't been a new one since the last call. The companion process is (re)spawned as
+/// needed, e.g. when `interval` or `options` change, with the same exponential backoff as the one answering requests.
+///
+/// # Errors
+///
+/// Will return `Err` if the snapshot couldn't be decoded or if the stream couldn't be (re)started, in the latter case
+/// `streaming_supported()` returns `false` until it's time for the next attempt
+pub fn latest_process_data(
+    interval: Duration,
+    options: ProcessDataOptions,
+) -> Result<Option<Vec<ProcessData>>> {
+    let mut stream_supervisor = STREAM_SUPERVISOR.lock().unwrap();
+    let StreamSupervisor { stream, backoff } = &mut *stream_supervisor;
+
+    if let Some(old_stream) = stream.as_mut() {
+        if old_stream.mailbox.ended.load(Ordering::Relaxed) {
+            let received_any = old_stream.mailbox.received_any.load(Ordering::Relaxed);
+            let reason = old_stream.failure_reason();
+            *stream = None;
+
+            let backoff = backoff.failed();
+
+            warn!(
+                "Process data stream ended unexpectedly{} ({reason}), restarting it in {backoff:.1?}",
+                if received_any {
+                    ""
+                } else {
+                    " before sending any process data"
+                }
+            );
+        } else if old_stream.interval != interval {
+            debug!("Refresh interval changed to {interval:?}, restarting process data stream");
+            *stream = None;
+        } else if old_stream.options != options {
+            debug!("Process data options changed to {options:?}, restarting process data stream");
+            *stream = None;
+        }
+    }
+
+    let timeout = if stream.is_none() {
+        if backoff.waiting() {
+            bail!("process data stream is waiting to be restarted");
+        }
+
+        match ProcessDataStream::spawn(interval, options) {
+            Ok(new_stream) => *stream = Some(new_stream),
+            Err(error) => {
+                let backoff = backoff.failed();
+                return Err(error.context(format!("restarting it in {backoff:.1?}")));
+            }
+        }
+
+        FIRST_SNAPSHOT_TIMEOUT
+    } else {
+        Duration::ZERO
+    };
+
+    let process_data = stream
+        .as_ref()
+        .and_then(|stream| stream.take(timeout))
+        .transpose();
+
+    if let Ok(Some(_)) = process_data {
+        backoff.succeeded();
+    }
+
+    if !matches!(process_data, Ok(None)) {
+        track_process_data(&process_data);
+    }
+
+    process_data
+}
+
+/// Keeps the process data stream around, restarting it with an exponential backoff whenever it ends
+struct StreamSupervisor {
+    stream: Option<ProcessDataStream>,
+    /// Ends of the stream and failed attempts to start it since the last snapshot
+    backoff: Backoff,
+}
+
+impl StreamSupervisor {
+    const fn new() -> Self {
+        Self {
+            stream: None,
+            backoff: Backoff::new(),
+        }
+    }
+}
+
+/// Snapshots pushed by a streaming companion process that haven't been picked up yet
+#[derive(Default)]
+struct Mailbox {
+    latest: Mutex<Option<Result<Vec<ProcessData>>>>,
+    arrived: Condvar,
+    received_any: AtomicBool,
+    ended: AtomicBool,
+}
+
+impl Mailbox {
+    /// Marks the stream as ended and wakes up anyone waiting for a snapshot
+    fn end(&self) {
+        let _latest = self.latest.lock().unwrap();
+        self.ended.store(true, Ordering::Relaxed);
+        self.arrived.notify_all();
+    }
+}
+
+/// A companion process started with `--interval` that pushes `ProcessData` snapshots on its own schedule. A reader
+/// thread decodes them as they arrive and only keeps the newest one, so picking it up never has to wait for procfs
+/// to be scanned.
+struct ProcessDataStream {
+    child: Child,
+    interval: Duration,
+    options: ProcessDataOptions,
+    mailbox: Arc<Mailbox>,
+}
+
+impl ProcessDataStream {
+    fn spawn(interval: Duration, options: ProcessDataOptions) -> Result<Self> {
+        let interval_millis = interval.as_millis().max(1).to_string();
+
+        let mut args = vec!["--interval", interval_millis.as_str()];
+
+        if options.smaps_rollup {
+            args.push("--smaps-rollup");
+        }
+
+        let mut child =
+            spawn(&args, false).context("unable to spawn streaming companion process")?;
+
+        let mut stdout = child
+            .stdout
+            .take()
+            .context("streaming companion process has no stdout")?;
+
+        let mailbox = Arc::new(Mailbox::default());
+        let thread_mailbox = Arc::clone(&mailbox);
+
+        std::thread::Builder::new()
+            .name("process-data-stream".into())
+            .spawn(move || {
+                let handshake = read_frame(&mut stdout).and_then(|frame| {
+                    rmp_serde::from_slice::<CompanionHandshake>(&frame)
+                        .context("unable to decode handshake")
+                });
+
+                match handshake {
+                    Ok(handshake) if handshake.is_compatible() => {}
+                    Ok(handshake) => {
+                        debug!(
+                            "Streaming companion process speaks protocol version {}",
+                            handshake.protocol_version
+                        );
+                        thread_mailbox.end();
+                        return;
+                    }
+                    Err(error) => {
+                        debug!("Streaming companion process handshake failed: {error:#}");
+                        thread_mailbox.end();
+                        return;
+                    }
+                }
+
+                loop {
+                    let frame = read_frame(&mut stdout).map(|frame| decode_frame(&frame));
+
+                    match frame {
+                        Ok(process_data) => {
+                            trace!("Received process data snapshot from stream");
+                            thread_mailbox.received_any.store(true, Ordering::Relaxed);
+                            *thread_mailbox.latest.lock().unwrap() = Some(process_data);
+                            thread_mailbox.arrived.notify_all();
+                        }
+                        Err(error) => {
+                            debug!("Process data stream ended: {error}");
+                            thread_mailbox.end();
+                            break;
+                        }
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            child,
+            interval,
+            options,
+            mailbox,
+        })
+    }
+
+    fn failure_reason(&mut self) -> String {
+        failure_reason(&mut self.child, &anyhow!("stdout has been closed"))
+    }
+
+    /// Takes the newest snapshot that hasn't been taken yet, waits up to `timeout` for one if there is none
+    fn take(&self, timeout: Duration) -> Option<Result<Vec<ProcessData>>> {
+        let latest = self.mailbox.latest.lock().unwrap();
+
+        let (mut latest, _) = self
+            .mailbox
+            .arrived
+            .wait_timeout_while(latest, timeout, |latest| {
+                latest.is_none() && !self.mailbox.ended.load(Ordering::Relaxed)
+            })
+            .unwrap();
+
+        latest.take()
+    }
+}
+
+impl Drop for ProcessDataStream {
+    fn drop(&mut self) {
+        // in Flatpak mode, this only ends flatpak-spawn, but the companion process on the host exits as well as soon as
+        // it fails to write into its closed stdout
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
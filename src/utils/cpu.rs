@@ -2,13 +2,16 @@ use anyhow::{Context, Result, anyhow, bail};
 use glob::glob;
 use lazy_regex::{Lazy, Regex, lazy_regex};
 use log::{debug, trace, warn};
+use process_data::fs_root::{procfs, sysfs};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::LazyLock,
 };
 
-const PROC_STAT: &str = "/proc/stat";
+use super::sysfs_glob;
+
+static PROC_STAT: LazyLock<PathBuf> = LazyLock::new(|| procfs("stat"));
 
 const KNOWN_HWMONS: &[&str] = &["zenpower", "coretemp", "k10temp"];
 
@@ -53,7 +56,7 @@ fn search_for_hwmons(names: &[&'static str]) -> Option<PathBuf> {
 
     trace!("Collecting hwmons for CPU temperature…");
 
-    for path in (glob("/sys/class/hwmon/hwmon*").unwrap()).flatten() {
+    for path in (sysfs_glob("class/hwmon/hwmon*").unwrap()).flatten() {
         trace!("Found hwmon {path:?}");
         if let Ok(read_name) = std::fs::read_to_string(path.join("name")) {
             let read_name = read_name.trim_end().to_string();
@@ -83,7 +86,7 @@ fn search_for_thermal_zones(types: &[&'static str]) -> Option<PathBuf> {
 
     trace!("Collecting thermal zones for CPU temperature…");
 
-    for path in (glob("/sys/class/thermal/thermal_zone*").unwrap()).flatten() {
+    for path in (sysfs_glob("class/thermal/thermal_zone*").unwrap()).flatten() {
         trace!("Found thermal zone {path:?}");
         if let Ok(read_type) = std::fs::read_to_string(path.join("type")) {
             let read_type = read_type.trim_end().to_string();
@@ -249,9 +252,9 @@ impl CpuInfo {
 pub fn get_cpu_freq(core: usize) -> Result<u64> {
     trace!("Finding CPU frequency for core {core}…");
 
-    std::fs::read_to_string(format!(
-        "/sys/devices/system/cpu/cpu{core}/cpufreq/scaling_cur_freq"
-    ))
+    std::fs::read_to_string(sysfs(format!(
+        "devices/system/cpu/cpu{core}/cpufreq/scaling_cur_freq"
+    )))
     .inspect_err(|err| trace!("Unable to get CPU frequency for core {core}: {err}"))
     .with_context(|| format!("unable to read scaling_cur_freq for core {core}"))?
     .replace('\n', "")
@@ -289,7 +292,7 @@ fn parse_proc_stat_line<S: AsRef<str>>(line: S) -> Result<(u64, u64)> {
 }
 
 fn parse_proc_stat<S: AsRef<str>>(stat: S) -> Vec<Result<(u64, u64)>> {
-    trace!("Parsing {PROC_STAT}…");
+    trace!("Parsing {}…", PROC_STAT.display());
 
     stat.as_ref()
         .lines()
@@ -309,10 +312,10 @@ fn parse_proc_stat<S: AsRef<str>>(stat: S) -> Vec<Result<(u64, u64)>> {
 /// Will return `Err` if the are problems during reading or parsing
 /// of /proc/stat
 pub fn get_cpu_usage() -> Vec<Result<(u64, u64)>> {
-    trace!("Reading {PROC_STAT}…");
+    trace!("Reading {}…", PROC_STAT.display());
 
-    let raw = std::fs::read_to_string("/proc/stat")
-        .context("unable to read /proc/stat")
+    let raw = std::fs::read_to_string(PROC_STAT.as_path())
+        .with_context(|| format!("unable to read {}", PROC_STAT.display()))
         .unwrap_or_default();
 
     parse_proc_stat(raw)
@@ -6,15 +6,16 @@ use gtk::gio::{Icon, ThemedIcon};
 use lazy_regex::{Lazy, Regex, lazy_regex};
 use log::trace;
 use path_dedot::ParseDot;
-use process_data::pci_slot::PciSlot;
+use process_data::{fs_root::sysfs, pci_slot::PciSlot};
 use std::{
     collections::HashMap,
     fmt::Display,
     path::{Path, PathBuf},
     str::FromStr,
+    sync::LazyLock,
 };
 
-const PATH_SYSFS: &str = "/sys/block";
+static PATH_SYSFS: LazyLock<PathBuf> = LazyLock::new(|| sysfs("block"));
 
 static RE_DRIVE: Lazy<Regex> = lazy_regex!(
     r" *(?P<read_ios>[0-9]*) *(?P<read_merges>[0-9]*) *(?P<read_sectors>[0-9]*) *(?P<read_ticks>[0-9]*) *(?P<write_ios>[0-9]*) *(?P<write_merges>[0-9]*) *(?P<write_sectors>[0-9]*) *(?P<write_ticks>[0-9]*) *(?P<in_flight>[0-9]*) *(?P<io_ticks>[0-9]*) *(?P<time_in_queue>[0-9]*) *(?P<discard_ios>[0-9]*) *(?P<discard_merges>[0-9]*) *(?P<discard_sectors>[0-9]*) *(?P<discard_ticks>[0-9]*) *(?P<flush_ios>[0-9]*) *(?P<flush_ticks>[0-9]*)"
@@ -187,8 +188,8 @@ impl Drive {
     /// reading or parsing
     pub fn get_sysfs_paths() -> Result<Vec<PathBuf>> {
         let mut list = Vec::new();
-        trace!("Finding entries in {PATH_SYSFS}");
-        let entries = std::fs::read_dir(PATH_SYSFS)?;
+        trace!("Finding entries in {}", PATH_SYSFS.display());
+        let entries = std::fs::read_dir(PATH_SYSFS.as_path())?;
         for entry in entries {
             let entry = entry?;
             let block_device = entry.file_name().to_string_lossy().to_string();
@@ -224,11 +225,11 @@ impl Drive {
     /// reading or parsing
     pub fn sys_stats(&self) -> Result<HashMap<String, usize>> {
         let stat = std::fs::read_to_string(self.sysfs_path.join("stat"))
-            .with_context(|| format!("unable to read /sys/block/{}/stat", self.block_device))?;
+            .with_context(|| format!("unable to read {}/stat", self.sysfs_path.display()))?;
 
         let captures = RE_DRIVE
             .captures(&stat)
-            .with_context(|| format!("unable to parse /sys/block/{}/stat", self.block_device))?;
+            .with_context(|| format!("unable to parse {}/stat", self.sysfs_path.display()))?;
 
         Ok(RE_DRIVE
             .capture_names()
@@ -18,7 +18,7 @@ use std::{
 use self::{amd::AmdGpu, intel::IntelGpu, nvidia::NvidiaGpu, other::OtherGpu};
 use crate::utils::{
     link::{Link, LinkData},
-    read_sysfs,
+    read_sysfs, sysfs_glob,
 };
 use crate::{
     i18n::i18n,
@@ -225,7 +225,7 @@ impl Gpu {
         debug!("Searching for GPUs…");
 
         let mut gpu_vec: Vec<Gpu> = Vec::new();
-        for (i, entry) in glob("/sys/class/drm/card?")?.flatten().enumerate() {
+        for (i, entry) in sysfs_glob("class/drm/card?")?.flatten().enumerate() {
             if let Ok(gpu) = Self::from_sysfs_path(entry, i) {
                 gpu_vec.push(gpu);
             }
@@ -4,7 +4,7 @@ use crate::utils::link::SataSpeed::{Sata150, Sata300, Sata600};
 use crate::utils::units::convert_speed_bits_decimal_with_places;
 use anyhow::{Context, Error, Result, anyhow, bail};
 use log::trace;
-use process_data::pci_slot::PciSlot;
+use process_data::{fs_root::sysfs, pci_slot::PciSlot};
 use std::fmt::{Display, Formatter};
 use std::path::Path;
 use std::str::FromStr;
@@ -60,10 +60,9 @@ pub enum UsbSpeed {
 
 impl LinkData<PcieLinkData> {
     pub fn from_pci_slot(pci_slot: &PciSlot) -> Result<Self> {
-        let pcie_dir = format!("/sys/bus/pci/devices/{pci_slot}/");
-        let pcie_folder = Path::new(pcie_dir.as_str());
+        let pcie_folder = sysfs("bus/pci/devices").join(pci_slot.to_string());
         if pcie_folder.exists() {
-            return Self::read_pcie_link_data(pcie_folder);
+            return Self::read_pcie_link_data(&pcie_folder);
         }
         bail!("Could not find PCIe address entry for {pci_slot}");
     }
@@ -137,8 +136,7 @@ impl LinkData<SataSpeed> {
     pub fn from_ata_slot(ata_slot: &AtaSlot) -> Result<Self> {
         trace!("Reading ATA link data for {ata_slot:?}…");
 
-        let ata_link_path =
-            Path::new("/sys/class/ata_link").join(format!("link{}", ata_slot.ata_link));
+        let ata_link_path = sysfs("class/ata_link").join(format!("link{}", ata_slot.ata_link));
 
         let current_sata_speed_raw = std::fs::read_to_string(ata_link_path.join("sata_spd"))
             .map(|x| x.trim().to_string())
@@ -160,8 +158,7 @@ impl LinkData<UsbSpeed> {
     pub fn from_usb_slot(usb_slot: &UsbSlot) -> Result<Self> {
         trace!("Reading USB link data for {usb_slot:?}…");
 
-        let usb_bus_path =
-            Path::new("/sys/bus/usb/devices/").join(format!("usb{}", usb_slot.usb_bus));
+        let usb_bus_path = sysfs("bus/usb/devices").join(format!("usb{}", usb_slot.usb_bus));
 
         let max_usb_port_speed_raw = std::fs::read_to_string(usb_bus_path.join("speed"))
             .map(|x| x.trim().to_string())
@@ -1,12 +1,13 @@
-use std::process::Command;
+use std::{path::PathBuf, process::Command, sync::LazyLock};
 
 use anyhow::{Context, Result, bail};
 use lazy_regex::{Lazy, Regex, lazy_regex};
 use log::{debug, trace};
+use process_data::fs_root::{procfs, sysfs};
 
 use super::{FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK};
 
-const PROC_MEMINFO: &str = "/proc/meminfo";
+static PROC_MEMINFO: LazyLock<PathBuf> = LazyLock::new(|| procfs("meminfo"));
 
 const TEMPLATE_RE_PRESENT: &str = r"MEMORY_DEVICE_%_PRESENT=(\d)";
 
@@ -58,10 +59,10 @@ impl MemoryData {
     pub fn new() -> Result<Self> {
         trace!("Gathering memory data…");
 
-        trace!("Reading {PROC_MEMINFO}…");
-        let proc_mem = std::fs::read_to_string("/proc/meminfo")
-            .inspect_err(|err| trace!("Unable to read {PROC_MEMINFO}: {err}"))
-            .context("unable to read /proc/meminfo")?;
+        trace!("Reading {}…", PROC_MEMINFO.display());
+        let proc_mem = std::fs::read_to_string(PROC_MEMINFO.as_path())
+            .inspect_err(|err| trace!("Unable to read {}: {err}", PROC_MEMINFO.display()))
+            .with_context(|| format!("unable to read {}", PROC_MEMINFO.display()))?;
 
         let total_mem = RE_MEM_TOTAL
             .captures(&proc_mem)
@@ -192,21 +193,19 @@ impl MemoryDevice {
     }
 
     fn virtual_dmi() -> Vec<Self> {
+        let dmi_path = sysfs("devices/virtual/dmi/id");
+
         let command = if *IS_FLATPAK {
             trace!("Executing udevadm outside Flatpak's sandbox…");
             Command::new(FLATPAK_SPAWN)
-                .args([
-                    "--host",
-                    "udevadm",
-                    "info",
-                    "-p",
-                    "/sys/devices/virtual/dmi/id",
-                ])
+                .args(["--host", "udevadm", "info", "-p"])
+                .arg(&dmi_path)
                 .output()
         } else {
             trace!("Executing udevadm…");
             Command::new("udevadm")
-                .args(["info", "-p", "/sys/devices/virtual/dmi/id"])
+                .args(["info", "-p"])
+                .arg(&dmi_path)
                 .output()
         };
 
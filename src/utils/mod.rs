@@ -4,10 +4,16 @@ use anyhow::{Context, Result};
 use gtk::glib::DateTime;
 use ini::Ini;
 use log::{debug, trace};
-use process_data::unix_as_millis;
+use process_data::{
+    fs_root::{SYSFS_ROOT, procfs},
+    unix_as_millis,
+};
+
+pub use process_data::rates::TICK_RATE;
 
 pub mod app;
 pub mod battery;
+pub mod companion;
 pub mod cpu;
 pub mod drive;
 pub mod gpu;
@@ -23,9 +29,12 @@ pub mod units;
 
 const FLATPAK_SPAWN: &str = "/usr/bin/flatpak-spawn";
 
+/// Container IDs are shortened to the same length as `docker ps` and `podman ps` do
+const SHORT_CONTAINER_ID_LENGTH: usize = 12;
+
 static BOOT_TIMESTAMP: LazyLock<Option<i64>> = LazyLock::new(|| {
     let unix_timestamp = (unix_as_millis() / 1000) as i64;
-    std::fs::read_to_string("/proc/uptime")
+    std::fs::read_to_string(procfs("uptime"))
         .context("unable to read /proc/uptime")
         .and_then(|procfs| {
             procfs
@@ -43,12 +52,18 @@ static BOOT_TIMESTAMP: LazyLock<Option<i64>> = LazyLock::new(|| {
         .ok()
 });
 
+/// Like `glob()`, but `pattern` (e.g. `class/drm/card?`) is relative to the sysfs root, which can be overridden using
+/// `RESOURCES_SYSFS_ROOT`
+pub fn sysfs_glob(pattern: &str) -> Result<glob::Paths, glob::PatternError> {
+    glob::glob(&format!(
+        "{}/{pattern}",
+        glob::Pattern::escape(&SYSFS_ROOT.to_string_lossy())
+    ))
+}
+
 static FLATPAK_APP_PATH: LazyLock<String> =
     LazyLock::new(|| flatpak_app_path().unwrap_or_else(|_| String::new()));
 
-pub static TICK_RATE: LazyLock<usize> =
-    LazyLock::new(|| sysconf::sysconf(sysconf::SysconfVariable::ScClkTck).unwrap_or(100) as usize);
-
 pub static NUM_CPUS: LazyLock<usize> = LazyLock::new(num_cpus::get);
 
 // Adapted from Mission Center: https://gitlab.com/mission-center-devs/mission-center/
@@ -79,6 +94,10 @@ pub fn flatpak_app_path() -> Result<String> {
         .to_string())
 }
 
+pub fn short_container_id(id: &str) -> String {
+    id.chars().take(SHORT_CONTAINER_ID_LENGTH).collect()
+}
+
 pub fn boot_time() -> Result<DateTime> {
     BOOT_TIMESTAMP
         .context("couldn't get boot timestamp")
@@ -2,6 +2,7 @@ use std::{
     ffi::OsString,
     fmt::Display,
     path::{Path, PathBuf},
+    sync::LazyLock,
 };
 
 use anyhow::{Context, Result};
@@ -9,10 +10,11 @@ use gtk::gio::{Icon, ThemedIcon};
 use log::trace;
 
 use crate::i18n::i18n;
+use process_data::fs_root::sysfs;
 
 use super::{pci::Device, read_uevent};
 
-const PATH_SYSFS: &str = "/sys/class/net";
+static PATH_SYSFS: LazyLock<PathBuf> = LazyLock::new(|| sysfs("class/net"));
 
 // this is a list because we don't look for exact matches but for if the device name starts with a certain string
 const INTERFACE_TYPE_MAP: &[(&str, InterfaceType)] = &[
@@ -151,9 +153,9 @@ impl NetworkInterface {
     pub fn get_sysfs_paths() -> Result<Vec<PathBuf>> {
         let mut list = Vec::new();
 
-        trace!("Finding entries in {PATH_SYSFS}");
+        trace!("Finding entries in {}", PATH_SYSFS.display());
 
-        let entries = std::fs::read_dir(PATH_SYSFS)?;
+        let entries = std::fs::read_dir(PATH_SYSFS.as_path())?;
         for entry in entries {
             let entry = entry?;
             let block_device = entry.file_name().to_string_lossy().to_string();
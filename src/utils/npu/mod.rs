@@ -14,7 +14,7 @@ use glob::glob;
 
 use crate::{
     i18n::i18n,
-    utils::{pci::Device, read_sysfs, read_uevent},
+    utils::{pci::Device, read_sysfs, read_uevent, sysfs_glob},
 };
 
 use self::{intel::IntelNpu, other::OtherNpu};
@@ -197,7 +197,7 @@ impl Npu {
         debug!("Searching for NPUs…");
 
         let mut npu_vec: Vec<Npu> = Vec::new();
-        for entry in glob("/sys/class/accel/accel?")?.flatten() {
+        for entry in sysfs_glob("class/accel/accel?")?.flatten() {
             if let Ok(npu) = Self::from_sysfs_path(entry) {
                 npu_vec.push(npu);
             }
@@ -1,11 +1,12 @@
 use lazy_regex::{Lazy, Regex, lazy_regex};
 use log::trace;
 
+use process_data::fs_root::procfs;
+
 use super::IS_FLATPAK;
 
 const PATH_OS_RELEASE: &str = "/etc/os-release";
 const PATH_OS_RELEASE_FLATPAK: &str = "/run/host/etc/os-release";
-const PATH_KERNEL_VERSION: &str = "/proc/sys/kernel/osrelease";
 
 static RE_PRETTY_NAME: Lazy<Regex> = lazy_regex!("PRETTY_NAME=\"(.*)\"");
 
@@ -29,7 +30,7 @@ impl OsInfo {
             .and_then(|captures| captures.get(1))
             .map(|capture| capture.as_str().trim().to_string());
 
-        let kernel_version = std::fs::read_to_string(PATH_KERNEL_VERSION)
+        let kernel_version = std::fs::read_to_string(procfs("sys/kernel/osrelease"))
             .ok()
             .map(|s| s.trim().to_string());
 
@@ -1,16 +1,25 @@
 use anyhow::{Context, Result, bail};
 use config::LIBEXECDIR;
-use log::{debug, error, info, trace};
-use process_data::{GpuIdentifier, GpuUsageStats, Niceness, ProcessData};
+use log::{debug, error, info, trace, warn};
+use nix::sys::signal::Signal;
+use process_data::{
+    CompanionError, CompanionRequest, GpuIdentifier, GpuUsageStats, Niceness, OomScoreAdj,
+    ProcessData, ProcessDataOptions, ThreadData,
+    cgroup::CgroupLimits,
+    environ::EnvironmentVariable,
+    open_file::OpenFile,
+    pidfd::ProcessIdentity,
+    rates,
+    scheduling::{IoPriority, SchedulingPolicy},
+    socket::Connection,
+};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     ffi::{OsStr, OsString},
-    io::{Read, Write},
-    process::{ChildStdin, ChildStdout, Command, Stdio},
-    sync::{LazyLock, Mutex},
-    time::Instant,
+    process::{Command, Output, Stdio},
+    sync::LazyLock,
+    time::{Duration, Instant},
 };
-use strum_macros::Display;
 
 use gtk::{
     gio::{Icon, ThemedIcon},
@@ -20,44 +29,10 @@ use gtk::{
 use crate::config;
 
 use super::{
-    FLATPAK_APP_PATH, FLATPAK_SPAWN, FiniteOr, IS_FLATPAK, NUM_CPUS, TICK_RATE, boot_time,
+    FLATPAK_APP_PATH, FLATPAK_SPAWN, FiniteOr, IS_FLATPAK, TICK_RATE, boot_time, companion,
+    settings::MemoryMetric,
 };
 
-static COMPANION_PROCESS: LazyLock<Mutex<(ChildStdin, ChildStdout)>> = LazyLock::new(|| {
-    let proxy_path = if *IS_FLATPAK {
-        format!(
-            "{}/libexec/resources/resources-processes",
-            FLATPAK_APP_PATH.as_str()
-        )
-    } else {
-        format!("{LIBEXECDIR}/resources-processes")
-    };
-
-    let child = if *IS_FLATPAK {
-        debug!("Spawning resources-processes in Flatpak mode ({proxy_path})");
-        Command::new(FLATPAK_SPAWN)
-            .args(["--host", proxy_path.as_str()])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()
-            .unwrap()
-    } else {
-        debug!("Spawning resources-processes in native mode ({proxy_path})");
-        Command::new(proxy_path)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()
-            .unwrap()
-    };
-
-    let stdin = child.stdin.unwrap();
-    let stdout = child.stdout.unwrap();
-
-    Mutex::new((stdin, stdout))
-});
-
 /// Represents a process that can be found within procfs.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Process {
@@ -69,17 +44,42 @@ pub struct Process {
     pub timestamp_last: u64,
     pub read_bytes_last: Option<u64>,
     pub write_bytes_last: Option<u64>,
+    pub read_chars_last: Option<u64>,
+    pub write_chars_last: Option<u64>,
+    pub read_syscalls_last: Option<u64>,
+    pub write_syscalls_last: Option<u64>,
+    pub major_faults_last: u64,
+    pub context_switches_last: Option<u64>,
     pub gpu_usage_stats_last: BTreeMap<GpuIdentifier, GpuUsageStats>,
+    /// Key: TID of the thread, Value: its combined user and system CPU time of the last refresh
+    pub thread_cpu_time_last: HashMap<libc::pid_t, u64>,
     pub display_name: String,
 }
 
 // TODO: Better name?
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProcessAction {
     TERM,
     STOP,
     KILL,
     CONT,
+    /// Sends any other signal, e.g. `SIGHUP` to make a daemon reload its configuration
+    Signal(Signal),
+}
+
+/// The format is the one understood by `resources-kill`, i.e. the signal name without the `SIG` prefix
+impl std::fmt::Display for ProcessAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessAction::TERM => write!(f, "TERM"),
+            ProcessAction::STOP => write!(f, "STOP"),
+            ProcessAction::KILL => write!(f, "KILL"),
+            ProcessAction::CONT => write!(f, "CONT"),
+            ProcessAction::Signal(signal) => {
+                write!(f, "{}", signal.as_str().trim_start_matches("SIG"))
+            }
+        }
+    }
 }
 
 impl Process {
@@ -89,44 +89,107 @@ impl Process {
     ///
     /// Will return `Err` if there are problems traversing and
     /// parsing procfs
-    pub fn all_data() -> Result<Vec<ProcessData>> {
+    pub fn all_data(options: ProcessDataOptions) -> Result<Vec<ProcessData>> {
         trace!("all_data() called");
 
         let start = Instant::now();
-        let output = {
-            trace!("Acquiring companion process lock");
-            let mut process = COMPANION_PROCESS.lock().unwrap();
-            trace!("Writing b\"\\n\" into companion process stdin");
-            let _ = process.0.write_all(b"\n");
-            trace!("Flushing");
-            let _ = process.0.flush();
+        let process_data = companion::process_data(options);
 
-            let mut len_bytes = [0_u8; (usize::BITS / 8) as usize];
+        let elapsed = start.elapsed();
+        trace!("all_data() done in {elapsed:.2?}");
 
-            trace!("Reading companion process output length as little-endian");
-            process.1.read_exact(&mut len_bytes)?;
+        process_data
+    }
 
-            let len = usize::from_le_bytes(len_bytes);
-            trace!("Companion process output is {len} bytes long");
+    /// Like `all_data()`, but the data is gathered by a companion process on its own every `interval`, so this doesn't
+    /// have to wait for procfs to be scanned. Returns `Ok(None)` if there hasn't been a new snapshot since the last
+    /// call. Falls back to `all_data()` whenever the stream is unavailable, e.g. while it waits to be restarted.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there are problems traversing and
+    /// parsing procfs
+    pub fn latest_data(
+        interval: Duration,
+        options: ProcessDataOptions,
+    ) -> Result<Option<Vec<ProcessData>>> {
+        if companion::streaming_supported() {
+            match companion::latest_process_data(interval, options) {
+                Err(error) => {
+                    warn!("Unable to stream process data, requesting it instead\n{error}");
+                }
+                result => return result,
+            }
+        }
 
-            let mut output_bytes = vec![0; len];
-            trace!("Reading companion process output");
-            process.1.read_exact(&mut output_bytes)?;
+        Self::all_data(options).map(Some)
+    }
 
-            output_bytes
-        };
+    /// Returns the open file descriptors of the process with the PID `pid`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the companion process can't be reached or if the file descriptors
+    /// of the process can't be read, e.g. because it belongs to another user
+    pub fn open_files(pid: libc::pid_t) -> Result<Vec<OpenFile>> {
+        companion::request(CompanionRequest::OpenFiles(pid))
+    }
 
-        let elapsed = start.elapsed();
-        trace!("Companion process was done in {elapsed:.2?}");
+    /// Returns all TCP and UDP sockets together with the PIDs of the processes owning them.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the companion process can't be reached
+    pub fn connections() -> Result<Vec<Connection>> {
+        companion::request(CompanionRequest::Connections)
+    }
 
-        trace!("Parsing companion process output");
-        let parsed =
-            rmp_serde::from_slice(&output).context("unable to decode companion process output");
+    /// Returns the resource usage and limits of the cgroup v2 with the path `cgroup_path`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the companion process can't be reached or if the cgroup doesn't exist
+    pub fn cgroup_limits<S: Into<String>>(cgroup_path: S) -> Result<CgroupLimits> {
+        companion::request(CompanionRequest::CgroupLimits(cgroup_path.into()))
+    }
 
-        let elapsed = start.elapsed();
-        trace!("all_data() done in {elapsed:.2?}");
+    /// Returns the environment variables of the process with the PID `pid`. If the companion process isn't allowed
+    /// to read them, e.g. because the process belongs to another user, it is run again through pkexec.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the environment couldn't be read, even with elevated privileges
+    pub fn environ(pid: libc::pid_t) -> Result<Vec<EnvironmentVariable>> {
+        match companion::request(CompanionRequest::Environ(pid)) {
+            Ok(environ) => Ok(environ),
+            // only retry if the companion process could be asked, but failed to read the environment
+            Err(error) if error.downcast_ref::<CompanionError>().is_some() => {
+                debug!(
+                    "Companion process couldn't read environment of {pid} ({error}), retrying with pkexec"
+                );
+
+                let output = Self::maybe_pkexec_output(
+                    companion::companion_path(),
+                    ["--environ".to_string(), pid.to_string()],
+                )?;
 
-        parsed
+                if !output.status.success() {
+                    bail!(
+                        "unable to read environment of {pid}: {error}, return code: {:?}",
+                        output.status.code()
+                    );
+                }
+
+                // the output is prefixed with its length, just like answers to requests
+                let payload = output
+                    .stdout
+                    .get((usize::BITS / 8) as usize..)
+                    .context("companion process output too short")?;
+
+                rmp_serde::from_slice(payload).context("unable to decode companion process output")
+            }
+            Err(error) => Err(error),
+        }
     }
 
     pub fn from_process_data(process_data: ProcessData) -> Self {
@@ -156,6 +219,12 @@ impl Process {
             None
         };
 
+        let read_chars_last = process_data.read_chars.map(|_| 0);
+        let write_chars_last = process_data.write_chars.map(|_| 0);
+        let read_syscalls_last = process_data.read_syscalls.map(|_| 0);
+        let write_syscalls_last = process_data.write_syscalls.map(|_| 0);
+        let context_switches_last = process_data.context_switches().map(|_| 0);
+
         let display_name = if executable_name.starts_with(&process_data.comm) {
             executable_name.clone()
         } else {
@@ -171,7 +240,14 @@ impl Process {
             timestamp_last: 0,
             read_bytes_last,
             write_bytes_last,
+            read_chars_last,
+            write_chars_last,
+            read_syscalls_last,
+            write_syscalls_last,
+            major_faults_last: 0,
+            context_switches_last,
             gpu_usage_stats_last: Default::default(),
+            thread_cpu_time_last: Default::default(),
             display_name,
         }
     }
@@ -181,84 +257,70 @@ impl Process {
         command: S,
         args: I,
     ) -> Result<i32> {
+        Self::maybe_pkexec_output(command, args)?
+            .status
+            .code()
+            .context("no status code?")
+    }
+
+    /// Like `maybe_pkexec_command()` but returns the whole `Output` of the command that ran last
+    fn maybe_pkexec_output<S: AsRef<OsStr>, I: IntoIterator<Item = S>>(
+        command: S,
+        args: I,
+    ) -> Result<Output> {
         let args = args
             .into_iter()
             .map(|s| s.as_ref().to_os_string())
             .collect::<Vec<_>>();
 
-        let status_code = if *IS_FLATPAK {
-            debug!(
-                "Executing command: {} --host {} {}",
-                FLATPAK_SPAWN,
-                command.as_ref().to_string_lossy(),
-                args.join(&OsString::from(" ")).to_string_lossy()
-            );
-
-            Command::new(FLATPAK_SPAWN)
-                .arg("--host")
-                .arg(command.as_ref())
-                .args(args.clone())
-                .output()?
-                .status
-                .code()
-                .context("no status code?")?
-        } else {
-            debug!(
-                "Executing command: {} {}",
-                command.as_ref().to_string_lossy(),
-                args.join(&OsString::from(" ")).to_string_lossy()
-            );
-
-            Command::new(command.as_ref())
-                .args(args.clone())
-                .output()?
-                .status
-                .code()
-                .context("no status code?")?
-        };
+        let output = Self::command_output(command.as_ref(), &args, false)?;
 
-        if status_code == libc::EPERM || status_code == libc::EACCES {
-            let pkexec_status_code = if *IS_FLATPAK {
-                debug!(
-                    "Received EPERM, executing command: {} --host pkexec --disable-internal-agent {} {}",
-                    FLATPAK_SPAWN,
-                    command.as_ref().to_string_lossy(),
-                    args.join(&OsString::from(" ")).to_string_lossy()
-                );
-                Command::new(FLATPAK_SPAWN)
-                    .args(["--host", "pkexec", "--disable-internal-agent"])
-                    .arg(command)
-                    .args(args)
-                    .output()?
-                    .status
-                    .code()
-                    .context("no status code?")?
-            } else {
-                debug!(
-                    "Received EPERM or EACCES, executing command: pkexec --disable-internal-agent {} {}",
-                    command.as_ref().to_string_lossy(),
-                    args.join(&OsString::from(" ")).to_string_lossy()
-                );
-                Command::new("pkexec")
-                    .arg("--disable-internal-agent")
-                    .arg(command)
-                    .args(args)
-                    .output()?
-                    .status
-                    .code()
-                    .context("no status code?")?
-            };
+        let status_code = output.status.code().context("no status code?")?;
 
-            Ok(pkexec_status_code)
+        if status_code == libc::EPERM || status_code == libc::EACCES {
+            debug!("Received EPERM or EACCES, retrying with pkexec");
+            Self::command_output(command.as_ref(), &args, true)
         } else {
-            Ok(status_code)
+            Ok(output)
+        }
+    }
+
+    /// Runs a command (on the host if we're running as a Flatpak and through pkexec if `privileged`) and returns its
+    /// `Output`
+    fn command_output(command: &OsStr, args: &[OsString], privileged: bool) -> Result<Output> {
+        let mut program: Vec<OsString> = Vec::new();
+
+        if *IS_FLATPAK {
+            program.extend([FLATPAK_SPAWN.into(), "--host".into()]);
         }
+
+        if privileged {
+            program.extend(["pkexec".into(), "--disable-internal-agent".into()]);
+        }
+
+        program.push(command.to_os_string());
+
+        debug!(
+            "Executing command: {} {}",
+            program.join(&OsString::from(" ")).to_string_lossy(),
+            args.join(&OsString::from(" ")).to_string_lossy()
+        );
+
+        Ok(Command::new(&program[0])
+            .args(&program[1..])
+            .args(args)
+            .output()?)
     }
 
+    /// Adjusts the niceness and affinity of the process `identity` and all of its threads. `io_priority`,
+    /// `scheduling_policy` and `oom_score_adj` are left untouched if they're `None`.
     pub fn adjust<I: IntoIterator<Item = bool>>(
-        &self,
+        identity: ProcessIdentity,
         niceness: Niceness,
         affinity: I,
+        io_priority: Option<IoPriority>,
+        scheduling_policy: Option<SchedulingPolicy>,
+        oom_score_adj: Option<OomScoreAdj>,
     ) -> Result<()> {
         let adjust_path = if *IS_FLATPAK {
             format!(
@@ -274,21 +336,40 @@ impl Process {
             .map(|b| if b { '1' } else { '0' })
             .collect::<String>();
 
-        debug!("Trying to adjust with niceness = {niceness} and affinity = {affinity_string}");
+        // resources-adjust keeps the current values when given "-"
+        let io_priority_string = io_priority.map_or_else(|| "-".to_string(), |io| io.to_string());
+        let scheduling_policy_string =
+            scheduling_policy.map_or_else(|| "-".to_string(), |policy| policy.to_string());
+        let oom_score_adj_string =
+            oom_score_adj.map_or_else(|| "-".to_string(), |adj| adj.to_string());
+
+        debug!(
+            "Trying to adjust with niceness = {niceness}, affinity = {affinity_string}, I/O priority = \
+            {io_priority_string}, scheduling policy = {scheduling_policy_string} and OOM score adjustment = \
+            {oom_score_adj_string}"
+        );
 
         let result = Self::maybe_pkexec_command(
             adjust_path,
             [
-                self.data.pid.to_string(),
+                identity.to_string(),
                 niceness.to_string(),
                 affinity_string,
+                io_priority_string,
+                scheduling_policy_string,
+                oom_score_adj_string,
             ],
         );
 
         if let Ok(return_code) = result {
             if return_code == 0 {
-                info!("Successfully adjusted {}", self.data.pid);
+                info!("Successfully adjusted {}", identity.pid);
                 Ok(())
+            } else if return_code == libc::ESTALE {
+                bail!(
+                    "refusing to adjust {}, its PID has been recycled by another process",
+                    identity.pid
+                )
             } else {
                 bail!("non-zero return code: {return_code}")
             }
@@ -299,9 +380,34 @@ impl Process {
         }
     }
 
+    /// Returns the PID together with the start time of this process, which `resources-kill` and `resources-adjust`
+    /// use to make sure that they don't act on another process that has been given the same PID in the meantime
+    pub fn identity(&self) -> ProcessIdentity {
+        ProcessIdentity {
+            pid: self.data.pid,
+            start_time: self.data.starttime,
+        }
+    }
+
     pub fn execute_process_action(&self, action: ProcessAction) -> Result<()> {
+        Self::execute_process_action_on([self.identity()], action).map(|_| ())
+    }
+
+    /// Executes `action` on the processes `identities` in the given order using a single invocation of
+    /// `resources-kill`, so that a whole process tree can be signalled leaf-first with at most one authentication.
+    /// Returns how many processes have actually been signalled, i.e. without those that had exited already.
+    pub fn execute_process_action_on<I: IntoIterator<Item = ProcessIdentity>>(
+        identities: I,
+        action: ProcessAction,
+    ) -> Result<usize> {
         let action_string = action.to_string();
 
+        let identities = identities.into_iter().collect::<Vec<_>>();
+
+        if identities.is_empty() {
+            return Ok(0);
+        }
+
         let kill_path = if *IS_FLATPAK {
             format!(
                 "{}/libexec/resources/resources-kill",
@@ -311,89 +417,176 @@ impl Process {
             format!("{LIBEXECDIR}/resources-kill")
         };
 
-        let result = Self::maybe_pkexec_command(
-            kill_path,
-            [self.data.pid.to_string(), action_string.clone()],
-        );
+        let mut remaining = identities.as_slice();
+        let mut privileged = false;
+        let mut signalled = 0;
+
+        loop {
+            let pids_string = remaining
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+
+            let output = Self::command_output(
+                kill_path.as_ref(),
+                &[pids_string.clone().into(), action_string.clone().into()],
+                privileged,
+            )
+            .inspect_err(|err| {
+                error!(
+                    "Unknown error while trying to send {action_string} to {pids_string}\n{err}\n{}",
+                    err.backtrace()
+                );
+            })?;
 
-        if let Ok(return_code) = result {
-            if return_code == 0 || return_code == 3 {
-                info!("Successfully {action_string}ed {}", self.data.pid);
-                Ok(())
+            let return_code = output.status.code().context("no status code?")?;
+
+            // resources-kill prints every process it's done with, in order, so that processes that have been signalled
+            // already aren't signalled a second time when the rest is retried with pkexec
+            let handled = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.split_once(' '))
+                .filter_map(|(_, errno)| errno.parse::<i32>().ok())
+                .collect::<Vec<_>>();
+
+            signalled += handled.iter().filter(|errno| **errno == 0).count();
+            remaining = &remaining[handled.len().min(remaining.len())..];
+
+            if (return_code == libc::EPERM || return_code == libc::EACCES)
+                && !privileged
+                && !remaining.is_empty()
+            {
+                debug!(
+                    "Not allowed to send {action_string} to all of {pids_string}, retrying the remaining {} with pkexec",
+                    remaining.len()
+                );
+                privileged = true;
+                continue;
+            }
+
+            return if return_code == 0 || return_code == libc::ESRCH {
+                info!("Successfully sent {action_string} to {signalled} of {pids_string}");
+                Ok(signalled)
+            } else if return_code == libc::ESTALE {
+                error!(
+                    "Refused to send {action_string} to {pids_string}, at least one PID has been recycled by another process"
+                );
+                bail!("PID has been recycled")
             } else {
                 error!(
-                    "Couldn't {action_string} {}, return code: {return_code}",
-                    self.data.pid
+                    "Couldn't send {action_string} to {pids_string}, return code: {return_code}"
                 );
                 bail!("non-zero return code: {return_code}")
-            }
-        } else if let Err(err) = result {
-            error!(
-                "Unknown error while trying to {action_string} {}\n{err}\n{}",
-                self.data.pid,
-                err.backtrace()
-            );
-            Err(err)
-        } else {
-            bail!("unknown error")
+            };
         }
     }
 
-    #[must_use]
     pub fn cpu_time_ratio(&self) -> f32 {
-        if self.cpu_time_last == 0 {
-            0.0
-        } else {
-            let delta_cpu_time = (self
-                .data
+        rates::cpu_time_ratio(
+            self.data
                 .user_cpu_time
-                .saturating_add(self.data.system_cpu_time))
-            .saturating_sub(self.cpu_time_last) as f32
-                * 1000.0;
-            let delta_time = self.data.timestamp.saturating_sub(self.timestamp_last);
-
-            (delta_cpu_time
-                / (delta_time
-                    .saturating_mul(*TICK_RATE as u64)
-                    .saturating_mul(*NUM_CPUS as u64)) as f32)
-                .finite_or_default()
+                .saturating_add(self.data.system_cpu_time),
+            self.cpu_time_last,
+            self.data.timestamp,
+            self.timestamp_last,
+        )
+    }
+
+    /// Returns the memory usage of this process according to `memory_metric`, falls back to resident minus shared
+    /// memory if `smaps_rollup` was unavailable
+    #[must_use]
+    pub fn memory_usage(&self, memory_metric: MemoryMetric) -> usize {
+        match (memory_metric, self.data.smaps_rollup) {
+            (MemoryMetric::Proportional, Some(smaps_rollup)) => smaps_rollup.pss,
+            (MemoryMetric::Unique, Some(smaps_rollup)) => smaps_rollup.uss,
+            _ => self.data.memory_usage,
         }
     }
 
+    #[must_use]
+    pub fn thread_cpu_time_ratio(&self, thread: &ThreadData) -> f32 {
+        let cpu_time_last = self
+            .thread_cpu_time_last
+            .get(&thread.tid)
+            .copied()
+            .unwrap_or_default();
+
+        rates::cpu_time_ratio(
+            thread.user_cpu_time.saturating_add(thread.system_cpu_time),
+            cpu_time_last,
+            self.data.timestamp,
+            self.timestamp_last,
+        )
+    }
+
     #[must_use]
     pub fn read_speed(&self) -> Option<f64> {
-        if let (Some(read_bytes), Some(read_bytes_last)) =
-            (self.data.read_bytes, self.read_bytes_last)
-        {
-            if self.timestamp_last == 0 {
-                Some(0.0)
-            } else {
-                let bytes_delta = read_bytes.saturating_sub(read_bytes_last) as f64;
-                let time_delta = self.data.timestamp.saturating_sub(self.timestamp_last) as f64;
-                Some((bytes_delta / time_delta) * 1000.0)
-            }
-        } else {
-            None
-        }
+        self.counter_rate(self.data.read_bytes, self.read_bytes_last)
     }
 
     #[must_use]
     pub fn write_speed(&self) -> Option<f64> {
-        if let (Some(write_bytes), Some(write_bytes_last)) =
-            (self.data.write_bytes, self.write_bytes_last)
-        {
-            if self.timestamp_last == 0 {
-                Some(0.0)
-            } else {
-                let bytes_delta = write_bytes.saturating_sub(write_bytes_last) as f64;
-                let time_delta = self.data.timestamp.saturating_sub(self.timestamp_last) as f64;
-                Some((bytes_delta / time_delta) * 1000.0)
-            }
+        self.counter_rate(self.data.write_bytes, self.write_bytes_last)
+    }
+
+    /// Returns the number of major page faults per second since the last refresh
+    #[must_use]
+    pub fn major_fault_rate(&self) -> f64 {
+        if self.timestamp_last == 0 {
+            0.0
         } else {
-            None
+            let faults_delta = self
+                .data
+                .major_faults
+                .saturating_sub(self.major_faults_last) as f64;
+            let time_delta = self.data.timestamp.saturating_sub(self.timestamp_last) as f64;
+            (faults_delta / time_delta) * 1000.0
         }
     }
 
+    /// Returns the number of voluntary and involuntary context switches per second since the last refresh
+    #[must_use]
+    pub fn context_switch_rate(&self) -> Option<f64> {
+        self.counter_rate(self.data.context_switches(), self.context_switches_last)
+    }
+
+    /// Returns the bytes per second read using read-like syscalls, regardless of whether they hit the storage layer
+    #[must_use]
+    pub fn read_chars_speed(&self) -> Option<f64> {
+        self.counter_rate(self.data.read_chars, self.read_chars_last)
+    }
+
+    /// Returns the bytes per second written using write-like syscalls, regardless of whether they hit the storage
+    /// layer
+    #[must_use]
+    pub fn write_chars_speed(&self) -> Option<f64> {
+        self.counter_rate(self.data.write_chars, self.write_chars_last)
+    }
+
+    #[must_use]
+    pub fn read_syscall_rate(&self) -> Option<f64> {
+        self.counter_rate(self.data.read_syscalls, self.read_syscalls_last)
+    }
+
+    #[must_use]
+    pub fn write_syscall_rate(&self) -> Option<f64> {
+        self.counter_rate(self.data.write_syscalls, self.write_syscalls_last)
+    }
+
+    /// Returns how much a monotonically increasing counter grew per second since the last refresh, `None` if the
+    /// counter is not available
+    fn counter_rate(&self, current: Option<u64>, last: Option<u64>) -> Option<f64> {
+        let (current, last) = current.zip(last)?;
+
+        Some(rates::per_second(
+            current,
+            last,
+            self.data.timestamp,
+            self.timestamp_last,
+        ))
+    }
+
     #[must_use]
     pub fn gpu_usage(&self) -> f32 {
         let mut returned_gpu_usage = 0.0;
@@ -147,6 +147,22 @@ pub enum SidebarMeterType {
     Graph,
 }
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, EnumString, Display, Hash, FromRepr)]
+pub enum MemoryMetric {
+    #[default]
+    ResidentMinusShared,
+    Proportional,
+    Unique,
+}
+
+impl MemoryMetric {
+    /// Whether this metric is calculated from `smaps_rollup`, which is expensive to read
+    pub const fn needs_smaps_rollup(&self) -> bool {
+        matches!(self, MemoryMetric::Proportional | MemoryMetric::Unique)
+    }
+}
+
 #[derive(Clone, Debug, Hash)]
 pub struct Settings(gio::Settings);
 
@@ -253,6 +269,27 @@ impl Settings {
         })
     }
 
+    pub fn memory_metric(&self) -> MemoryMetric {
+        MemoryMetric::from_str(self.string("memory-metric").as_str()).unwrap_or_default()
+    }
+
+    pub fn set_memory_metric(&self, value: MemoryMetric) -> Result<(), glib::error::BoolError> {
+        debug!("Setting memory-metric to {}", value);
+        self.set_string("memory-metric", &value.to_string())
+    }
+
+    pub fn connect_memory_metric<F: Fn(MemoryMetric) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("memory-metric"), move |settings, _key| {
+            f(
+                MemoryMetric::from_str(settings.string("memory-metric").as_str())
+                    .unwrap_or_default(),
+            );
+        })
+    }
+
     // the following three functions are kept for compatibility reasons and for not having an oddly named function
     // called "set_is_maximized" generated by the macro
     pub fn maximized(&self) -> bool {
@@ -359,6 +396,13 @@ impl Settings {
         apps_show_decoder,
         apps_show_swap,
         apps_show_combined_memory,
+        apps_show_char_read_speed,
+        apps_show_char_read_total,
+        apps_show_char_write_speed,
+        apps_show_char_write_total,
+        apps_show_read_syscalls,
+        apps_show_write_syscalls,
+        apps_show_cancelled_write_total,
         processes_show_id,
         processes_show_user,
         processes_show_memory,
@@ -377,6 +421,22 @@ impl Settings {
         processes_show_priority,
         processes_show_swap,
         processes_show_combined_memory,
+        processes_show_state,
+        processes_show_major_fault_rate,
+        processes_show_context_switch_rate,
+        processes_show_char_read_speed,
+        processes_show_char_read_total,
+        processes_show_char_write_speed,
+        processes_show_char_write_total,
+        processes_show_read_syscalls,
+        processes_show_write_syscalls,
+        processes_show_cancelled_write_total,
+        processes_show_io_priority,
+        processes_show_scheduling_policy,
+        processes_show_oom_score,
+        processes_show_tree,
+        processes_show_subtree_usage,
+        processes_elevated_monitoring,
         show_logical_cpus,
         show_graph_grids,
         normalize_cpu_usage,